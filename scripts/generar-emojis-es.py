@@ -0,0 +1,75 @@
+#!/usr/bin/env python3
+"""Genera src/text/emojis_es.rs con los nombres cortos en español de CLDR.
+
+Lee `annotations/es.xml` y `annotationsDerived/es.xml` (el nombre "tts" de
+cada emoji) de una copia local de CLDR o, si no se indica, de la versión
+publicada en GitHub.
+
+Uso:
+    ./scripts/generar-emojis-es.py                 # descarga CLDR
+    ./scripts/generar-emojis-es.py --cldr ~/cldr   # copia local
+"""
+
+import argparse
+import pathlib
+import urllib.request
+import xml.etree.ElementTree as ET
+
+VERSION_CLDR = "46"
+URL_CLDR = "https://raw.githubusercontent.com/unicode-org/cldr/release-{}/common/{}/es.xml"
+ARCHIVOS = ["annotations", "annotationsDerived"]
+SALIDA = pathlib.Path(__file__).resolve().parent.parent / "src" / "text" / "emojis_es.rs"
+
+
+def leer(cldr, carpeta):
+    if cldr:
+        return (pathlib.Path(cldr) / "common" / carpeta / "es.xml").read_bytes()
+    with urllib.request.urlopen(URL_CLDR.format(VERSION_CLDR, carpeta)) as respuesta:
+        return respuesta.read()
+
+
+def nombres(contenido):
+    for anotacion in ET.fromstring(contenido).iter("annotation"):
+        if anotacion.get("type") == "tts" and anotacion.text:
+            # Sin selectores de variante, como los busca `nombre_es`; sin los
+            # dos puntos de "tecla: 1", como los nombres en inglés
+            emoji = anotacion.get("cp").replace("\ufe0f", "")
+            nombre = anotacion.text.strip().replace("bandera: ", "bandera de ")
+            yield emoji, nombre.replace(":", "")
+
+
+def literal(texto):
+    return '"' + texto.replace("\\", "\\\\").replace('"', '\\"') + '"'
+
+
+def main():
+    argumentos = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    argumentos.add_argument("--cldr", help="copia local del repositorio de CLDR")
+    argumentos = argumentos.parse_args()
+
+    tabla = {}
+    for carpeta in ARCHIVOS:
+        for emoji, nombre in nombres(leer(argumentos.cldr, carpeta)):
+            tabla.setdefault(emoji, nombre)
+
+    origen = "copia local" if argumentos.cldr else "release-" + VERSION_CLDR
+    lineas = [
+        "//! Nombres cortos en español de los emojis, de CLDR",
+        "//! (`annotations/es.xml` y `annotationsDerived/es.xml`, {}).".format(origen),
+        "//!",
+        "//! Generado por `scripts/generar-emojis-es.py`: no editar a mano.",
+        "",
+        "/// Nombre de cada emoji, sin selectores de variante, ordenados para",
+        "/// buscar con `binary_search_by`.",
+        "pub const NOMBRES_ES: &[(&str, &str)] = &[",
+    ]
+    # El orden de Python (por punto de código) es el de los `&str` de Rust
+    for emoji in sorted(tabla):
+        lineas.append("    ({}, {}),".format(literal(emoji), literal(tabla[emoji])))
+    lineas.append("];")
+    SALIDA.write_text("\n".join(lineas) + "\n", encoding="utf-8")
+    print("{} nombres en {}".format(len(tabla), SALIDA))
+
+
+if __name__ == "__main__":
+    main()
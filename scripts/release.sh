@@ -64,6 +64,8 @@ fi
 # Copiar documentación
 cp README.md "$RELEASE_DIR/"
 cp LICENSE "$RELEASE_DIR/"
+cp tray_narrator.example.toml "$RELEASE_DIR/"
+cp lexico.example.toml "$RELEASE_DIR/"
 
 # Crear zip
 cd release
@@ -1,18 +1,36 @@
 //! Hilo de reproducción de audio con rodio.
 
-use std::fs::File;
-use std::io::BufReader;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc::Receiver;
 use std::time::Duration;
 
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::buffer::SamplesBuffer;
+use rodio::{OutputStream, Sink};
 
-use crate::config::RUTA_TEMP_WAV;
 use crate::logging::log;
+use crate::profiles;
 use crate::state::{
     ComandoAudio, DEBE_SALIR, ESTADO_AUDIO, ESTADO_IDLE, ESTADO_PAUSADO, ESTADO_REPRODUCIENDO,
+    NARRACION_ACTUAL, REPRODUCCIONES_ATENDIDAS,
 };
+use crate::tts::AudioSintetizado;
+
+/// Añade el audio sintetizado al final del sink: en memoria o en streaming.
+fn anadir(sink: &Sink, audio: AudioSintetizado) {
+    match audio {
+        AudioSintetizado::Completo(audio) => sink.append(SamplesBuffer::new(
+            audio.canales,
+            audio.frecuencia_muestreo,
+            audio.muestras,
+        )),
+        AudioSintetizado::EnCurso(flujo) => sink.append(flujo),
+    }
+}
+
+/// `true` si el comando pertenece a una narración ya cancelada o sustituida.
+fn es_obsoleto(narracion: u64) -> bool {
+    narracion != NARRACION_ACTUAL.load(Ordering::SeqCst)
+}
 
 /// Hilo principal de reproducción de audio.
 ///
@@ -39,28 +57,43 @@ pub fn hilo_audio(receiver: Receiver<ComandoAudio>) {
         }
 
         match receiver.recv_timeout(Duration::from_millis(100)) {
-            Ok(ComandoAudio::Reproducir) => {
+            Ok(ComandoAudio::Reproducir { narracion, audio }) => {
                 log("Comando Reproducir");
 
-                if let Some(ref sink) = sink_actual {
-                    sink.stop();
+                if es_obsoleto(narracion) {
+                    log("Narración cancelada: fragmento descartado");
+                } else {
+                    if let Some(ref sink) = sink_actual {
+                        sink.stop();
+                    }
+
+                    match Sink::try_new(&stream_handle) {
+                        Ok(sink) => {
+                            log("Reproduciendo...");
+                            sink.set_volume(profiles::volumen_actual());
+                            anadir(&sink, audio);
+                            ESTADO_AUDIO.store(ESTADO_REPRODUCIENDO, Ordering::SeqCst);
+                            sink_actual = Some(sink);
+                        }
+                        Err(e) => log(&format!("Error sink: {}", e)),
+                    }
                 }
+                REPRODUCCIONES_ATENDIDAS.fetch_add(1, Ordering::SeqCst);
+            }
 
-                match Sink::try_new(&stream_handle) {
-                    Ok(sink) => match File::open(RUTA_TEMP_WAV) {
-                        Ok(archivo) => match Decoder::new(BufReader::new(archivo)) {
-                            Ok(fuente) => {
-                                log("Reproduciendo...");
-                                sink.append(fuente);
-                                ESTADO_AUDIO.store(ESTADO_REPRODUCIENDO, Ordering::SeqCst);
-                                sink_actual = Some(sink);
-                            }
-                            Err(e) => log(&format!("Error decoder: {}", e)),
-                        },
-                        Err(e) => log(&format!("Error abriendo WAV: {}", e)),
-                    },
-                    Err(e) => log(&format!("Error sink: {}", e)),
+            Ok(ComandoAudio::Encolar { narracion, audio }) => {
+                // Sin sink la narración se detuvo (o falló el primer fragmento)
+                match sink_actual {
+                    Some(ref sink) if !es_obsoleto(narracion) => {
+                        anadir(sink, audio);
+                        // Si el fragmento anterior ya había terminado, sigue sonando
+                        if ESTADO_AUDIO.load(Ordering::SeqCst) == ESTADO_IDLE {
+                            ESTADO_AUDIO.store(ESTADO_REPRODUCIENDO, Ordering::SeqCst);
+                        }
+                    }
+                    _ => log("Narración cancelada: fragmento descartado"),
                 }
+                REPRODUCCIONES_ATENDIDAS.fetch_add(1, Ordering::SeqCst);
             }
 
             Ok(ComandoAudio::Detener) => {
@@ -0,0 +1,359 @@
+//! Interfaz de línea de comandos.
+//!
+//! Parser escrito a mano: las opciones son pocas y así el binario no carga
+//! con una dependencia de parsing de argumentos.
+
+use std::path::PathBuf;
+
+use crate::config::{
+    self, formatear_velocidad, Motor, Sobrescrituras, VELOCIDAD_MAXIMA_X100, VELOCIDAD_MINIMA_X100,
+    VERSION,
+};
+
+/// Texto de ayuda mostrado con `--help`.
+pub const AYUDA: &str = "\
+TrayNarrator - Lector TTS ultraligero usando Piper TTS
+
+Uso:
+  tray_narrator [opciones]                  Modo tray (por defecto)
+  tray_narrator speak [opciones] <texto>    Lee el texto y termina
+  tray_narrator speak [opciones] --file <ruta>
+  tray_narrator speak [opciones] -          Lee el texto de la entrada estándar
+  tray_narrator doctor [opciones]           Comprueba la instalación de Piper y el audio
+
+Opciones:
+  --config <ruta>   Archivo de configuración alternativo
+  --voice <voz>     Voz: modelo .onnx en Piper, nombre en espeak-ng
+  --engine <motor>  Motor de síntesis: piper, espeak o mock
+  --speed <xN>      Velocidad de lectura, p. ej. x1.5
+  --no-tray         No mostrar el icono en la bandeja del sistema
+  --no-hotkeys      No registrar los atajos de teclado globales
+  -h, --help        Muestra esta ayuda
+  -V, --version     Muestra la versión
+";
+
+/// Origen del texto a leer en modo `speak`
+#[derive(Debug, PartialEq)]
+pub enum FuenteTexto {
+    /// Texto pasado directamente como argumento
+    Texto(String),
+    /// Archivo de texto (`--file`)
+    Archivo(PathBuf),
+    /// Entrada estándar (`-`)
+    Stdin,
+}
+
+/// Modo de ejecución elegido
+#[derive(Debug, PartialEq)]
+pub enum Comando {
+    /// Icono en la bandeja + hotkeys (modo por defecto)
+    Tray,
+    /// Leer un texto una vez y terminar
+    Hablar(FuenteTexto),
+    /// Comprobar la instalación y mostrar un informe
+    Diagnostico,
+}
+
+/// Argumentos ya interpretados
+#[derive(Debug, PartialEq)]
+pub struct Argumentos {
+    pub comando: Comando,
+    /// `--config`: archivo de configuración alternativo
+    pub ruta_config: Option<PathBuf>,
+    /// `--voice`: voz que sustituye a `ruta_modelo` / `voz_espeak`
+    pub voz: Option<PathBuf>,
+    /// `--engine`: motor que sustituye a `motor`
+    pub motor: Option<Motor>,
+    /// `--speed`: velocidad inicial (multiplicador × 100)
+    pub velocidad: Option<u32>,
+    /// `--no-tray`
+    pub sin_tray: bool,
+    /// `--no-hotkeys`
+    pub sin_hotkeys: bool,
+}
+
+impl Argumentos {
+    /// Opciones que sustituyen a las del archivo de configuración, también
+    /// tras una recarga en caliente.
+    pub fn sobrescrituras(&self) -> Sobrescrituras {
+        Sobrescrituras {
+            voz: self.voz.clone(),
+            motor: self.motor,
+        }
+    }
+
+    /// Velocidad con la que se empieza: `--speed`, si no la guardada, la del
+    /// perfil restaurado o el preset por defecto del archivo. La velocidad
+    /// es estado de la ejecución: una recarga del archivo no la cambia.
+    pub fn velocidad_inicial(&self, guardada: Option<u32>, perfil: Option<u32>) -> u32 {
+        self.velocidad
+            .or(guardada)
+            .or(perfil)
+            .unwrap_or_else(|| config::actual().velocidad_inicial())
+    }
+}
+
+/// Resultado del parsing: ejecutar o solo mostrar información
+#[derive(Debug, PartialEq)]
+pub enum Accion {
+    Ejecutar(Argumentos),
+    MostrarAyuda,
+    MostrarVersion,
+}
+
+/// Convierte una velocidad `xN` (o `N`) en multiplicador × 100.
+///
+/// Ejemplo: "x1.35" → 135.
+pub fn parsear_velocidad(valor: &str) -> Result<u32, String> {
+    let numero = valor.strip_prefix(['x', 'X']).unwrap_or(valor);
+    let multiplicador: f32 = numero
+        .parse()
+        .map_err(|_| format!("velocidad inválida '{}': usa el formato x1.5", valor))?;
+
+    let multiplicador_x100 = (multiplicador * 100.0).round() as u32;
+    if !multiplicador.is_finite()
+        || !(VELOCIDAD_MINIMA_X100..=VELOCIDAD_MAXIMA_X100).contains(&multiplicador_x100)
+    {
+        return Err(format!(
+            "velocidad '{}' fuera de rango ({} a {})",
+            valor,
+            formatear_velocidad(VELOCIDAD_MINIMA_X100),
+            formatear_velocidad(VELOCIDAD_MAXIMA_X100)
+        ));
+    }
+
+    Ok(multiplicador_x100)
+}
+
+/// Interpreta los argumentos de la línea de comandos (sin el nombre del programa).
+pub fn parsear<I>(args: I) -> Result<Accion, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    let mut es_hablar = false;
+    let mut es_diagnostico = false;
+    let mut palabras: Vec<String> = Vec::new();
+    let mut archivo: Option<PathBuf> = None;
+    let mut desde_stdin = false;
+
+    let mut argumentos = Argumentos {
+        comando: Comando::Tray,
+        ruta_config: None,
+        voz: None,
+        motor: None,
+        velocidad: None,
+        sin_tray: false,
+        sin_hotkeys: false,
+    };
+
+    // Lee el valor obligatorio de una opción como `--config <ruta>`
+    fn valor_de(opcion: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+        args.next()
+            .ok_or_else(|| format!("la opción {} necesita un valor", opcion))
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Accion::MostrarAyuda),
+            "-V" | "--version" => return Ok(Accion::MostrarVersion),
+            "--config" => argumentos.ruta_config = Some(valor_de(&arg, &mut args)?.into()),
+            "--voice" => argumentos.voz = Some(valor_de(&arg, &mut args)?.into()),
+            "--engine" => argumentos.motor = Some(valor_de(&arg, &mut args)?.parse()?),
+            "--speed" => {
+                argumentos.velocidad = Some(parsear_velocidad(&valor_de(&arg, &mut args)?)?)
+            }
+            "--no-tray" => argumentos.sin_tray = true,
+            "--no-hotkeys" => argumentos.sin_hotkeys = true,
+            "--file" if es_hablar => archivo = Some(valor_de(&arg, &mut args)?.into()),
+            "-" if es_hablar => desde_stdin = true,
+            "speak" if !es_hablar && !es_diagnostico => es_hablar = true,
+            "doctor" if !es_hablar && !es_diagnostico => es_diagnostico = true,
+            "--" if es_hablar => palabras.extend(args.by_ref()),
+            opcion if opcion.starts_with('-') => {
+                return Err(format!("opción desconocida '{}'", opcion))
+            }
+            palabra if es_hablar => palabras.push(palabra.to_string()),
+            otro => return Err(format!("comando desconocido '{}'", otro)),
+        }
+    }
+
+    if es_hablar {
+        let fuentes = usize::from(!palabras.is_empty())
+            + usize::from(archivo.is_some())
+            + usize::from(desde_stdin);
+
+        let fuente = match (fuentes, archivo) {
+            (0, _) => return Err("speak necesita un texto, --file <ruta> o -".to_string()),
+            (1, Some(ruta)) => FuenteTexto::Archivo(ruta),
+            (1, None) if desde_stdin => FuenteTexto::Stdin,
+            (1, None) => FuenteTexto::Texto(palabras.join(" ")),
+            _ => return Err("usa solo una fuente de texto: <texto>, --file o -".to_string()),
+        };
+        argumentos.comando = Comando::Hablar(fuente);
+    } else if es_diagnostico {
+        argumentos.comando = Comando::Diagnostico;
+    } else if argumentos.sin_tray && argumentos.sin_hotkeys {
+        return Err("--no-tray y --no-hotkeys juntos dejan el modo tray sin nada que hacer".into());
+    }
+
+    Ok(Accion::Ejecutar(argumentos))
+}
+
+/// Texto mostrado con `--version`.
+pub fn texto_version() -> String {
+    format!("tray_narrator {}", VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsear_str(args: &[&str]) -> Result<Accion, String> {
+        parsear(args.iter().map(|a| a.to_string()))
+    }
+
+    fn argumentos(args: &[&str]) -> Argumentos {
+        match parsear_str(args) {
+            Ok(Accion::Ejecutar(argumentos)) => argumentos,
+            otro => panic!("{:?} → {:?}", args, otro),
+        }
+    }
+
+    #[test]
+    fn velocidades() {
+        assert_eq!(parsear_velocidad("x1.35"), Ok(135));
+        assert_eq!(parsear_velocidad("X2"), Ok(200));
+        assert_eq!(parsear_velocidad("0.5"), Ok(50));
+        assert!(parsear_velocidad("x0.05").is_err());
+        assert!(parsear_velocidad("x11").is_err());
+        assert!(parsear_velocidad("xNaN").is_err());
+        assert!(parsear_velocidad("xinf").is_err());
+        assert!(parsear_velocidad("rápido").is_err());
+    }
+
+    #[test]
+    fn modo_tray_y_opciones() {
+        let args = argumentos(&[]);
+        assert_eq!(args.comando, Comando::Tray);
+        assert_eq!(args.ruta_config, None);
+
+        let args = argumentos(&[
+            "--config",
+            "otra.toml",
+            "--voice",
+            "voz.onnx",
+            "--engine",
+            "mock",
+            "--speed",
+            "x1.5",
+            "--no-tray",
+        ]);
+        assert_eq!(args.ruta_config, Some(PathBuf::from("otra.toml")));
+        assert_eq!(args.voz, Some(PathBuf::from("voz.onnx")));
+        assert_eq!(args.motor, Some(Motor::Mock));
+        assert_eq!(args.velocidad, Some(150));
+        assert!(args.sin_tray && !args.sin_hotkeys);
+
+        assert_eq!(
+            parsear_str(&["--help", "--bogus"]),
+            Ok(Accion::MostrarAyuda)
+        );
+        assert_eq!(parsear_str(&["-V"]), Ok(Accion::MostrarVersion));
+    }
+
+    #[test]
+    fn subcomandos() {
+        assert_eq!(
+            argumentos(&["speak", "hola", "mundo"]).comando,
+            Comando::Hablar(FuenteTexto::Texto("hola mundo".into()))
+        );
+        assert_eq!(
+            argumentos(&["speak", "--", "-x", "--speed"]).comando,
+            Comando::Hablar(FuenteTexto::Texto("-x --speed".into()))
+        );
+        assert_eq!(
+            argumentos(&["speak", "-"]).comando,
+            Comando::Hablar(FuenteTexto::Stdin)
+        );
+        assert_eq!(
+            argumentos(&["speak", "--file", "texto.txt"]).comando,
+            Comando::Hablar(FuenteTexto::Archivo("texto.txt".into()))
+        );
+        assert_eq!(argumentos(&["doctor"]).comando, Comando::Diagnostico);
+        // Las opciones pueden ir antes o después del subcomando
+        assert_eq!(
+            argumentos(&["--speed", "x2", "doctor"]).velocidad,
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn sobrescrituras_sobre_el_archivo() {
+        let _bloqueo = config::BLOQUEO_PRUEBAS.lock();
+        let ruta = std::env::temp_dir().join(format!(
+            "tray_narrator_prueba_{}_cli.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &ruta,
+            "motor = \"piper\"\nruta_modelo = \"archivo.onnx\"\nvelocidad_preset_defecto = 0",
+        )
+        .unwrap();
+
+        let args = argumentos(&["--voice", "voz.onnx", "--engine", "mock", "--speed", "x1.5"]);
+        config::establecer_sobrescrituras(args.sobrescrituras());
+        config::cargar_config(&ruta);
+        let comprobar = || {
+            let actual = config::actual();
+            assert_eq!(actual.motor, Motor::Mock);
+            assert_eq!(actual.ruta_modelo, PathBuf::from("voz.onnx"));
+            assert_eq!(actual.voz_espeak, "voz.onnx");
+            assert_eq!(args.velocidad_inicial(Some(80), Some(120)), 150);
+        };
+        comprobar();
+
+        // La recarga en caliente cambia el archivo, no las opciones
+        std::fs::write(
+            &ruta,
+            "motor = \"espeak\"\nvoz_espeak = \"en\"\nvelocidad_preset_defecto = 1\nvolumen = 0.5",
+        )
+        .unwrap();
+        config::recargar_config(&ruta);
+        assert_eq!(config::actual().volumen, 0.5);
+        comprobar();
+
+        // Sin opciones manda el archivo
+        let sin_opciones = argumentos(&[]);
+        config::establecer_sobrescrituras(sin_opciones.sobrescrituras());
+        config::cargar_config(&ruta);
+        assert_eq!(config::actual().motor, Motor::Espeak);
+        assert_eq!(
+            sin_opciones.velocidad_inicial(None, None),
+            config::actual().velocidad_inicial()
+        );
+        assert_eq!(sin_opciones.velocidad_inicial(Some(80), Some(120)), 80);
+        std::fs::remove_file(&ruta).unwrap();
+    }
+
+    #[test]
+    fn errores() {
+        for args in [
+            &["--config"][..],
+            &["--speed", "x99"],
+            &["--engine", "otro"],
+            &["--bogus"],
+            &["hablar"],
+            &["speak"],
+            &["speak", "hola", "-"],
+            &["speak", "--file"],
+            &["-"],
+            &["--file", "a.txt"],
+            &["doctor", "speak"],
+            &["--no-tray", "--no-hotkeys"],
+        ] {
+            assert!(parsear_str(args).is_err(), "{:?} debería fallar", args);
+        }
+    }
+}
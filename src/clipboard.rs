@@ -6,13 +6,21 @@ use std::time::Duration;
 use arboard::Clipboard;
 use enigo::{Enigo, Key, Keyboard, Settings};
 
-use crate::config::DELAY_COPIAR_MS;
+use crate::config;
 
 /// Simula la pulsación de Ctrl+C para copiar el texto seleccionado
 pub fn simular_copiar() -> Result<(), String> {
     let mut enigo = Enigo::new(&Settings::default())
         .map_err(|e| format!("Error inicializando Enigo: {}", e))?;
 
+    // Con Mayús+F8 la tecla sigue pulsada: soltarla para que la aplicación
+    // reciba Ctrl+C y no Ctrl+Mayús+C (en un navegador abre el inspector)
+    for mayus in [Key::LShift, Key::RShift] {
+        enigo
+            .key(mayus, enigo::Direction::Release)
+            .map_err(|e| format!("Error soltando Mayús: {}", e))?;
+    }
+
     // Simular Ctrl+C
     enigo
         .key(Key::Control, enigo::Direction::Press)
@@ -24,7 +32,7 @@ pub fn simular_copiar() -> Result<(), String> {
         .key(Key::Control, enigo::Direction::Release)
         .map_err(|e| format!("Error soltando Ctrl: {}", e))?;
 
-    thread::sleep(Duration::from_millis(DELAY_COPIAR_MS));
+    thread::sleep(Duration::from_millis(config::actual().delay_copiar_ms));
     Ok(())
 }
 
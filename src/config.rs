@@ -1,43 +1,40 @@
-//! Configuración y constantes del programa.
+//! Configuración del programa.
 //!
-//! Modifica estas constantes según tu instalación.
+//! Los valores se leen al arrancar desde un archivo TOML (`tray_narrator.toml`,
+//! ubicado según la plataforma, ver `paths`). Las claves ausentes toman los valores por defecto
+//! definidos en `Config::default()`, así que un archivo vacío equivale a la
+//! configuración de fábrica. El esquema completo está documentado en
+//! `tray_narrator.example.toml`.
+//!
+//! Un hilo vigila el archivo y recarga la configuración en caliente cuando
+//! cambia; las ediciones inválidas se rechazan y se conserva la anterior.
 
-/// Ruta al ejecutable de Piper TTS (formato Windows)
-pub const RUTA_PIPER: &str = r"C:\TrayNarrator\piper\piper.exe";
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
-/// Ruta al modelo de voz .onnx de Piper (formato Windows)
-pub const RUTA_MODELO: &str = r"C:\TrayNarrator\piper\es_ES-sharvard-medium.onnx";
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 
-/// Ruta del archivo temporal WAV
-pub const RUTA_TEMP_WAV: &str = r"C:\TrayNarrator\temp.wav";
+use crate::language;
+use crate::logging::log;
+use crate::paths;
+use crate::state::DEBE_SALIR;
 
-/// Ruta del archivo de log para debugging
-pub const RUTA_LOG: &str = r"C:\TrayNarrator\log.txt";
+/// Nombre del archivo de configuración
+pub const NOMBRE_ARCHIVO_CONFIG: &str = "tray_narrator.toml";
 
-/// Tiempo de espera después de simular Ctrl+C (milisegundos)
-pub const DELAY_COPIAR_MS: u64 = 150;
+/// Nombre del léxico de pronunciación
+pub const NOMBRE_ARCHIVO_LEXICO: &str = "lexico.toml";
 
-/// Presets de velocidad: (etiqueta, length_scale × 100).
-///
-/// `length_scale = 1.0 / multiplicador`. Piper usa length_scale para controlar
-/// la duración de los fonemas: menor = más rápido.
-///
-/// Ejemplo: x2 → length_scale 0.50 → almacenado como 50.
-pub const VELOCIDADES_PRESET: &[(&str, u32)] = &[
-    ("x0.5", 200),  // length_scale 2.00 — muy lento
-    ("x0.75", 133), // length_scale 1.33
-    ("x1", 100),    // length_scale 1.00 — normal
-    ("x1.25", 80),  // length_scale 0.80
-    ("x1.5", 67),   // length_scale 0.67
-    ("x2", 50),     // length_scale 0.50
-    ("x3", 33),     // length_scale 0.33 — muy rápido
-];
-
-/// Índice del preset por defecto (x1 = velocidad normal)
-pub const VELOCIDAD_PRESET_DEFECTO: usize = 2;
-
-/// Velocidad inicial (length_scale × 100). Corresponde al preset por defecto.
-pub const VELOCIDAD_INICIAL: u32 = VELOCIDADES_PRESET[VELOCIDAD_PRESET_DEFECTO].1;
+/// Cada cuánto se comprueba si el archivo de configuración ha cambiado
+pub const INTERVALO_VIGILANCIA: Duration = Duration::from_secs(1);
 
 /// Versión de la aplicación (obtenida de Cargo.toml)
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -45,3 +42,1001 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 // Para ocultar la ventana de Piper en Windows
 #[cfg(windows)]
 pub const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ESQUEMA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Límites de la velocidad (multiplicador × 100): de x0.1 a x10.
+pub const VELOCIDAD_MINIMA_X100: u32 = 10;
+pub const VELOCIDAD_MAXIMA_X100: u32 = 1000;
+
+/// Formatea un multiplicador × 100 como etiqueta `xN`, sin ceros sobrantes.
+///
+/// Ejemplo: 100 → "x1", 150 → "x1.5", 135 → "x1.35".
+pub fn formatear_velocidad(multiplicador_x100: u32) -> String {
+    let entero = multiplicador_x100 / 100;
+    let decimales = multiplicador_x100 % 100;
+    if decimales == 0 {
+        format!("x{}", entero)
+    } else if decimales % 10 == 0 {
+        format!("x{}.{}", entero, decimales / 10)
+    } else {
+        format!("x{}.{:02}", entero, decimales)
+    }
+}
+
+/// Preset de velocidad: multiplicador y etiqueta opcional para el menú.
+///
+/// Piper recibe `length_scale = 1.0 / multiplicador`: length_scale controla
+/// la duración de los fonemas, menor = más rápido.
+///
+/// Ejemplo: x2 → length_scale 0.50.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PresetVelocidad {
+    /// Texto del menú; si falta se usa el multiplicador (`x1.5`)
+    #[serde(default)]
+    pub etiqueta: Option<String>,
+    pub multiplicador: f32,
+}
+
+impl PresetVelocidad {
+    fn new(multiplicador: f32) -> Self {
+        Self {
+            etiqueta: None,
+            multiplicador,
+        }
+    }
+
+    /// Multiplicador × 100, la unidad en que se guarda la velocidad actual.
+    pub fn multiplicador_x100(&self) -> u32 {
+        (self.multiplicador * 100.0).round() as u32
+    }
+
+    /// Etiqueta visible del preset.
+    pub fn etiqueta(&self) -> String {
+        self.etiqueta
+            .clone()
+            .unwrap_or_else(|| formatear_velocidad(self.multiplicador_x100()))
+    }
+}
+
+/// Motor de síntesis de voz (ver `tts`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Motor {
+    /// Piper TTS (por defecto)
+    #[serde(rename = "piper")]
+    Piper,
+    /// CLI de espeak-ng: voz robótica, pero sin modelos que descargar
+    #[serde(rename = "espeak", alias = "espeak-ng")]
+    Espeak,
+    /// Tonos generados sin motor externo, para pruebas
+    #[serde(rename = "mock")]
+    Mock,
+}
+
+impl Motor {
+    /// Nombre del motor tal como se escribe en la configuración.
+    pub fn nombre(self) -> &'static str {
+        match self {
+            Motor::Piper => "piper",
+            Motor::Espeak => "espeak",
+            Motor::Mock => "mock",
+        }
+    }
+}
+
+impl FromStr for Motor {
+    type Err = String;
+
+    fn from_str(valor: &str) -> Result<Self, Self::Err> {
+        match valor {
+            "piper" => Ok(Motor::Piper),
+            "espeak" | "espeak-ng" => Ok(Motor::Espeak),
+            "mock" => Ok(Motor::Mock),
+            otro => Err(format!(
+                "motor desconocido '{}': usa piper, espeak o mock",
+                otro
+            )),
+        }
+    }
+}
+
+/// Cómo entrega Piper el audio generado (ver `tts::piper`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SalidaPiper {
+    /// WAV en `ruta_temp_wav` que luego se lee a memoria (por defecto)
+    #[serde(rename = "wav")]
+    Wav,
+    /// PCM crudo por la salida estándar (`--output-raw`): suena según se
+    /// genera y no se escribe nada en disco
+    #[serde(rename = "raw")]
+    Raw,
+}
+
+/// Qué signos de puntuación y símbolos se leen por su nombre (ver
+/// `text::verbalizar_signos`). Cada nivel incluye los del anterior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum NivelPuntuacion {
+    /// Ninguno: la puntuación solo marca las pausas y los símbolos se callan
+    #[serde(rename = "ninguno")]
+    Ninguno,
+    /// Símbolos que no son puntuación: `@`, `#`, `&`, `%`, `$`… (por defecto)
+    #[serde(rename = "algunos")]
+    Algunos,
+    /// Además paréntesis, corchetes, comillas, rayas y operadores
+    #[serde(rename = "mayoria")]
+    Mayoria,
+    /// Además comas, puntos, guiones y el resto de la puntuación
+    #[serde(rename = "todos")]
+    Todos,
+}
+
+impl NivelPuntuacion {
+    /// Niveles de menos a más signos.
+    pub const NIVELES: [NivelPuntuacion; 4] = [
+        NivelPuntuacion::Ninguno,
+        NivelPuntuacion::Algunos,
+        NivelPuntuacion::Mayoria,
+        NivelPuntuacion::Todos,
+    ];
+
+    /// Posición en `NIVELES`.
+    pub fn indice(self) -> usize {
+        self as usize
+    }
+
+    /// Etiqueta visible del nivel.
+    pub fn etiqueta(self) -> &'static str {
+        match self {
+            NivelPuntuacion::Ninguno => "Ninguno",
+            NivelPuntuacion::Algunos => "Algunos",
+            NivelPuntuacion::Mayoria => "La mayoría",
+            NivelPuntuacion::Todos => "Todos",
+        }
+    }
+
+    /// Nivel siguiente, volviendo a `Ninguno` tras `Todos`.
+    pub fn siguiente(self) -> NivelPuntuacion {
+        Self::NIVELES[(self.indice() + 1) % Self::NIVELES.len()]
+    }
+}
+
+/// Cómo se nombran las letras al deletrear (Mayús+F8).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum AlfabetoDeletreo {
+    /// El nombre de la letra: "be", "uve doble" (por defecto)
+    #[serde(rename = "letras")]
+    Letras,
+    /// El alfabeto de deletreo del idioma: "a de Alicante" en español, el de
+    /// la OTAN en inglés
+    #[serde(rename = "fonetico")]
+    Fonetico,
+    /// El alfabeto de la OTAN en cualquier idioma: "Alfa", "Bravo"
+    #[serde(rename = "otan")]
+    Otan,
+}
+
+/// Cuándo se lee el texto como código fuente (ver `text::leer_codigo`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ModoCodigo {
+    /// Si el texto parece código (por defecto)
+    #[serde(rename = "auto")]
+    Auto,
+    /// Siempre, para repasar código aunque no lo parezca
+    #[serde(rename = "siempre")]
+    Siempre,
+    /// Nunca: todo se lee como prosa
+    #[serde(rename = "nunca")]
+    Nunca,
+}
+
+impl ModoCodigo {
+    /// Modos en el orden del menú.
+    pub const MODOS: [ModoCodigo; 3] = [ModoCodigo::Auto, ModoCodigo::Siempre, ModoCodigo::Nunca];
+
+    /// Posición en `MODOS`.
+    pub fn indice(self) -> usize {
+        self as usize
+    }
+
+    /// Etiqueta visible del modo.
+    pub fn etiqueta(self) -> &'static str {
+        match self {
+            ModoCodigo::Auto => "Automático",
+            ModoCodigo::Siempre => "Siempre",
+            ModoCodigo::Nunca => "Nunca",
+        }
+    }
+}
+
+/// Qué se hace con los emojis del texto (ver `text::nombrar_emojis`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ModoEmoji {
+    /// Se leen por su nombre corto de CLDR en el idioma de la voz: "cohete"
+    /// (por defecto)
+    #[serde(rename = "nombrar")]
+    Nombrar,
+    /// Se quitan del texto
+    #[serde(rename = "omitir")]
+    Omitir,
+}
+
+/// Límite superior del volumen (1.0 = volumen original del audio).
+pub const VOLUMEN_MAXIMO: f32 = 2.0;
+
+/// Límite superior de `noise_scale` y `noise_w`.
+pub const RUIDO_MAXIMO: f32 = 2.0;
+
+/// Límite superior de `sentence_silence` (segundos).
+pub const SILENCIO_FRASE_MAXIMO: f32 = 10.0;
+
+/// Límite superior de `silencio_parrafo_ms`.
+pub const SILENCIO_PARRAFO_MAXIMO_MS: u64 = 10_000;
+
+/// Confianza mínima por defecto para cambiar de voz según el idioma.
+const CONFIANZA_IDIOMA_DEFECTO: f32 = 0.5;
+
+/// Palabras mínimas por defecto de un tramo en otro idioma.
+const PALABRAS_MINIMAS_TRAMO_DEFECTO: u32 = 3;
+
+/// Parámetros de prosodia de Piper. Los ausentes se heredan del nivel
+/// anterior: perfil → voz → general → sección `inference` del `.onnx.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Prosodia {
+    /// Variación de la entonación (`--noise_scale`; 0 = monótono)
+    pub noise_scale: Option<f32>,
+    /// Variación de la duración de los fonemas (`--noise_w`)
+    pub noise_w: Option<f32>,
+    /// Silencio tras cada frase, en segundos (`--sentence_silence`)
+    pub sentence_silence: Option<f32>,
+}
+
+impl Prosodia {
+    /// Completa los valores ausentes con los de `base`.
+    pub fn sobre(self, base: Prosodia) -> Prosodia {
+        Prosodia {
+            noise_scale: self.noise_scale.or(base.noise_scale),
+            noise_w: self.noise_w.or(base.noise_w),
+            sentence_silence: self.sentence_silence.or(base.sentence_silence),
+        }
+    }
+
+    /// Comprueba los rangos; `clave` es el prefijo para los mensajes.
+    fn validar(&self, clave: &str) -> Result<(), String> {
+        let ruidos = [("noise_scale", self.noise_scale), ("noise_w", self.noise_w)];
+        for (nombre, valor) in ruidos {
+            if valor.is_some_and(|v| !v.is_finite() || !(0.0..=RUIDO_MAXIMO).contains(&v)) {
+                return Err(format!(
+                    "clave '{}.{}': debe estar entre 0 y {}",
+                    clave, nombre, RUIDO_MAXIMO
+                ));
+            }
+        }
+        if self
+            .sentence_silence
+            .is_some_and(|v| !v.is_finite() || !(0.0..=SILENCIO_FRASE_MAXIMO).contains(&v))
+        {
+            return Err(format!(
+                "clave '{}.sentence_silence': debe estar entre 0 y {} segundos",
+                clave, SILENCIO_FRASE_MAXIMO
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Reglas de normalización del texto (`[normalizacion]`): cada una convierte
+/// en palabras un tipo de expresión antes de sintetizar. Todas activas por
+/// defecto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Normalizacion {
+    /// "Sr." → "señor", "e.g." → "for example"
+    pub abreviaturas: bool,
+    /// "12/03/2026" → "doce de marzo de dos mil veintiséis"
+    pub fechas: bool,
+    /// "14:30" → "catorce y treinta"
+    pub horas: bool,
+    /// "3,50 €" → "tres euros con cincuenta céntimos"
+    pub monedas: bool,
+    /// "3,5 %" → "tres coma cinco por ciento"
+    pub porcentajes: bool,
+    /// "5 km" → "cinco kilómetros"
+    pub unidades: bool,
+    /// "1º" → "primero", "21st" → "twenty-first"
+    pub ordinales: bool,
+    /// "siglo XXI" → "siglo veintiuno", "Felipe II" → "Felipe segundo"
+    pub romanos: bool,
+    /// El resto de números: "1.000.000" → "un millón"
+    pub numeros: bool,
+}
+
+impl Default for Normalizacion {
+    fn default() -> Self {
+        Self {
+            abreviaturas: true,
+            fechas: true,
+            horas: true,
+            monedas: true,
+            porcentajes: true,
+            unidades: true,
+            ordinales: true,
+            romanos: true,
+            numeros: true,
+        }
+    }
+}
+
+/// Perfil con nombre: agrupa ajustes que se cambian juntos desde el tray o
+/// con F7. Las claves ausentes no tocan el valor actual (velocidad) o usan el
+/// de la configuración general (voz, volumen).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Perfil {
+    /// Nombre mostrado en el menú, único entre los perfiles
+    pub nombre: String,
+    /// Voz del motor: modelo .onnx en Piper, nombre de voz en espeak-ng
+    /// (sustituye a `ruta_modelo` / `voz_espeak`)
+    #[serde(default)]
+    pub voz: Option<PathBuf>,
+    /// Hablante (nombre o número) en modelos multi-hablante (sustituye a
+    /// `hablante`)
+    #[serde(default)]
+    pub hablante: Option<String>,
+    /// Velocidad aplicada al activar el perfil (multiplicador)
+    #[serde(default)]
+    pub velocidad: Option<f32>,
+    /// Volumen de reproducción (sustituye a `volumen`)
+    #[serde(default)]
+    pub volumen: Option<f32>,
+    /// Prosodia de Piper (sustituye a la de la voz y la general)
+    #[serde(default)]
+    pub prosodia: Prosodia,
+    /// Nivel de puntuación aplicado al activar el perfil
+    #[serde(default)]
+    pub puntuacion: Option<NivelPuntuacion>,
+    /// Modo de lectura de código aplicado al activar el perfil
+    #[serde(default)]
+    pub modo_codigo: Option<ModoCodigo>,
+}
+
+impl Perfil {
+    /// Velocidad del perfil como multiplicador × 100.
+    pub fn velocidad_x100(&self) -> Option<u32> {
+        self.velocidad.map(|v| (v * 100.0).round() as u32)
+    }
+}
+
+/// Configuración completa de la aplicación.
+///
+/// Cada campo corresponde a una clave de primer nivel del archivo TOML.
+/// Las claves desconocidas se rechazan para detectar errores tipográficos.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Motor de síntesis
+    pub motor: Motor,
+    /// Ruta al ejecutable de Piper TTS
+    pub ruta_piper: PathBuf,
+    /// Mantener Piper abierto entre lecturas (modo `--json-input`) en vez
+    /// de lanzar un proceso por lectura
+    pub piper_persistente: bool,
+    /// Salida de Piper: WAV temporal o PCM crudo en streaming. Con `raw` se
+    /// lanza un proceso por fragmento y `piper_persistente` no se usa
+    pub salida_piper: SalidaPiper,
+    /// Ruta al modelo de voz .onnx de Piper
+    pub ruta_modelo: PathBuf,
+    /// Carpeta donde se buscan voces de Piper (`*.onnx` + `*.onnx.json`)
+    /// para el submenú "Voz" del tray
+    pub dir_voces: PathBuf,
+    /// Hablante de Piper (nombre de `speaker_id_map` o número) en modelos
+    /// multi-hablante. `None` = el hablante por defecto del modelo
+    pub hablante: Option<String>,
+    /// Prosodia de Piper para todas las voces (`[prosodia]`)
+    pub prosodia: Prosodia,
+    /// Prosodia por voz, por nombre de modelo sin extensión
+    /// (`[prosodia_voces."es_ES-sharvard-medium"]`)
+    pub prosodia_voces: BTreeMap<String, Prosodia>,
+    /// Voz para cada idioma detectado, por código ISO 639-1 (`en = '...'`).
+    /// Vacío = sin detección de idioma
+    pub voces_idioma: BTreeMap<String, PathBuf>,
+    /// Confianza mínima de la detección (0 a 1) para cambiar de voz
+    pub confianza_idioma: f32,
+    /// Palabras mínimas de un tramo en otro idioma dentro de una frase para
+    /// leerlo con su voz. 0 = una sola voz por lectura
+    pub palabras_minimas_tramo: u32,
+    /// Reglas de normalización de números, fechas y abreviaturas
+    pub normalizacion: Normalizacion,
+    /// Quitar el marcado de Markdown, HTML y wikitexto (y las URLs y
+    /// llamadas a notas) del texto copiado
+    pub limpiar_marcado: bool,
+    /// Signos de puntuación y símbolos que se leen por su nombre al arrancar
+    /// (luego se cambia con F6 o desde el tray)
+    pub puntuacion: NivelPuntuacion,
+    /// Nombres de las letras al deletrear
+    pub alfabeto_deletreo: AlfabetoDeletreo,
+    /// Cuándo se lee la selección como código fuente al arrancar (luego se
+    /// cambia desde el tray)
+    pub modo_codigo: ModoCodigo,
+    /// Leer los emojis por su nombre o quitarlos
+    pub emojis: ModoEmoji,
+    /// Ejecutable de espeak-ng (basta el nombre si está en el PATH)
+    pub ruta_espeak: PathBuf,
+    /// Voz de espeak-ng (p. ej. `es`, `en-us`)
+    pub voz_espeak: String,
+    /// Ruta del archivo temporal WAV: cada lectura usa uno propio con este
+    /// nombre de base (ver `tts::ruta_wav_unica`)
+    pub ruta_temp_wav: PathBuf,
+    /// Ruta del archivo de log para debugging
+    pub ruta_log: PathBuf,
+    /// Léxico de pronunciación del usuario (`lexico.toml`)
+    pub ruta_lexico: PathBuf,
+    /// Tiempo de espera después de simular Ctrl+C (milisegundos)
+    pub delay_copiar_ms: u64,
+    /// Silencio entre párrafos (milisegundos), además de la pausa de fin de
+    /// frase del motor
+    pub silencio_parrafo_ms: u64,
+    /// Presets de velocidad, de más lento a más rápido
+    pub velocidades_preset: Vec<PresetVelocidad>,
+    /// Índice del preset por defecto en `velocidades_preset`
+    pub velocidad_preset_defecto: usize,
+    /// Si es `true`, los hotkeys de velocidad suman o restan `paso_velocidad`
+    /// en vez de saltar entre presets
+    pub ajuste_fino: bool,
+    /// Incremento del ajuste fino (multiplicador, p. ej. 0.05)
+    pub paso_velocidad: f32,
+    /// Volumen de reproducción, de 0.0 a `VOLUMEN_MAXIMO` (1.0 = original)
+    pub volumen: f32,
+    /// Perfiles en el orden en que aparecen en el menú y se ciclan con F7
+    pub perfiles: Vec<Perfil>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            motor: Motor::Piper,
+            ruta_piper: paths::ruta_piper_defecto(),
+            piper_persistente: true,
+            salida_piper: SalidaPiper::Wav,
+            ruta_modelo: paths::ruta_modelo_defecto(),
+            dir_voces: paths::dir_voces_defecto(),
+            hablante: None,
+            prosodia: Prosodia::default(),
+            prosodia_voces: BTreeMap::new(),
+            voces_idioma: BTreeMap::new(),
+            confianza_idioma: CONFIANZA_IDIOMA_DEFECTO,
+            palabras_minimas_tramo: PALABRAS_MINIMAS_TRAMO_DEFECTO,
+            normalizacion: Normalizacion::default(),
+            limpiar_marcado: true,
+            puntuacion: NivelPuntuacion::Algunos,
+            alfabeto_deletreo: AlfabetoDeletreo::Letras,
+            modo_codigo: ModoCodigo::Auto,
+            emojis: ModoEmoji::Nombrar,
+            ruta_espeak: PathBuf::from("espeak-ng"),
+            voz_espeak: "es".to_string(),
+            ruta_temp_wav: paths::ruta_temp_wav_defecto(),
+            ruta_log: paths::ruta_log_defecto(),
+            ruta_lexico: paths::ruta_config_defecto(NOMBRE_ARCHIVO_LEXICO),
+            delay_copiar_ms: 150,
+            silencio_parrafo_ms: 500,
+            velocidades_preset: vec![
+                PresetVelocidad::new(0.5),  // length_scale 2.00 — muy lento
+                PresetVelocidad::new(0.75), // length_scale 1.33
+                PresetVelocidad::new(1.0),  // length_scale 1.00 — normal
+                PresetVelocidad::new(1.25), // length_scale 0.80
+                PresetVelocidad::new(1.5),  // length_scale 0.67
+                PresetVelocidad::new(2.0),  // length_scale 0.50
+                PresetVelocidad::new(3.0),  // length_scale 0.33 — muy rápido
+            ],
+            velocidad_preset_defecto: 2,
+            ajuste_fino: false,
+            paso_velocidad: 0.05,
+            volumen: 1.0,
+            perfiles: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Velocidad inicial (multiplicador × 100). Corresponde al preset por defecto.
+    pub fn velocidad_inicial(&self) -> u32 {
+        self.velocidades_preset[self.velocidad_preset_defecto].multiplicador_x100()
+    }
+
+    /// Voz por defecto del motor configurado (sin perfil).
+    pub fn voz_motor(&self) -> PathBuf {
+        match self.motor {
+            Motor::Piper => self.ruta_modelo.clone(),
+            Motor::Espeak => PathBuf::from(&self.voz_espeak),
+            Motor::Mock => PathBuf::new(),
+        }
+    }
+
+    /// Paso del ajuste fino (multiplicador × 100).
+    pub fn paso_velocidad_x100(&self) -> u32 {
+        (self.paso_velocidad * 100.0).round() as u32
+    }
+
+    /// Comprueba las restricciones que el tipado de serde no puede expresar.
+    fn validar(&self) -> Result<(), String> {
+        let rutas = [
+            ("ruta_piper", &self.ruta_piper),
+            ("ruta_modelo", &self.ruta_modelo),
+            ("dir_voces", &self.dir_voces),
+            ("ruta_espeak", &self.ruta_espeak),
+            ("ruta_temp_wav", &self.ruta_temp_wav),
+            ("ruta_log", &self.ruta_log),
+            ("ruta_lexico", &self.ruta_lexico),
+        ];
+        for (clave, ruta) in rutas {
+            if ruta.as_os_str().is_empty() {
+                return Err(format!("clave '{}': la ruta no puede estar vacía", clave));
+            }
+        }
+
+        if self.voz_espeak.trim().is_empty() {
+            return Err("clave 'voz_espeak': no puede estar vacía".to_string());
+        }
+
+        if self.hablante.as_ref().is_some_and(|h| h.trim().is_empty()) {
+            return Err("clave 'hablante': no puede estar vacía".to_string());
+        }
+
+        self.prosodia.validar("prosodia")?;
+        for (voz, prosodia) in &self.prosodia_voces {
+            prosodia.validar(&format!("prosodia_voces.{}", voz))?;
+        }
+
+        for (idioma, voz) in &self.voces_idioma {
+            if !language::idioma_conocido(idioma) {
+                return Err(format!(
+                    "clave 'voces_idioma.{}': idioma desconocido (usa el código ISO 639-1, p. ej. 'en')",
+                    idioma
+                ));
+            }
+            if voz.as_os_str().is_empty() {
+                return Err(format!(
+                    "clave 'voces_idioma.{}': no puede estar vacía",
+                    idioma
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.confianza_idioma) {
+            return Err("clave 'confianza_idioma': debe estar entre 0 y 1".to_string());
+        }
+
+        if self.silencio_parrafo_ms > SILENCIO_PARRAFO_MAXIMO_MS {
+            return Err(format!(
+                "clave 'silencio_parrafo_ms': debe estar entre 0 y {}",
+                SILENCIO_PARRAFO_MAXIMO_MS
+            ));
+        }
+
+        if self.velocidades_preset.is_empty() {
+            return Err("clave 'velocidades_preset': debe contener al menos un preset".to_string());
+        }
+
+        let rango = VELOCIDAD_MINIMA_X100..=VELOCIDAD_MAXIMA_X100;
+        let mut anterior = 0;
+        for (i, preset) in self.velocidades_preset.iter().enumerate() {
+            if preset
+                .etiqueta
+                .as_ref()
+                .is_some_and(|e| e.trim().is_empty())
+            {
+                return Err(format!(
+                    "clave 'velocidades_preset[{}].etiqueta': no puede estar vacía",
+                    i
+                ));
+            }
+            let multiplicador = preset.multiplicador_x100();
+            if !preset.multiplicador.is_finite() || !rango.contains(&multiplicador) {
+                return Err(format!(
+                    "clave 'velocidades_preset[{}].multiplicador': debe estar entre {} y {}",
+                    i,
+                    formatear_velocidad(VELOCIDAD_MINIMA_X100),
+                    formatear_velocidad(VELOCIDAD_MAXIMA_X100)
+                ));
+            }
+            if multiplicador <= anterior {
+                return Err(format!(
+                    "clave 'velocidades_preset[{}].multiplicador': los presets deben ir \
+                     de más lento a más rápido, sin repetir",
+                    i
+                ));
+            }
+            anterior = multiplicador;
+        }
+
+        if self.velocidad_preset_defecto >= self.velocidades_preset.len() {
+            return Err(format!(
+                "clave 'velocidad_preset_defecto': el índice {} no existe (hay {} presets)",
+                self.velocidad_preset_defecto,
+                self.velocidades_preset.len()
+            ));
+        }
+
+        let paso = self.paso_velocidad_x100();
+        if !self.paso_velocidad.is_finite() || !(1..=100).contains(&paso) {
+            return Err("clave 'paso_velocidad': debe estar entre 0.01 y 1".to_string());
+        }
+
+        if !volumen_valido(self.volumen) {
+            return Err(format!(
+                "clave 'volumen': debe estar entre 0 y {}",
+                VOLUMEN_MAXIMO
+            ));
+        }
+
+        for (i, perfil) in self.perfiles.iter().enumerate() {
+            let clave = format!("perfiles[{}]", i);
+            if perfil.nombre.trim().is_empty() {
+                return Err(format!("clave '{}.nombre': no puede estar vacía", clave));
+            }
+            if self.perfiles[..i].iter().any(|p| p.nombre == perfil.nombre) {
+                return Err(format!(
+                    "clave '{}.nombre': el perfil '{}' está repetido",
+                    clave, perfil.nombre
+                ));
+            }
+            if perfil
+                .voz
+                .as_ref()
+                .is_some_and(|v| v.as_os_str().is_empty())
+            {
+                return Err(format!(
+                    "clave '{}.voz': la ruta no puede estar vacía",
+                    clave
+                ));
+            }
+            if perfil
+                .hablante
+                .as_ref()
+                .is_some_and(|h| h.trim().is_empty())
+            {
+                return Err(format!("clave '{}.hablante': no puede estar vacía", clave));
+            }
+            if let Some(velocidad) = perfil.velocidad {
+                let x100 = (velocidad * 100.0).round() as u32;
+                if !velocidad.is_finite()
+                    || !(VELOCIDAD_MINIMA_X100..=VELOCIDAD_MAXIMA_X100).contains(&x100)
+                {
+                    return Err(format!(
+                        "clave '{}.velocidad': debe estar entre {} y {}",
+                        clave,
+                        formatear_velocidad(VELOCIDAD_MINIMA_X100),
+                        formatear_velocidad(VELOCIDAD_MAXIMA_X100)
+                    ));
+                }
+            }
+            if perfil.volumen.is_some_and(|v| !volumen_valido(v)) {
+                return Err(format!(
+                    "clave '{}.volumen': debe estar entre 0 y {}",
+                    clave, VOLUMEN_MAXIMO
+                ));
+            }
+            perfil.prosodia.validar(&format!("{}.prosodia", clave))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Comprueba que un volumen está entre 0 y `VOLUMEN_MAXIMO`.
+fn volumen_valido(volumen: f32) -> bool {
+    (0.0..=VOLUMEN_MAXIMO).contains(&volumen)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CARGA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    /// Configuración activa. Se reemplaza entera al cargar el archivo.
+    static ref CONFIG: RwLock<Arc<Config>> = RwLock::new(Arc::new(Config::default()));
+
+    /// Valores de la línea de comandos que prevalecen sobre el archivo.
+    static ref SOBRESCRITURAS: RwLock<Sobrescrituras> = RwLock::new(Sobrescrituras::default());
+}
+
+/// Valores que sustituyen a los del archivo durante toda la ejecución,
+/// también tras una recarga en caliente (p. ej. `--voice`).
+#[derive(Debug, Clone, Default)]
+pub struct Sobrescrituras {
+    /// `--voice`: sustituye a `ruta_modelo` y a `voz_espeak`
+    pub voz: Option<PathBuf>,
+    /// `--engine`
+    pub motor: Option<Motor>,
+}
+
+impl Sobrescrituras {
+    fn aplicar(&self, config: &mut Config) {
+        if let Some(ref voz) = self.voz {
+            config.ruta_modelo = voz.clone();
+            config.voz_espeak = voz.to_string_lossy().into_owned();
+        }
+        if let Some(motor) = self.motor {
+            config.motor = motor;
+        }
+    }
+}
+
+/// Fija las sobrescrituras de la línea de comandos.
+///
+/// Debe llamarse antes de `cargar_config` para que se apliquen desde el inicio.
+pub fn establecer_sobrescrituras(sobrescrituras: Sobrescrituras) {
+    let mut config = (*actual()).clone();
+    sobrescrituras.aplicar(&mut config);
+    *SOBRESCRITURAS.write() = sobrescrituras;
+    activar(config);
+}
+
+/// Indica si `--voice` fija la voz; en ese caso los perfiles no la cambian.
+pub fn voz_forzada() -> bool {
+    SOBRESCRITURAS.read().voz.is_some()
+}
+
+/// Serializa las pruebas que cambian la configuración activa o las
+/// sobrescrituras, que son globales.
+#[cfg(test)]
+pub static BLOQUEO_PRUEBAS: parking_lot::Mutex<()> = parking_lot::const_mutex(());
+
+/// Se incrementa cada vez que se activa una configuración nueva, para que
+/// otros módulos (p. ej. el tray) detecten los cambios sin comparar campos.
+pub static GENERACION_CONFIG: AtomicU32 = AtomicU32::new(0);
+
+/// Devuelve la configuración activa.
+pub fn actual() -> Arc<Config> {
+    CONFIG.read().clone()
+}
+
+/// Reemplaza la configuración activa.
+fn activar(config: Config) {
+    *CONFIG.write() = Arc::new(config);
+    GENERACION_CONFIG.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Ruta del archivo de configuración según la plataforma.
+pub fn ruta_archivo_config() -> PathBuf {
+    paths::ruta_config_defecto(NOMBRE_ARCHIVO_CONFIG)
+}
+
+/// Interpreta y valida el contenido de un archivo de configuración.
+pub fn parsear_config(contenido: &str) -> Result<Config, String> {
+    let config: Config = toml::from_str(contenido).map_err(|e| e.to_string())?;
+    config.validar()?;
+    Ok(config)
+}
+
+/// Lee y valida el archivo, aplicando las sobrescrituras de la línea de
+/// comandos. Devuelve `Ok(None)` si no existe.
+fn leer_config(ruta: &Path) -> Result<Option<Config>, String> {
+    match fs::read_to_string(ruta) {
+        Ok(contenido) => {
+            let mut config = parsear_config(&contenido)?;
+            SOBRESCRITURAS.read().aplicar(&mut config);
+            Ok(Some(config))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("error leyendo el archivo: {}", e)),
+    }
+}
+
+/// Carga la configuración desde `ruta` y la activa.
+///
+/// Si el archivo no existe se usan los valores por defecto. Si contiene
+/// errores se registran en el log y también se usan los valores por defecto.
+pub fn cargar_config(ruta: &Path) {
+    match leer_config(ruta) {
+        Ok(Some(config)) => {
+            activar(config);
+            log(&format!("Configuración cargada desde {}", ruta.display()));
+        }
+        Ok(None) => log(&format!(
+            "Sin archivo de configuración en {}, usando valores por defecto",
+            ruta.display()
+        )),
+        Err(e) => log(&format!(
+            "Configuración inválida en {}: {}. Usando valores por defecto",
+            ruta.display(),
+            e
+        )),
+    }
+}
+
+/// Vuelve a leer `ruta` tras un cambio. Si el archivo es inválido o ha
+/// desaparecido se conserva la configuración activa.
+pub fn recargar_config(ruta: &Path) {
+    match leer_config(ruta) {
+        Ok(Some(config)) => {
+            if config == *actual() {
+                return;
+            }
+            activar(config);
+            log(&format!("Configuración recargada desde {}", ruta.display()));
+        }
+        Ok(None) => log(&format!(
+            "{} ya no existe, se mantiene la configuración actual",
+            ruta.display()
+        )),
+        Err(e) => log(&format!(
+            "Cambio rechazado en {}: {}. Se mantiene la configuración anterior",
+            ruta.display(),
+            e
+        )),
+    }
+}
+
+/// Fecha de modificación del archivo, o `None` si no existe.
+pub fn fecha_modificacion(ruta: &Path) -> Option<SystemTime> {
+    fs::metadata(ruta).and_then(|m| m.modified()).ok()
+}
+
+/// Hilo que vigila el archivo de configuración y lo recarga al cambiar.
+///
+/// Compara la fecha de modificación a intervalos regulares: es suficiente
+/// para un archivo que se edita a mano y no añade dependencias.
+pub fn hilo_vigilancia_config(ruta: PathBuf) {
+    log(&format!("Vigilando cambios en {}", ruta.display()));
+    let mut ultima_modificacion = fecha_modificacion(&ruta);
+
+    while !DEBE_SALIR.load(Ordering::SeqCst) {
+        thread::sleep(INTERVALO_VIGILANCIA);
+
+        let modificacion = fecha_modificacion(&ruta);
+        if modificacion != ultima_modificacion {
+            ultima_modificacion = modificacion;
+            recargar_config(&ruta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Archivo de configuración temporal, único para este proceso.
+    fn archivo_temporal(nombre: &str, contenido: &str) -> PathBuf {
+        let ruta = std::env::temp_dir().join(format!(
+            "tray_narrator_prueba_{}_{}.toml",
+            std::process::id(),
+            nombre
+        ));
+        fs::write(&ruta, contenido).unwrap();
+        ruta
+    }
+
+    fn error(contenido: &str) -> String {
+        parsear_config(contenido).expect_err(contenido)
+    }
+
+    #[test]
+    fn archivo_de_ejemplo() {
+        // El ejemplo documenta los valores por defecto
+        let ejemplo = include_str!("../tray_narrator.example.toml");
+        assert_eq!(parsear_config(ejemplo), Ok(Config::default()));
+        assert_eq!(parsear_config(""), Ok(Config::default()));
+    }
+
+    #[test]
+    fn claves_desconocidas() {
+        assert!(error("volumne = 1.0").contains("unknown field `volumne`"));
+        assert!(error("[prosodia]\nruido = 0.5").contains("unknown field `ruido`"));
+        assert!(error("[normalizacion]\nfecha = false").contains("unknown field `fecha`"));
+        assert!(error("[[perfiles]]\nnombre = \"a\"\nvoice = \"x\"").contains("unknown field"));
+    }
+
+    #[test]
+    fn valores_fuera_de_rango() {
+        for contenido in [
+            "volumen = -1.0",
+            "volumen = 2.5",
+            "volumen = nan",
+            "confianza_idioma = 1.5",
+            "confianza_idioma = nan",
+            "paso_velocidad = 0.0",
+            "paso_velocidad = inf",
+            "silencio_parrafo_ms = 100000",
+            "velocidad_preset_defecto = 99",
+            "velocidades_preset = []",
+            "velocidades_preset = [{ multiplicador = 2.0 }, { multiplicador = 1.0 }]",
+            "velocidades_preset = [{ multiplicador = -1.0 }]",
+            "velocidades_preset = [{ multiplicador = nan }]",
+            "ruta_piper = \"\"",
+            "voz_espeak = \" \"",
+            "[prosodia]\nnoise_w = 3.0",
+            "[prosodia]\nsentence_silence = -0.1",
+            "[prosodia]\nnoise_scale = nan",
+            "[prosodia_voces.es]\nnoise_scale = -1.0",
+            "[voces_idioma]\nxx = \"voz.onnx\"",
+            "[[perfiles]]\nnombre = \"\"",
+            "[[perfiles]]\nnombre = \"a\"\nvelocidad = 0.0",
+            "[[perfiles]]\nnombre = \"a\"\nvelocidad = nan",
+            "[[perfiles]]\nnombre = \"a\"\nvolumen = -0.5",
+            "[[perfiles]]\nnombre = \"a\"\n[[perfiles]]\nnombre = \"a\"",
+        ] {
+            assert!(
+                parsear_config(contenido).is_err(),
+                "{:?} debería fallar",
+                contenido
+            );
+        }
+        assert!(error("volumen = -1.0").contains("'volumen'"));
+        assert!(
+            error("[[perfiles]]\nnombre = \"a\"\nvolumen = 3.0").contains("'perfiles[0].volumen'")
+        );
+    }
+
+    #[test]
+    fn prosodia_por_niveles() {
+        let config = parsear_config(
+            "[prosodia]\nnoise_scale = 0.5\nnoise_w = 0.7\n\
+             [prosodia_voces.\"es_ES-x-medium\"]\nnoise_w = 0.6\n\
+             [[perfiles]]\nnombre = \"Lento\"\nprosodia = { sentence_silence = 0.4 }\n\
+             [[perfiles]]\nnombre = \"Plano\"\nprosodia = { noise_scale = 0.0 }\n",
+        )
+        .unwrap();
+        let modelo = Prosodia {
+            noise_scale: Some(0.667),
+            noise_w: Some(0.8),
+            sentence_silence: None,
+        };
+        let general = config.prosodia.sobre(modelo);
+        let de_la_voz = config.prosodia_voces["es_ES-x-medium"].sobre(general);
+
+        // El perfil solo cambia lo que indica; lo demás viene de debajo
+        assert_eq!(
+            config.perfiles[0].prosodia.sobre(de_la_voz),
+            Prosodia {
+                noise_scale: Some(0.5),
+                noise_w: Some(0.6),
+                sentence_silence: Some(0.4),
+            }
+        );
+        // Un cero es un valor, no un hueco
+        assert_eq!(
+            config.perfiles[1].prosodia.sobre(general),
+            Prosodia {
+                noise_scale: Some(0.0),
+                noise_w: Some(0.7),
+                sentence_silence: None,
+            }
+        );
+        assert_eq!(Prosodia::default().sobre(modelo), modelo);
+    }
+
+    #[test]
+    fn recarga_fallida_conserva_la_anterior() {
+        let _bloqueo = BLOQUEO_PRUEBAS.lock();
+        establecer_sobrescrituras(Sobrescrituras::default());
+        let ruta = archivo_temporal("recarga", "volumen = 0.5");
+        cargar_config(&ruta);
+        assert_eq!(actual().volumen, 0.5);
+
+        let generacion = GENERACION_CONFIG.load(Ordering::SeqCst);
+        for invalido in ["volumen = 7.0", "volumen = [", "volumne = 0.7"] {
+            fs::write(&ruta, invalido).unwrap();
+            recargar_config(&ruta);
+            assert_eq!(actual().volumen, 0.5, "{:?}", invalido);
+            assert_eq!(GENERACION_CONFIG.load(Ordering::SeqCst), generacion);
+        }
+        // Sin cambios reales tampoco se activa una configuración nueva
+        fs::write(&ruta, "volumen = 0.50").unwrap();
+        recargar_config(&ruta);
+        assert_eq!(GENERACION_CONFIG.load(Ordering::SeqCst), generacion);
+
+        fs::write(&ruta, "volumen = 0.7").unwrap();
+        recargar_config(&ruta);
+        assert_eq!(actual().volumen, 0.7);
+        assert_eq!(GENERACION_CONFIG.load(Ordering::SeqCst), generacion + 1);
+
+        // Si el archivo desaparece también se conserva
+        fs::remove_file(&ruta).unwrap();
+        recargar_config(&ruta);
+        assert_eq!(actual().volumen, 0.7);
+        assert_eq!(GENERACION_CONFIG.load(Ordering::SeqCst), generacion + 1);
+    }
+}
@@ -0,0 +1,258 @@
+//! Autodiagnóstico de la instalación (`tray_narrator doctor`).
+//!
+//! Comprueba una a una las piezas que necesita una lectura (Piper, modelo,
+//! datos de espeak-ng, salida de audio, portapapeles) y termina con una
+//! síntesis de prueba con el motor configurado. Cada fallo va acompañado de
+//! una pista para resolverlo.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use arboard::Clipboard;
+use rodio::OutputStream;
+
+use crate::config::{self, VERSION};
+use crate::logging::log;
+use crate::tts;
+
+/// Texto usado en la síntesis de prueba
+const TEXTO_PRUEBA: &str = "Prueba de TrayNarrator.";
+
+/// Comprobación fallida: qué ha pasado y cómo arreglarlo.
+struct Fallo {
+    detalle: String,
+    remedio: String,
+}
+
+impl Fallo {
+    fn new(detalle: impl Into<String>, remedio: impl Into<String>) -> Self {
+        Self {
+            detalle: detalle.into(),
+            remedio: remedio.into(),
+        }
+    }
+}
+
+/// Resultado de una comprobación: descripción del éxito o el fallo.
+type Resultado = Result<String, Fallo>;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPROBACIONES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// El ejecutable de Piper existe y se puede ejecutar.
+fn comprobar_piper(ruta: &Path) -> Resultado {
+    let metadata = fs::metadata(ruta).map_err(|e| {
+        Fallo::new(
+            format!("{} no accesible: {}", ruta.display(), e),
+            "Descarga Piper desde https://github.com/rhasspy/piper/releases y ajusta `ruta_piper`",
+        )
+    })?;
+
+    if !metadata.is_file() {
+        return Err(Fallo::new(
+            format!("{} no es un archivo", ruta.display()),
+            "`ruta_piper` debe apuntar al ejecutable, no a su carpeta",
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(Fallo::new(
+                format!("{} no tiene permiso de ejecución", ruta.display()),
+                format!("Ejecuta: chmod +x {}", ruta.display()),
+            ));
+        }
+    }
+
+    Ok(ruta.display().to_string())
+}
+
+/// El modelo `.onnx` existe y tiene al lado su `.onnx.json` válido.
+fn comprobar_modelo(modelo: &Path) -> Resultado {
+    const REMEDIO_DESCARGA: &str = "Descarga el .onnx y su .onnx.json desde \
+         https://huggingface.co/rhasspy/piper-voices y ajusta `ruta_modelo`";
+
+    if !modelo.is_file() {
+        return Err(Fallo::new(
+            format!("{} no existe", modelo.display()),
+            REMEDIO_DESCARGA,
+        ));
+    }
+
+    let json = tts::ruta_json_modelo(modelo);
+    let contenido = fs::read_to_string(&json)
+        .map_err(|e| Fallo::new(format!("falta {}: {}", json.display(), e), REMEDIO_DESCARGA))?;
+
+    serde_json::from_str::<serde_json::Value>(&contenido).map_err(|e| {
+        Fallo::new(
+            format!("{} no es JSON válido: {}", json.display(), e),
+            "Vuelve a descargar el .onnx.json del modelo",
+        )
+    })?;
+
+    Ok(format!("{} (+ .onnx.json)", modelo.display()))
+}
+
+/// Carpeta de bibliotecas multiarquitectura de Debian y derivadas para la
+/// arquitectura del ejecutable (`x86_64-linux-gnu`, `aarch64-linux-gnu`…).
+fn triplete_multiarch() -> String {
+    match std::env::consts::ARCH {
+        "x86" => "i386-linux-gnu".to_string(),
+        "arm" => "arm-linux-gnueabihf".to_string(),
+        arquitectura => format!("{}-linux-gnu", arquitectura),
+    }
+}
+
+/// Carpetas donde puede estar `espeak-ng-data`, en orden de preferencia.
+///
+/// Piper la busca junto a su ejecutable; las instalaciones del sistema en
+/// Linux la dejan en `/usr/share` o en `/usr/lib/<arquitectura>`.
+fn candidatos_espeak(ruta_piper: &Path) -> Vec<PathBuf> {
+    let mut candidatos = Vec::new();
+    if let Some(ruta) = std::env::var_os("ESPEAK_DATA_PATH") {
+        candidatos.push(PathBuf::from(ruta).join("espeak-ng-data"));
+    }
+    if let Some(dir) = ruta_piper.parent() {
+        candidatos.push(dir.join("espeak-ng-data"));
+    }
+    if cfg!(unix) {
+        candidatos.push(PathBuf::from("/usr/share/espeak-ng-data"));
+        candidatos.push(
+            Path::new("/usr/lib")
+                .join(triplete_multiarch())
+                .join("espeak-ng-data"),
+        );
+        candidatos.push(PathBuf::from("/usr/lib/espeak-ng-data"));
+    }
+    candidatos
+}
+
+/// `espeak-ng-data` se encuentra en alguno de los lugares habituales.
+fn comprobar_espeak(ruta_piper: &Path) -> Resultado {
+    let candidatos = candidatos_espeak(ruta_piper);
+    candidatos
+        .iter()
+        .find(|dir| dir.is_dir())
+        .map(|dir| dir.display().to_string())
+        .ok_or_else(|| {
+            let buscados: Vec<String> = candidatos
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect();
+            Fallo::new(
+                format!("no encontrada en: {}", buscados.join(", ")),
+                "Copia la carpeta espeak-ng-data de la release de Piper junto a su ejecutable",
+            )
+        })
+}
+
+/// Se puede abrir el dispositivo de salida de audio por defecto.
+fn comprobar_audio() -> Resultado {
+    OutputStream::try_default()
+        .map(|_| "dispositivo por defecto disponible".to_string())
+        .map_err(|e| {
+            Fallo::new(
+                e.to_string(),
+                "Conecta o activa un dispositivo de salida y compruébalo en la configuración de sonido",
+            )
+        })
+}
+
+/// Se puede acceder al portapapeles.
+fn comprobar_portapapeles() -> Resultado {
+    Clipboard::new()
+        .map(|_| "accesible".to_string())
+        .map_err(|e| {
+            Fallo::new(
+                e.to_string(),
+                "Comprueba que hay una sesión gráfica activa (en Linux, DISPLAY o WAYLAND_DISPLAY)",
+            )
+        })
+}
+
+/// El motor configurado genera audio a partir de un texto corto.
+fn comprobar_sintesis(motor: &str) -> Resultado {
+    let audio = tts::sintetizar(TEXTO_PRUEBA, None).map_err(|e| {
+        Fallo::new(
+            e,
+            "Revisa las comprobaciones anteriores; ejecuta el motor a mano para ver su salida completa",
+        )
+    })?;
+
+    if audio.muestras.is_empty() {
+        return Err(Fallo::new(
+            "el audio generado está vacío",
+            "Comprueba que el modelo corresponde a la versión de Piper instalada",
+        ));
+    }
+
+    Ok(format!(
+        "{:.1} s de audio con {}",
+        audio.duracion().as_secs_f32(),
+        motor
+    ))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// INFORME
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Escribe una línea del informe en la consola y en el log, donde queda
+/// aunque el informe no se vea (lanzado sin terminal en Windows).
+fn informar(linea: &str) {
+    println!("{}", linea);
+    if !linea.is_empty() {
+        log(&format!("doctor: {}", linea));
+    }
+}
+
+/// Ejecuta todas las comprobaciones e imprime el informe (también en el
+/// log).
+///
+/// Devuelve `true` si todas pasaron.
+pub fn ejecutar(ruta_config: &Path) -> bool {
+    let config = config::actual();
+
+    informar(&format!("TrayNarrator v{} — diagnóstico", VERSION));
+    informar(&format!("Configuración: {}", ruta_config.display()));
+    informar(&format!("Motor: {}", config.motor.nombre()));
+    informar("");
+
+    let comprobaciones: [(&str, Resultado); 6] = [
+        ("Ejecutable de Piper", comprobar_piper(&config.ruta_piper)),
+        ("Modelo de voz", comprobar_modelo(&config.ruta_modelo)),
+        ("Datos de espeak-ng", comprobar_espeak(&config.ruta_piper)),
+        ("Salida de audio", comprobar_audio()),
+        ("Portapapeles", comprobar_portapapeles()),
+        (
+            "Síntesis de prueba",
+            comprobar_sintesis(config.motor.nombre()),
+        ),
+    ];
+
+    let mut correctas = 0;
+    for (nombre, resultado) in &comprobaciones {
+        match resultado {
+            Ok(detalle) => {
+                correctas += 1;
+                informar(&format!("[ OK  ] {}: {}", nombre, detalle));
+            }
+            Err(fallo) => {
+                informar(&format!("[FALLO] {}: {}", nombre, fallo.detalle));
+                informar(&format!("        → {}", fallo.remedio));
+            }
+        }
+    }
+
+    informar("");
+    informar(&format!(
+        "Resultado: {}/{} comprobaciones correctas",
+        correctas,
+        comprobaciones.len()
+    ));
+
+    correctas == comprobaciones.len()
+}
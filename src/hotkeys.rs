@@ -7,11 +7,12 @@ use inputbot::KeybdKey;
 
 use crate::clipboard::{leer_portapapeles, simular_copiar};
 use crate::logging::log;
+use crate::narrator;
+use crate::profiles::ciclar_perfil;
 use crate::state::{
-    enviar_comando, velocidad_preset_mas_lento, velocidad_preset_mas_rapido, ComandoAudio,
-    ESTADO_AUDIO, ESTADO_IDLE,
+    ciclar_nivel_puntuacion, enviar_comando, nivel_puntuacion, velocidad_mas_lento,
+    velocidad_mas_rapido, ComandoAudio, ESTADO_AUDIO, ESTADO_IDLE,
 };
-use crate::tts::generar_audio_piper;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // MANEJADORES DE TECLAS
@@ -19,7 +20,7 @@ use crate::tts::generar_audio_piper;
 
 fn manejar_f8() {
     log("=== F8 presionado ===");
-    enviar_comando(ComandoAudio::Detener);
+    narrator::cancelar();
 
     log("Simulando Ctrl+C...");
     if let Err(e) = simular_copiar() {
@@ -39,13 +40,31 @@ fn manejar_f8() {
         }
     };
 
-    if let Err(e) = generar_audio_piper(&texto) {
-        log(&format!("Error Piper: {}", e));
+    if let Err(e) = narrator::narrar(&texto) {
+        log(&format!("Error TTS: {}", e));
+    }
+}
+
+/// Mayús+F8: copia la selección y la deletrea.
+fn manejar_deletrear() {
+    log("=== Mayús+F8 presionado ===");
+    narrator::cancelar();
+
+    if let Err(e) = simular_copiar() {
+        log(&format!("Error Ctrl+C: {}", e));
         return;
     }
+    let texto = match leer_portapapeles() {
+        Ok(t) => t,
+        Err(e) => {
+            log(&format!("Error portapapeles: {}", e));
+            return;
+        }
+    };
 
-    log("Enviando Reproducir...");
-    enviar_comando(ComandoAudio::Reproducir);
+    if let Err(e) = narrator::deletrear(&texto) {
+        log(&format!("Error TTS: {}", e));
+    }
 }
 
 fn manejar_f9() {
@@ -55,14 +74,27 @@ fn manejar_f9() {
     }
 }
 
+fn manejar_f7() {
+    log("F7 - Siguiente perfil");
+    ciclar_perfil();
+}
+
+fn manejar_f6() {
+    ciclar_nivel_puntuacion();
+    log(&format!(
+        "F6 - Puntuación → {}",
+        nivel_puntuacion().etiqueta()
+    ));
+}
+
 fn manejar_mas_rapido() {
     log("Ctrl+[ - Más rápido");
-    velocidad_preset_mas_rapido();
+    velocidad_mas_rapido();
 }
 
 fn manejar_mas_lento() {
     log("Ctrl+] - Más lento");
-    velocidad_preset_mas_lento();
+    velocidad_mas_lento();
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -73,9 +105,23 @@ fn manejar_mas_lento() {
 pub fn hilo_inputbot() {
     log("Hilo inputbot iniciado");
 
-    // F8: Leer
+    // F8: Leer; Mayús+F8: Deletrear
     KeybdKey::F8Key.bind(|| {
-        thread::spawn(manejar_f8);
+        if KeybdKey::LShiftKey.is_pressed() || KeybdKey::RShiftKey.is_pressed() {
+            thread::spawn(manejar_deletrear);
+        } else {
+            thread::spawn(manejar_f8);
+        }
+    });
+
+    // F7: Siguiente perfil
+    KeybdKey::F7Key.bind(|| {
+        manejar_f7();
+    });
+
+    // F6: Siguiente nivel de puntuación
+    KeybdKey::F6Key.bind(|| {
+        manejar_f6();
     });
 
     // F9: Pausar/Reanudar
@@ -83,14 +129,14 @@ pub fn hilo_inputbot() {
         manejar_f9();
     });
 
-    // Ctrl+[ : Más rápido (siguiente preset)
+    // Ctrl+[ : Más rápido (siguiente preset o un paso de ajuste fino)
     KeybdKey::LBracketKey.bind(|| {
         if KeybdKey::LControlKey.is_pressed() || KeybdKey::RControlKey.is_pressed() {
             manejar_mas_rapido();
         }
     });
 
-    // Ctrl+] : Más lento (preset anterior)
+    // Ctrl+] : Más lento (preset anterior o un paso de ajuste fino)
     KeybdKey::RBracketKey.bind(|| {
         if KeybdKey::LControlKey.is_pressed() || KeybdKey::RControlKey.is_pressed() {
             manejar_mas_lento();
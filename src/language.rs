@@ -0,0 +1,471 @@
+//! Detección del idioma del texto.
+//!
+//! Si `voces_idioma` asigna voces a idiomas, cada lectura detecta el idioma
+//! dominante del texto (con whatlang) y se lee con la voz de ese idioma. Si la
+//! detección no es fiable, o el idioma no tiene voz asignada, se usa la voz
+//! por defecto.
+//!
+//! Además, cada frase se divide en tramos de un mismo idioma (con un mínimo
+//! de palabras por tramo) para leer con su voz los términos en otro idioma
+//! dentro de un texto en español, y viceversa.
+//!
+//! Los idiomas se identifican por su código ISO 639-1 (`es`, `en`), que es el
+//! que usan los nombres de las voces de Piper (`es_ES-...`) y de espeak-ng.
+
+use std::path::Path;
+
+use whatlang::{Detector, Lang};
+
+use crate::config::{self, Config};
+use crate::logging::log;
+use crate::voices;
+
+/// Códigos ISO 639-1 de los idiomas que reconoce whatlang.
+const CODIGOS: &[(Lang, &str)] = &[
+    (Lang::Afr, "af"),
+    (Lang::Aka, "ak"),
+    (Lang::Amh, "am"),
+    (Lang::Ara, "ar"),
+    (Lang::Aze, "az"),
+    (Lang::Bel, "be"),
+    (Lang::Ben, "bn"),
+    (Lang::Bul, "bg"),
+    (Lang::Cat, "ca"),
+    (Lang::Ces, "cs"),
+    (Lang::Cmn, "zh"),
+    (Lang::Dan, "da"),
+    (Lang::Deu, "de"),
+    (Lang::Ell, "el"),
+    (Lang::Eng, "en"),
+    (Lang::Epo, "eo"),
+    (Lang::Est, "et"),
+    (Lang::Fin, "fi"),
+    (Lang::Fra, "fr"),
+    (Lang::Guj, "gu"),
+    (Lang::Heb, "he"),
+    (Lang::Hin, "hi"),
+    (Lang::Hrv, "hr"),
+    (Lang::Hun, "hu"),
+    (Lang::Hye, "hy"),
+    (Lang::Ind, "id"),
+    (Lang::Ita, "it"),
+    (Lang::Jav, "jv"),
+    (Lang::Jpn, "ja"),
+    (Lang::Kan, "kn"),
+    (Lang::Kat, "ka"),
+    (Lang::Khm, "km"),
+    (Lang::Kor, "ko"),
+    (Lang::Lat, "la"),
+    (Lang::Lav, "lv"),
+    (Lang::Lit, "lt"),
+    (Lang::Mal, "ml"),
+    (Lang::Mar, "mr"),
+    (Lang::Mkd, "mk"),
+    (Lang::Mya, "my"),
+    (Lang::Nep, "ne"),
+    (Lang::Nld, "nl"),
+    (Lang::Nob, "nb"),
+    (Lang::Ori, "or"),
+    (Lang::Pan, "pa"),
+    (Lang::Pes, "fa"),
+    (Lang::Pol, "pl"),
+    (Lang::Por, "pt"),
+    (Lang::Ron, "ro"),
+    (Lang::Rus, "ru"),
+    (Lang::Sin, "si"),
+    (Lang::Slk, "sk"),
+    (Lang::Slv, "sl"),
+    (Lang::Sna, "sn"),
+    (Lang::Spa, "es"),
+    (Lang::Srp, "sr"),
+    (Lang::Swe, "sv"),
+    (Lang::Tam, "ta"),
+    (Lang::Tel, "te"),
+    (Lang::Tgl, "tl"),
+    (Lang::Tha, "th"),
+    (Lang::Tuk, "tk"),
+    (Lang::Tur, "tr"),
+    (Lang::Ukr, "uk"),
+    (Lang::Urd, "ur"),
+    (Lang::Uzb, "uz"),
+    (Lang::Vie, "vi"),
+    (Lang::Yid, "yi"),
+    (Lang::Zul, "zu"),
+];
+
+/// Código ISO 639-1 de un idioma de whatlang.
+fn codigo_iso(lang: Lang) -> &'static str {
+    CODIGOS
+        .iter()
+        .find(|(l, _)| *l == lang)
+        .map_or_else(|| lang.code(), |(_, codigo)| codigo)
+}
+
+/// Idioma de whatlang para un código ISO 639-1 (`es`) o 639-3 (`spa`).
+fn lang_de_codigo(codigo: &str) -> Option<Lang> {
+    let codigo = codigo.to_lowercase();
+    CODIGOS
+        .iter()
+        .find(|(_, c)| *c == codigo)
+        .map(|(lang, _)| *lang)
+        .or_else(|| Lang::from_code(codigo))
+}
+
+/// `true` si el código corresponde a un idioma que se puede detectar.
+pub fn idioma_conocido(codigo: &str) -> bool {
+    lang_de_codigo(codigo).is_some()
+}
+
+/// Código ISO 639-1 normalizado (`ES`, `spa` → `es`). Devuelve el código en
+/// minúsculas tal cual si no es un idioma conocido.
+pub fn normalizar(codigo: &str) -> String {
+    lang_de_codigo(codigo).map_or_else(
+        || codigo.to_lowercase(),
+        |lang| codigo_iso(lang).to_string(),
+    )
+}
+
+/// Idioma de una voz: el de su `.onnx.json` en Piper (`es_ES` → `es`) o el
+/// prefijo del nombre en espeak-ng (`en-us` → `en`).
+pub fn idioma_de_voz(voz: &Path) -> Option<String> {
+    let idioma = match voices::info_voz(voz) {
+        Some(info) => info.idioma,
+        None => voz.file_stem()?.to_string_lossy().into_owned(),
+    };
+    let prefijo = idioma.split(['_', '-']).next()?;
+    lang_de_codigo(prefijo).map(|lang| codigo_iso(lang).to_string())
+}
+
+/// Detector de whatlang limitado a `candidatos` (códigos ISO 639-1). Con
+/// menos de dos candidatos considera todos los idiomas: con uno solo
+/// whatlang siempre lo elegiría.
+fn detector(candidatos: &[String]) -> Detector {
+    let permitidos: Vec<Lang> = candidatos
+        .iter()
+        .filter_map(|c| lang_de_codigo(c))
+        .collect();
+    if permitidos.len() > 1 {
+        Detector::with_allowlist(permitidos)
+    } else {
+        Detector::new()
+    }
+}
+
+/// Detecta el idioma dominante de `texto` entre `candidatos` (códigos ISO
+/// 639-1; vacío = todos). Devuelve `None` si la confianza no llega a
+/// `confianza_minima`.
+pub fn detectar(texto: &str, candidatos: &[String], confianza_minima: f32) -> Option<String> {
+    let info = detector(candidatos).detect(texto)?;
+    if info.confidence() < f64::from(confianza_minima) {
+        log(&format!(
+            "Idioma incierto ({}, confianza {:.2}): se usa la voz por defecto",
+            codigo_iso(info.lang()),
+            info.confidence()
+        ));
+        return None;
+    }
+    Some(codigo_iso(info.lang()).to_string())
+}
+
+/// Idiomas entre los que se detecta: los de `voces_idioma` más el de la voz
+/// por defecto, para no confundir el idioma habitual con otro parecido.
+fn candidatos(config: &Config, voz_defecto: &Path) -> Vec<String> {
+    let mut candidatos: Vec<String> = config.voces_idioma.keys().map(|c| normalizar(c)).collect();
+    if let Some(idioma) = idioma_de_voz(voz_defecto) {
+        if !candidatos.contains(&idioma) {
+            candidatos.push(idioma);
+        }
+    }
+    candidatos
+}
+
+/// Idioma dominante del texto para elegir voz, o `None` si no hay voces por
+/// idioma configuradas o la detección no es fiable.
+pub fn idioma_del_texto(texto: &str, voz_defecto: &Path) -> Option<String> {
+    let config = config::actual();
+    if config.voces_idioma.is_empty() {
+        return None;
+    }
+
+    let idioma = detectar(
+        texto,
+        &candidatos(&config, voz_defecto),
+        config.confianza_idioma,
+    )?;
+    log(&format!("Idioma detectado: {}", idioma));
+    Some(idioma)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRAMOS DE IDIOMA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Parte de una frase en un mismo idioma.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tramo {
+    pub texto: String,
+    /// Código ISO 639-1, o `None` para la voz por defecto
+    pub idioma: Option<String>,
+}
+
+/// Divide una frase en tramos de idioma para leer cada uno con su voz
+/// (`palabras_minimas_tramo`). Sin voces por idioma, o con el mínimo a 0,
+/// devuelve la frase entera en `idioma_texto`.
+pub fn tramos(frase: &str, idioma_texto: Option<&str>, voz_defecto: &Path) -> Vec<Tramo> {
+    let config = config::actual();
+    if config.voces_idioma.is_empty() || config.palabras_minimas_tramo == 0 {
+        return vec![Tramo {
+            texto: frase.to_string(),
+            idioma: idioma_texto.map(str::to_string),
+        }];
+    }
+
+    let tramos = dividir_en_tramos(
+        frase,
+        idioma_texto,
+        &candidatos(&config, voz_defecto),
+        config.confianza_idioma,
+        config.palabras_minimas_tramo as usize,
+    );
+    if tramos.len() > 1 {
+        let resumen: Vec<String> = tramos
+            .iter()
+            .map(|t| {
+                format!(
+                    "{} ({} palabras)",
+                    t.idioma.as_deref().unwrap_or("?"),
+                    t.texto.split_whitespace().count()
+                )
+            })
+            .collect();
+        log(&format!("Frase con varios idiomas: {}", resumen.join(", ")));
+    }
+    tramos
+}
+
+/// Divide `frase` en tramos de al menos `palabras_minimas` palabras.
+///
+/// Primero se detecta el idioma de la frase completa (si no es fiable se usa
+/// `idioma_base`). Después cada ventana de palabras vota, con su confianza,
+/// por el idioma que detecta, y cada palabra se queda con el más votado. Los
+/// tramos más cortos que el mínimo se funden con el anterior (o el siguiente)
+/// para no cambiar de voz por una palabra suelta.
+fn dividir_en_tramos(
+    frase: &str,
+    idioma_base: Option<&str>,
+    candidatos: &[String],
+    confianza_minima: f32,
+    palabras_minimas: usize,
+) -> Vec<Tramo> {
+    let palabras: Vec<&str> = frase.split_whitespace().collect();
+    let detector = detector(candidatos);
+    let idioma_frase = detector
+        .detect(frase)
+        .filter(|info| info.confidence() >= f64::from(confianza_minima))
+        .map(|info| codigo_iso(info.lang()).to_string())
+        .or_else(|| idioma_base.map(str::to_string));
+
+    if palabras.len() <= palabras_minimas {
+        return vec![Tramo {
+            texto: frase.to_string(),
+            idioma: idioma_frase,
+        }];
+    }
+
+    // Votos por palabra: idioma → suma de confianzas de las ventanas que la
+    // contienen
+    let ventana = palabras_minimas.max(3);
+    let mut votos: Vec<Vec<(Lang, f64)>> = vec![Vec::new(); palabras.len()];
+    for inicio in 0..=palabras.len().saturating_sub(ventana) {
+        let fin = (inicio + ventana).min(palabras.len());
+        let Some(info) = detector.detect(&palabras[inicio..fin].join(" ")) else {
+            continue;
+        };
+        for votos_palabra in &mut votos[inicio..fin] {
+            match votos_palabra
+                .iter_mut()
+                .find(|(lang, _)| *lang == info.lang())
+            {
+                Some((_, suma)) => *suma += info.confidence(),
+                None => votos_palabra.push((info.lang(), info.confidence())),
+            }
+        }
+    }
+
+    let idiomas: Vec<Option<String>> = votos
+        .iter()
+        .map(|votos_palabra| {
+            votos_palabra
+                .iter()
+                .filter(|(_, suma)| *suma > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(lang, _)| codigo_iso(*lang).to_string())
+                .or_else(|| idioma_frase.clone())
+        })
+        .collect();
+
+    // Tramos como (idioma, número de palabras)
+    let mut tramos: Vec<(Option<String>, usize)> = Vec::new();
+    for idioma in idiomas {
+        match tramos.last_mut() {
+            Some((ultimo, palabras)) if *ultimo == idioma => *palabras += 1,
+            _ => tramos.push((idioma, 1)),
+        }
+    }
+    fundir_tramos_cortos(&mut tramos, palabras_minimas);
+    ajustar_a_pausas(&mut tramos, &palabras);
+
+    let mut siguiente = 0;
+    tramos
+        .into_iter()
+        .map(|(idioma, num_palabras)| {
+            let texto = palabras[siguiente..siguiente + num_palabras].join(" ");
+            siguiente += num_palabras;
+            Tramo { texto, idioma }
+        })
+        .collect()
+}
+
+/// Palabras alrededor de un cambio de idioma donde se busca una pausa.
+const DISTANCIA_PAUSA: usize = 2;
+
+/// Mueve cada cambio de idioma a la pausa (coma, punto y coma, dos puntos)
+/// más cercana, si hay una a `DISTANCIA_PAUSA` palabras o menos: las
+/// ventanas tienden a detectar el cambio una o dos palabras tarde.
+fn ajustar_a_pausas(tramos: &mut [(Option<String>, usize)], palabras: &[&str]) {
+    let termina_en_pausa = |idx: usize| palabras[idx].ends_with([',', ';', ':']);
+
+    let mut inicio_anterior = 0;
+    for i in 1..tramos.len() {
+        let cambio = inicio_anterior + tramos[i - 1].1;
+        let fin_siguiente = cambio + tramos[i].1;
+
+        // Nuevo cambio en `j`: tras la palabra `j - 1`, sin vaciar ningún tramo
+        let minimo = (inicio_anterior + 1).max(cambio.saturating_sub(DISTANCIA_PAUSA));
+        let maximo = (fin_siguiente - 1).min(cambio + DISTANCIA_PAUSA);
+        let pausa = (minimo..=maximo)
+            .filter(|&j| termina_en_pausa(j - 1))
+            .min_by_key(|&j| j.abs_diff(cambio));
+
+        if let Some(j) = pausa {
+            tramos[i - 1].1 = j - inicio_anterior;
+            tramos[i].1 = fin_siguiente - j;
+        }
+        inicio_anterior += tramos[i - 1].1;
+    }
+}
+
+/// Funde cada tramo de menos de `palabras_minimas` palabras con el anterior
+/// (o con el siguiente si es el primero) hasta que no quede ninguno corto.
+fn fundir_tramos_cortos(tramos: &mut Vec<(Option<String>, usize)>, palabras_minimas: usize) {
+    while tramos.len() > 1 {
+        let Some(corto) = tramos.iter().position(|(_, n)| *n < palabras_minimas) else {
+            break;
+        };
+        let (_, palabras) = tramos.remove(corto);
+        let vecino = corto.saturating_sub(1).min(tramos.len() - 1);
+        tramos[vecino].1 += palabras;
+
+        // Al quitar un tramo pueden quedar juntos dos del mismo idioma
+        let mut i = 1;
+        while i < tramos.len() {
+            if tramos[i].0 == tramos[i - 1].0 {
+                let (_, palabras) = tramos.remove(i);
+                tramos[i - 1].1 += palabras;
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tramo(idioma: Option<&str>, palabras: usize) -> (Option<String>, usize) {
+        (idioma.map(str::to_string), palabras)
+    }
+
+    #[test]
+    fn funde_tramos_cortos() {
+        // Uno corto en medio se funde con el anterior, y los dos vecinos del
+        // mismo idioma quedan en uno
+        let mut tramos = vec![
+            tramo(Some("es"), 5),
+            tramo(Some("en"), 1),
+            tramo(Some("es"), 4),
+        ];
+        fundir_tramos_cortos(&mut tramos, 3);
+        assert_eq!(tramos, [tramo(Some("es"), 10)]);
+
+        // El primero, si es corto, se funde con el siguiente
+        let mut tramos = vec![
+            tramo(Some("en"), 2),
+            tramo(Some("es"), 6),
+            tramo(Some("en"), 4),
+        ];
+        fundir_tramos_cortos(&mut tramos, 3);
+        assert_eq!(tramos, [tramo(Some("es"), 8), tramo(Some("en"), 4)]);
+
+        // Un único tramo se deja aunque sea corto
+        let mut tramos = vec![tramo(None, 1)];
+        fundir_tramos_cortos(&mut tramos, 3);
+        assert_eq!(tramos, [tramo(None, 1)]);
+    }
+
+    #[test]
+    fn ajusta_a_pausas() {
+        let palabras: Vec<&str> = "Abre el archivo de configuración, then restart the server now"
+            .split_whitespace()
+            .collect();
+
+        // Cambio detectado una palabra tarde: vuelve a la coma
+        let mut tramos = vec![tramo(Some("es"), 6), tramo(Some("en"), 4)];
+        ajustar_a_pausas(&mut tramos, &palabras);
+        assert_eq!(tramos, [tramo(Some("es"), 5), tramo(Some("en"), 5)]);
+
+        // Y una palabra pronto
+        let mut tramos = vec![tramo(Some("es"), 4), tramo(Some("en"), 6)];
+        ajustar_a_pausas(&mut tramos, &palabras);
+        assert_eq!(tramos, [tramo(Some("es"), 5), tramo(Some("en"), 5)]);
+
+        // Sin pausa cerca, el cambio se queda donde estaba
+        let mut tramos = vec![tramo(Some("es"), 2), tramo(Some("en"), 8)];
+        ajustar_a_pausas(&mut tramos, &palabras);
+        assert_eq!(tramos, [tramo(Some("es"), 2), tramo(Some("en"), 8)]);
+    }
+
+    #[test]
+    fn divide_en_tramos() {
+        let candidatos = ["es".to_string(), "en".to_string()];
+        let frase = "Abre el archivo de configuración que está en la carpeta del programa, \
+                     then restart the server and check that everything works";
+        let tramos = dividir_en_tramos(frase, Some("es"), &candidatos, 0.0, 3);
+        assert_eq!(
+            tramos,
+            [
+                Tramo {
+                    texto: "Abre el archivo de configuración que está en la carpeta del programa,"
+                        .to_string(),
+                    idioma: Some("es".to_string()),
+                },
+                Tramo {
+                    texto: "then restart the server and check that everything works".to_string(),
+                    idioma: Some("en".to_string()),
+                },
+            ]
+        );
+
+        // Una frase no más larga que el mínimo no se divide; sin detección
+        // fiable se usa el idioma base
+        let tramos = dividir_en_tramos("Hola, world", Some("es"), &candidatos, 1.1, 3);
+        assert_eq!(
+            tramos,
+            [Tramo {
+                texto: "Hola, world".to_string(),
+                idioma: Some("es".to_string()),
+            }]
+        );
+    }
+}
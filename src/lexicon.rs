@@ -0,0 +1,438 @@
+//! Léxico de pronunciación del usuario.
+//!
+//! `lexico.toml` (ver `lexico.example.toml`) sustituye palabras que la voz
+//! pronuncia mal antes de sintetizar: por palabra completa, distinguiendo o
+//! no mayúsculas, o con expresiones regulares. Cada regla puede limitarse a
+//! una voz o a un idioma. El archivo se vigila como el de configuración y un
+//! cambio inválido conserva las reglas anteriores.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::Deserialize;
+
+use crate::config::{self, fecha_modificacion, INTERVALO_VIGILANCIA};
+use crate::language;
+use crate::logging::log;
+use crate::state::DEBE_SALIR;
+
+/// Contenido con el que se crea el léxico desde el tray si no existe.
+const PLANTILLA: &str = include_str!("../lexico.example.toml");
+
+/// Formato del archivo.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ArchivoLexico {
+    reglas: Vec<Entrada>,
+}
+
+/// Regla tal como se escribe en `[[reglas]]`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entrada {
+    /// Palabra a sustituir, o expresión regular con `regex = true`
+    buscar: String,
+    /// Texto que se lee en su lugar
+    decir: String,
+    /// Distinguir mayúsculas y minúsculas
+    #[serde(default)]
+    mayusculas: bool,
+    /// `buscar` es una expresión regular (y `decir` admite `$1`)
+    #[serde(default)]
+    regex: bool,
+    /// Solo con esta voz (nombre del modelo sin extensión)
+    #[serde(default)]
+    voz: Option<String>,
+    /// Solo en texto de este idioma (ISO 639-1)
+    #[serde(default)]
+    idioma: Option<String>,
+}
+
+/// Regla compilada.
+#[derive(Debug)]
+struct Regla {
+    patron: Regex,
+    decir: String,
+    /// Si `decir` expande grupos (`$1`); las palabras se sustituyen tal cual
+    expandir: bool,
+    voz: Option<String>,
+    idioma: Option<String>,
+}
+
+impl Regla {
+    /// Compila una entrada; `numero` (desde 1) identifica la regla en los
+    /// mensajes de error.
+    fn compilar(entrada: Entrada, numero: usize) -> Result<Regla, String> {
+        if entrada.buscar.trim().is_empty() {
+            return Err(format!("regla {}: 'buscar' no puede estar vacío", numero));
+        }
+        if entrada.voz.as_ref().is_some_and(|v| v.trim().is_empty()) {
+            return Err(format!("regla {}: 'voz' no puede estar vacía", numero));
+        }
+        if let Some(idioma) = &entrada.idioma {
+            if !language::idioma_conocido(idioma) {
+                return Err(format!(
+                    "regla {}: idioma desconocido '{}' (usa un código ISO 639-1: es, en…)",
+                    numero, idioma
+                ));
+            }
+        }
+
+        let patron = if entrada.regex {
+            entrada.buscar.clone()
+        } else {
+            patron_palabra(&entrada.buscar)
+        };
+        let patron = RegexBuilder::new(&patron)
+            .case_insensitive(!entrada.mayusculas)
+            .build()
+            .map_err(|e| format!("regla {}: expresión regular inválida: {}", numero, e))?;
+
+        Ok(Regla {
+            patron,
+            decir: entrada.decir,
+            expandir: entrada.regex,
+            voz: entrada.voz,
+            idioma: entrada.idioma.as_deref().map(language::normalizar),
+        })
+    }
+
+    /// Si la regla se aplica con esta voz y en este idioma.
+    fn aplica(&self, voz: &str, idioma: Option<&str>) -> bool {
+        self.voz.as_deref().map_or(true, |v| v == voz)
+            && self.idioma.as_deref().map_or(true, |i| Some(i) == idioma)
+    }
+
+    /// Orden de aplicación: primero las de una voz, luego las de un idioma y
+    /// por último las generales.
+    fn prioridad(&self) -> u8 {
+        match (&self.voz, &self.idioma) {
+            (Some(_), _) => 0,
+            (None, Some(_)) => 1,
+            (None, None) => 2,
+        }
+    }
+}
+
+/// Patrón de una palabra completa. Los límites de palabra solo se exigen en
+/// los extremos que son letras o números, para que "C++" o ".NET" también
+/// funcionen.
+fn patron_palabra(palabra: &str) -> String {
+    let limite = |c: Option<char>| {
+        if c.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            r"\b"
+        } else {
+            ""
+        }
+    };
+    format!(
+        "{}{}{}",
+        limite(palabra.chars().next()),
+        regex::escape(palabra),
+        limite(palabra.chars().next_back())
+    )
+}
+
+/// Reglas activas y el archivo del que salieron.
+#[derive(Debug, Default)]
+struct Lexico {
+    ruta: PathBuf,
+    modificacion: Option<SystemTime>,
+    reglas: Arc<Vec<Regla>>,
+}
+
+lazy_static! {
+    static ref LEXICO: RwLock<Lexico> = RwLock::new(Lexico::default());
+}
+
+/// Interpreta y compila el contenido de un léxico.
+fn parsear_lexico(contenido: &str) -> Result<Vec<Regla>, String> {
+    let archivo: ArchivoLexico = toml::from_str(contenido).map_err(|e| e.to_string())?;
+    let mut reglas = archivo
+        .reglas
+        .into_iter()
+        .enumerate()
+        .map(|(i, entrada)| Regla::compilar(entrada, i + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Estable: dentro de cada grupo se respeta el orden del archivo
+    reglas.sort_by_key(Regla::prioridad);
+    Ok(reglas)
+}
+
+/// Lee el léxico de `ruta`. Un archivo ausente es un léxico vacío.
+fn leer_lexico(ruta: &Path) -> Result<Vec<Regla>, String> {
+    match fs::read_to_string(ruta) {
+        Ok(contenido) => parsear_lexico(&contenido),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("error leyendo el archivo: {}", e)),
+    }
+}
+
+/// Vuelve a leer el léxico de la configuración activa. Si tiene errores se
+/// conservan las reglas anteriores (o ninguna, al arrancar).
+pub fn cargar() {
+    let ruta = config::actual().ruta_lexico.clone();
+    let modificacion = fecha_modificacion(&ruta);
+
+    let resultado = leer_lexico(&ruta);
+
+    // La fecha se recuerda también si falla, para no repetir el aviso hasta
+    // el próximo cambio
+    let mut lexico = LEXICO.write();
+    match resultado {
+        Ok(reglas) => {
+            if modificacion.is_some() {
+                log(&format!(
+                    "Léxico cargado desde {}: {} reglas",
+                    ruta.display(),
+                    reglas.len()
+                ));
+            }
+            lexico.reglas = Arc::new(reglas);
+        }
+        Err(e) => log(&format!(
+            "Léxico inválido en {}: {}. Se mantienen las reglas anteriores",
+            ruta.display(),
+            e
+        )),
+    }
+    lexico.ruta = ruta;
+    lexico.modificacion = modificacion;
+}
+
+/// Hilo que recarga el léxico cuando cambia el archivo o su ruta en la
+/// configuración.
+pub fn hilo_vigilancia_lexico() {
+    while !DEBE_SALIR.load(Ordering::SeqCst) {
+        thread::sleep(INTERVALO_VIGILANCIA);
+
+        let ruta = config::actual().ruta_lexico.clone();
+        let cambiado = {
+            let lexico = LEXICO.read();
+            ruta != lexico.ruta || fecha_modificacion(&ruta) != lexico.modificacion
+        };
+        if cambiado {
+            cargar();
+        }
+    }
+}
+
+/// Aplica las reglas que corresponden a la voz y al idioma del texto.
+pub fn aplicar(texto: &str, voz: &Path, idioma: Option<&str>) -> String {
+    let reglas = LEXICO.read().reglas.clone();
+    if reglas.is_empty() {
+        return texto.to_string();
+    }
+
+    let nombre_voz = voz
+        .file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    aplicar_reglas(&reglas, texto, &nombre_voz, idioma)
+}
+
+/// Aplica en orden las reglas de `reglas` que corresponden a la voz (nombre
+/// del modelo sin extensión) y al idioma.
+fn aplicar_reglas(reglas: &[Regla], texto: &str, voz: &str, idioma: Option<&str>) -> String {
+    let mut texto = texto.to_string();
+    for regla in reglas.iter().filter(|r| r.aplica(voz, idioma)) {
+        texto = if regla.expandir {
+            regla.patron.replace_all(&texto, regla.decir.as_str())
+        } else {
+            regla.patron.replace_all(&texto, NoExpand(&regla.decir))
+        }
+        .into_owned();
+    }
+    texto
+}
+
+/// Abre el léxico en el editor del sistema, creándolo a partir de la
+/// plantilla si todavía no existe.
+pub fn abrir_en_editor() -> Result<(), String> {
+    let ruta = config::actual().ruta_lexico.clone();
+    if !ruta.exists() {
+        if let Some(dir) = ruta.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("No se pudo crear {}: {}", dir.display(), e))?;
+        }
+        fs::write(&ruta, PLANTILLA)
+            .map_err(|e| format!("No se pudo crear {}: {}", ruta.display(), e))?;
+        log(&format!("Léxico creado en {}", ruta.display()));
+    }
+
+    // El Bloc de notas siempre está disponible; un .toml puede no tener
+    // programa asociado
+    #[cfg(windows)]
+    let mut comando = Command::new("notepad.exe");
+    #[cfg(not(windows))]
+    let mut comando = Command::new("xdg-open");
+
+    let mut hijo = comando
+        .arg(&ruta)
+        .spawn()
+        .map_err(|e| format!("No se pudo abrir {}: {}", ruta.display(), e))?;
+    // Recoger el proceso al terminar sin bloquear el tray
+    thread::spawn(move || hijo.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aplicar_toml(contenido: &str, texto: &str, voz: &str, idioma: Option<&str>) -> String {
+        let reglas = parsear_lexico(contenido).unwrap();
+        aplicar_reglas(&reglas, texto, voz, idioma)
+    }
+
+    #[test]
+    fn palabras_completas() {
+        let lexico = r#"
+            [[reglas]]
+            buscar = "gui"
+            decir = "güi"
+
+            [[reglas]]
+            buscar = "C++"
+            decir = "ce más más"
+
+            [[reglas]]
+            buscar = ".NET"
+            decir = "punto net"
+        "#;
+        assert_eq!(
+            aplicar_toml(lexico, "La GUI y la guitarra", "voz", None),
+            "La güi y la guitarra"
+        );
+        assert_eq!(
+            aplicar_toml(lexico, "Usa C++, no C+ ni C++20.", "voz", None),
+            "Usa ce más más, no C+ ni ce más más20."
+        );
+        assert_eq!(
+            aplicar_toml(lexico, "En .NET 8 o en .net", "voz", None),
+            "En punto net 8 o en punto net"
+        );
+        assert_eq!(patron_palabra("C++"), r"\bC\+\+");
+        assert_eq!(patron_palabra(".NET"), r"\.NET\b");
+    }
+
+    #[test]
+    fn mayusculas() {
+        let lexico = r#"
+            [[reglas]]
+            buscar = "GUI"
+            decir = "gui"
+            mayusculas = true
+        "#;
+        assert_eq!(
+            aplicar_toml(lexico, "GUI, Gui y gui", "voz", None),
+            "gui, Gui y gui"
+        );
+    }
+
+    #[test]
+    fn expresiones_regulares() {
+        let lexico = r#"
+            [[reglas]]
+            buscar = '\bv(\d+(?:\.\d+)*)\b'
+            decir = "versión $1"
+            regex = true
+
+            [[reglas]]
+            buscar = "precio"
+            decir = "$1 euros"
+        "#;
+        // `$1` se expande en las expresiones regulares y no en las palabras
+        assert_eq!(
+            aplicar_toml(lexico, "Sale la v2.1 a este precio", "voz", None),
+            "Sale la versión 2.1 a este $1 euros"
+        );
+
+        let error = parsear_lexico(
+            r#"
+            [[reglas]]
+            buscar = "mal("
+            decir = "x"
+            regex = true
+        "#,
+        )
+        .unwrap_err();
+        assert!(error.contains("regla 1"), "{}", error);
+        assert!(parsear_lexico("[[reglas]]\nbuscar = \" \"\ndecir = \"x\"").is_err());
+        assert!(
+            parsear_lexico("[[reglas]]\nbuscar = \"a\"\ndecir = \"b\"\nidioma = \"xx\"").is_err()
+        );
+        assert!(parsear_lexico("[[reglas]]\nbuscar = \"a\"\ndecir = \"b\"\notra = 1").is_err());
+    }
+
+    #[test]
+    fn voz_idioma_y_prioridad() {
+        // En el archivo, la general va primero; se aplica la última
+        let lexico = r#"
+            [[reglas]]
+            buscar = "Nguyen"
+            decir = "general"
+
+            [[reglas]]
+            buscar = "Nguyen"
+            decir = "en español"
+            idioma = "es"
+
+            [[reglas]]
+            buscar = "Nguyen"
+            decir = "Nuyen"
+            voz = "es_ES-sharvard-medium"
+        "#;
+        assert_eq!(
+            aplicar_toml(lexico, "Nguyen", "es_ES-sharvard-medium", Some("es")),
+            "Nuyen"
+        );
+        assert_eq!(
+            aplicar_toml(lexico, "Nguyen", "otra", Some("es")),
+            "en español"
+        );
+        assert_eq!(
+            aplicar_toml(lexico, "Nguyen", "otra", Some("en")),
+            "general"
+        );
+        assert_eq!(aplicar_toml(lexico, "Nguyen", "otra", None), "general");
+
+        let reglas = parsear_lexico(lexico).unwrap();
+        let prioridades: Vec<u8> = reglas.iter().map(Regla::prioridad).collect();
+        assert_eq!(prioridades, [0, 1, 2]);
+    }
+
+    #[test]
+    fn plantilla() {
+        // Tal cual, todos los ejemplos están comentados
+        assert!(parsear_lexico(PLANTILLA).unwrap().is_empty());
+
+        // Y descomentados son reglas válidas
+        let ejemplos: String = PLANTILLA
+            .lines()
+            .skip_while(|l| !l.contains("Ejemplos"))
+            .filter_map(|l| l.strip_prefix("# "))
+            .filter(|l| l.starts_with("[[") || l.contains(" = "))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        let reglas = parsear_lexico(&ejemplos).unwrap();
+        assert_eq!(reglas.len(), 4);
+        assert_eq!(
+            aplicar_reglas(
+                &reglas,
+                "TrayNarrator v2.1 con GUI",
+                "es_ES-sharvard-medium",
+                Some("es")
+            ),
+            "Trei Narreitor versión 2.1 con GUI"
+        );
+    }
+}
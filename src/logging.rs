@@ -1,13 +1,27 @@
 //! Función de logging para debugging.
 
-use std::fs::OpenOptions;
+use std::fs::{self, File, OpenOptions};
 use std::io::Write;
+use std::path::Path;
 
-use crate::config::RUTA_LOG;
+use crate::config;
+
+/// Abre el log en modo append. Si falla, crea su carpeta y lo reintenta
+/// (en Linux el directorio de estado puede no existir todavía).
+fn abrir_log(ruta: &Path) -> Option<File> {
+    let abrir = || OpenOptions::new().create(true).append(true).open(ruta);
+    abrir()
+        .or_else(|e| match ruta.parent() {
+            Some(dir) => fs::create_dir_all(dir).and_then(|_| abrir()),
+            None => Err(e),
+        })
+        .ok()
+}
 
 /// Escribe un mensaje en el archivo de log para debugging
 pub fn log(mensaje: &str) {
-    if let Ok(mut archivo) = OpenOptions::new().create(true).append(true).open(RUTA_LOG) {
+    let ruta_log = config::actual().ruta_log.clone();
+    if let Some(mut archivo) = abrir_log(&ruta_log) {
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map(|d| d.as_secs())
@@ -6,6 +6,9 @@
 //! - Ctrl+[: Más rápido (siguiente preset xN)
 //! - Ctrl+]: Más lento (preset anterior xN)
 //!
+//! También puede leer un texto desde la línea de comandos y terminar:
+//! `tray_narrator speak "texto"` (ver `tray_narrator --help`).
+//!
 //! COMPILACIÓN CRUZADA (desde WSL2):
 //! cargo build --release --target x86_64-pc-windows-gnu
 //!
@@ -15,24 +18,114 @@
 #![windows_subsystem = "windows"]
 
 mod audio;
+mod cli;
 mod clipboard;
 mod config;
+mod doctor;
 mod hotkeys;
+mod language;
+mod lexicon;
 mod logging;
+mod narrator;
+mod paths;
+mod persistence;
+mod profiles;
 mod state;
+mod text;
 mod tray;
 mod tts;
+mod voices;
 
+use std::io::Read;
+use std::process::ExitCode;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
-use config::VELOCIDAD_INICIAL;
+use cli::{Accion, Argumentos, Comando, FuenteTexto};
 use logging::log;
-use state::{ComandoAudio, CANAL_AUDIO};
+use state::{
+    ComandoAudio, CANAL_AUDIO, DEBE_SALIR, ESTADO_AUDIO, ESTADO_IDLE, MODO_CODIGO,
+    NIVEL_PUNTUACION, REPRODUCCIONES_ATENDIDAS, VELOCIDAD_ACTUAL,
+};
+
+/// Intervalo de sondeo mientras se espera al hilo de audio en modo `speak`
+const INTERVALO_ESPERA: Duration = Duration::from_millis(50);
 
-fn main() {
+fn main() -> ExitCode {
+    let argumentos = match cli::parsear(std::env::args().skip(1)) {
+        Ok(Accion::Ejecutar(argumentos)) => argumentos,
+        Ok(Accion::MostrarAyuda) => {
+            enganchar_consola();
+            print!("{}", cli::AYUDA);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Accion::MostrarVersion) => {
+            enganchar_consola();
+            println!("{}", cli::texto_version());
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            enganchar_consola();
+            log(&format!("Error en la línea de comandos: {}", e));
+            eprintln!("Error: {}\n\n{}", e, cli::AYUDA);
+            return ExitCode::FAILURE;
+        }
+    };
+    // El modo tray no escribe en la consola: si se enganchara a la de quien
+    // lo lanzó, un Ctrl+C en ella lo cerraría
+    if argumentos.comando != Comando::Tray {
+        enganchar_consola();
+    }
+
+    // La configuración va primero: define, entre otras cosas, la ruta del log
+    config::establecer_sobrescrituras(argumentos.sobrescrituras());
+    let ruta_config = argumentos
+        .ruta_config
+        .clone()
+        .unwrap_or_else(config::ruta_archivo_config);
+    config::cargar_config(&ruta_config);
     log("=== TrayNarrator iniciado ===");
-    log(&format!("Velocidad inicial: {}%", VELOCIDAD_INICIAL));
+    lexicon::cargar();
+
+    // Solo el modo tray recuerda lo que el usuario elige; `speak` y `doctor`
+    // parten siempre de la configuración
+    let estado_guardado = match argumentos.comando {
+        Comando::Tray => persistence::cargar(),
+        _ => persistence::EstadoPersistido::default(),
+    };
+
+    let perfil = estado_guardado
+        .perfil
+        .as_deref()
+        .and_then(profiles::restaurar_perfil);
+    if let Some(voz) = estado_guardado.voz.clone() {
+        voices::restaurar_voz(voz);
+    }
+    voices::restaurar_hablantes(&estado_guardado.hablantes);
+
+    let velocidad_inicial = argumentos.velocidad_inicial(
+        estado_guardado.velocidad,
+        perfil.as_ref().and_then(|p| p.velocidad_x100()),
+    );
+    VELOCIDAD_ACTUAL.store(velocidad_inicial, Ordering::SeqCst);
+    log(&format!(
+        "Velocidad inicial: {}",
+        config::formatear_velocidad(velocidad_inicial)
+    ));
+
+    let puntuacion_inicial = estado_guardado
+        .puntuacion
+        .or(perfil.as_ref().and_then(|p| p.puntuacion))
+        .unwrap_or(config::actual().puntuacion);
+    NIVEL_PUNTUACION.store(puntuacion_inicial as u8, Ordering::SeqCst);
+
+    let modo_codigo_inicial = estado_guardado
+        .modo_codigo
+        .or(perfil.as_ref().and_then(|p| p.modo_codigo))
+        .unwrap_or(config::actual().modo_codigo);
+    MODO_CODIGO.store(modo_codigo_inicial as u8, Ordering::SeqCst);
 
     // Crear canal de audio
     let (sender, receiver) = mpsc::channel::<ComandoAudio>();
@@ -42,17 +135,124 @@ fn main() {
     }
 
     // Iniciar hilo de audio
-    thread::spawn(move || {
+    let hilo_audio = thread::spawn(move || {
         audio::hilo_audio(receiver);
     });
 
-    // Iniciar hilo de inputbot
-    thread::spawn(|| {
-        hotkeys::hilo_inputbot();
+    let codigo = match argumentos.comando {
+        Comando::Hablar(ref fuente) => match modo_hablar(fuente, &hilo_audio) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                log(&format!("Error speak: {}", e));
+                eprintln!("Error: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Comando::Diagnostico => {
+            if doctor::ejecutar(&ruta_config) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Comando::Tray => {
+            modo_tray(&argumentos, ruta_config);
+            ExitCode::SUCCESS
+        }
+    };
+
+    DEBE_SALIR.store(true, Ordering::SeqCst);
+    tts::cerrar();
+    log("=== TrayNarrator terminado ===");
+    codigo
+}
+
+/// Engancha el proceso a la consola desde la que se lanzó, para que la ayuda,
+/// los errores y los informes de `speak` y `doctor` se vean en ella: con
+/// `windows_subsystem = "windows"` el proceso arranca sin consola y lo que
+/// escribe se pierde. Si se lanzó desde el Explorador no hay consola a la que
+/// engancharse y no pasa nada; si la salida está redirigida, se respeta.
+#[cfg(windows)]
+fn enganchar_consola() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(id_proceso: u32) -> i32;
+    }
+
+    // SAFETY: AttachConsole no recibe punteros; si falla solo devuelve 0
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Fuera de Windows el proceso ya hereda la terminal.
+#[cfg(not(windows))]
+fn enganchar_consola() {}
+
+/// Modo por defecto: hotkeys + icono en la bandeja (cada uno desactivable).
+fn modo_tray(argumentos: &Argumentos, ruta_config: std::path::PathBuf) {
+    // Recargar la configuración cuando se edite el archivo
+    thread::spawn(move || {
+        config::hilo_vigilancia_config(ruta_config);
     });
+    // Y el léxico de pronunciación, que puede cambiar de ruta con ella
+    thread::spawn(lexicon::hilo_vigilancia_lexico);
 
-    // Ejecutar el system tray en el hilo principal (bloquea hasta salir)
-    tray::run_tray();
+    // Iniciar hilo de inputbot
+    let hilo_hotkeys = if argumentos.sin_hotkeys {
+        log("Hotkeys desactivados (--no-hotkeys)");
+        None
+    } else {
+        Some(thread::spawn(|| {
+            hotkeys::hilo_inputbot();
+        }))
+    };
 
-    log("=== TrayNarrator terminado ===");
+    if argumentos.sin_tray {
+        // Sin tray solo quedan los hotkeys: vivir mientras viva su hilo
+        log("Tray desactivado (--no-tray)");
+        if let Some(hilo) = hilo_hotkeys {
+            let _ = hilo.join();
+        }
+    } else {
+        // Ejecutar el system tray en el hilo principal (bloquea hasta salir)
+        tray::run_tray();
+    }
+}
+
+/// Modo `speak`: lee el texto indicado una vez y espera a que termine el audio.
+fn modo_hablar(fuente: &FuenteTexto, hilo_audio: &thread::JoinHandle<()>) -> Result<(), String> {
+    let texto = match fuente {
+        FuenteTexto::Texto(texto) => texto.clone(),
+        FuenteTexto::Archivo(ruta) => std::fs::read_to_string(ruta)
+            .map_err(|e| format!("No se pudo leer {}: {}", ruta.display(), e))?,
+        FuenteTexto::Stdin => {
+            let mut texto = String::new();
+            std::io::stdin()
+                .read_to_string(&mut texto)
+                .map_err(|e| format!("Error leyendo la entrada estándar: {}", e))?;
+            texto
+        }
+    };
+
+    // Los fragmentos empiezan a sonar mientras se sintetizan los siguientes
+    let atendidas = REPRODUCCIONES_ATENDIDAS.load(Ordering::SeqCst);
+    let enviados = narrator::narrar(&texto)? as u32;
+
+    // Esperar a que el hilo de audio atienda todos los fragmentos y terminen
+    // de sonar. Si el hilo muere (p. ej. sin dispositivo de audio) no hay nada
+    // que esperar.
+    while REPRODUCCIONES_ATENDIDAS.load(Ordering::SeqCst) < atendidas + enviados {
+        if hilo_audio.is_finished() {
+            return Err("El hilo de audio terminó sin reproducir".to_string());
+        }
+        thread::sleep(INTERVALO_ESPERA);
+    }
+    while ESTADO_AUDIO.load(Ordering::SeqCst) != ESTADO_IDLE && !hilo_audio.is_finished() {
+        thread::sleep(INTERVALO_ESPERA);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,293 @@
+//! Narración por fragmentos.
+//!
+//! Primero se quita el marcado (Markdown, HTML, wikitexto) del texto copiado.
+//! Después se divide en frases y se sintetiza una a una: la primera se envía
+//! al hilo de audio en cuanto está lista y el resto se encola a continuación
+//! mientras suena, así el tiempo hasta el primer audio no depende de la
+//! longitud del texto. Con un motor en streaming (Piper con
+//! `salida_piper = "raw"`) las frases seguidas con la misma voz de un párrafo
+//! se pasan juntas a un solo proceso, que suena según las va generando.
+//!
+//! El idioma se detecta sobre el texto completo y, con voces por idioma, cada
+//! frase se divide además en tramos de un mismo idioma que se leen con su
+//! voz. Los tramos se encolan uno tras otro en el mismo `Sink`, así que suenan
+//! seguidos. Entre párrafos se encola un silencio (`silencio_parrafo_ms`).
+//!
+//! El código fuente (según `modo_codigo`) no se limpia ni se divide en
+//! tramos: cada línea se lee como una frase con la voz actual, con los
+//! identificadores partidos en palabras y los operadores por su nombre.
+//!
+//! El deletreo sigue el mismo camino con fragmentos de unos pocos caracteres
+//! ya escritos como deben sonar.
+
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::config::{self, ModoCodigo};
+use crate::language::{self, Tramo};
+use crate::logging::log;
+use crate::profiles;
+use crate::state::{enviar_comando, modo_codigo, ComandoAudio, NARRACION_ACTUAL};
+use crate::text;
+use crate::tts::{self, Audio, AudioSintetizado, Preparacion};
+
+/// Silencio que se conserva al final de un tramo que no cierra su frase: lo
+/// justo para separar palabras, sin la pausa de fin de frase de Piper.
+const MARGEN_ENTRE_TRAMOS: Duration = Duration::from_millis(40);
+
+/// Texto que se sintetiza de una vez, con su idioma.
+struct Pieza {
+    tramo: Tramo,
+    /// `false` si la frase continúa en otro idioma en la pieza siguiente
+    fin_de_frase: bool,
+    /// Última pieza de su párrafo: tras ella va el silencio entre párrafos
+    fin_de_parrafo: bool,
+}
+
+/// `true` si la narración ya no es la actual (se canceló o empezó otra).
+fn cancelada(narracion: u64) -> bool {
+    NARRACION_ACTUAL.load(Ordering::SeqCst) != narracion
+}
+
+/// Detiene la narración en curso: descarta los fragmentos pendientes y corta
+/// el audio que esté sonando.
+pub fn cancelar() {
+    NARRACION_ACTUAL.fetch_add(1, Ordering::SeqCst);
+    enviar_comando(ComandoAudio::Detener);
+}
+
+/// Narra un texto, sustituyendo cualquier narración anterior.
+///
+/// Bloquea hasta haber sintetizado y enviado todos los fragmentos (no hasta
+/// que terminen de sonar). Devuelve el número de fragmentos enviados al hilo
+/// de audio, silencios entre párrafos incluidos. Solo falla si no se pudo
+/// sintetizar ningún fragmento; un error en un fragmento posterior se
+/// registra y se salta.
+pub fn narrar(texto: &str) -> Result<usize, String> {
+    let narracion = NARRACION_ACTUAL.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let codigo = match modo_codigo() {
+        ModoCodigo::Auto => text::parece_codigo(texto),
+        ModoCodigo::Siempre => true,
+        ModoCodigo::Nunca => false,
+    };
+    if codigo {
+        return narrar_codigo(narracion, texto);
+    }
+
+    let limpio;
+    let texto = if config::actual().limpiar_marcado {
+        limpio = text::limpiar_marcado(texto);
+        limpio.as_str()
+    } else {
+        texto
+    };
+
+    let parrafos = text::dividir_parrafos(texto);
+    if parrafos.is_empty() {
+        return Err("Texto vacío después de limpiar".to_string());
+    }
+
+    let voz_defecto = profiles::voz_actual();
+    let idioma = language::idioma_del_texto(texto, &voz_defecto);
+    let mut fragmentos = Vec::new();
+    for parrafo in &parrafos {
+        for (n, frase) in parrafo.iter().enumerate() {
+            let ultima_frase = n + 1 == parrafo.len();
+            let tramos = language::tramos(frase, idioma.as_deref(), &voz_defecto);
+            let ultimo = tramos.len().saturating_sub(1);
+            fragmentos.extend(tramos.into_iter().enumerate().map(|(i, tramo)| Pieza {
+                tramo,
+                fin_de_frase: i == ultimo,
+                fin_de_parrafo: ultima_frase && i == ultimo,
+            }));
+        }
+    }
+    log(&format!(
+        "Narración {}: {} párrafos, {} frases, {} fragmentos",
+        narracion,
+        parrafos.len(),
+        parrafos.iter().map(Vec::len).sum::<usize>(),
+        fragmentos.len()
+    ));
+
+    enviar_piezas(narracion, &fragmentos, Preparacion::Completa)
+}
+
+/// Lee un texto como código fuente: una frase por línea y un párrafo por
+/// bloque separado por líneas en blanco, todo con la voz actual.
+fn narrar_codigo(narracion: u64, texto: &str) -> Result<usize, String> {
+    let parrafos = text::leer_codigo(texto, idioma_de_nombres());
+    if parrafos.is_empty() {
+        return Err("Texto vacío después de limpiar".to_string());
+    }
+
+    let mut fragmentos = Vec::new();
+    for parrafo in parrafos.iter() {
+        for (n, linea) in parrafo.iter().enumerate() {
+            fragmentos.push(Pieza {
+                tramo: Tramo {
+                    texto: linea.clone(),
+                    idioma: None,
+                },
+                fin_de_frase: true,
+                fin_de_parrafo: n + 1 == parrafo.len(),
+            });
+        }
+    }
+    log(&format!(
+        "Narración {} (código): {} bloques, {} líneas",
+        narracion,
+        parrafos.len(),
+        fragmentos.len()
+    ));
+
+    enviar_piezas(narracion, &fragmentos, Preparacion::Codigo)
+}
+
+/// Idioma de la voz actual para los textos que añade el narrador (nombres
+/// de letras, operadores…). Sin nombres para ese idioma se usan los
+/// ingleses, los más reconocibles para cualquier voz.
+fn idioma_de_nombres() -> text::Idioma {
+    language::idioma_de_voz(&profiles::voz_actual())
+        .as_deref()
+        .and_then(text::Idioma::desde_codigo)
+        .unwrap_or(text::Idioma::En)
+}
+
+/// Deletrea un texto carácter a carácter con los nombres del idioma de la
+/// voz actual, sustituyendo cualquier narración anterior. Como `narrar`,
+/// devuelve el número de fragmentos enviados.
+pub fn deletrear(texto: &str) -> Result<usize, String> {
+    let narracion = NARRACION_ACTUAL.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let texto = text::depurar_unicode(texto);
+    let grupos = text::deletrear(
+        &texto,
+        idioma_de_nombres(),
+        config::actual().alfabeto_deletreo,
+    );
+    if grupos.is_empty() {
+        return Err("Texto vacío después de limpiar".to_string());
+    }
+    log(&format!(
+        "Deletreo {}: {} caracteres en {} fragmentos",
+        narracion,
+        texto.trim().chars().count(),
+        grupos.len()
+    ));
+
+    let fragmentos: Vec<Pieza> = grupos
+        .into_iter()
+        .map(|texto| Pieza {
+            tramo: Tramo {
+                texto,
+                idioma: None,
+            },
+            fin_de_frase: true,
+            fin_de_parrafo: false,
+        })
+        .collect();
+    enviar_piezas(narracion, &fragmentos, Preparacion::Literal)
+}
+
+/// Piezas que se sintetizan juntas. Un motor en streaming recibe de una vez
+/// todas las piezas seguidas con el mismo idioma hasta el fin de párrafo, así
+/// que carga el modelo una vez para todas y genera la siguiente mientras
+/// suena la anterior; los demás, una a una.
+fn agrupar(piezas: &[Pieza], en_flujo: bool) -> Vec<&[Pieza]> {
+    let mut grupos = Vec::new();
+    let mut inicio = 0;
+    for (i, pieza) in piezas.iter().enumerate() {
+        let sigue = en_flujo
+            && !pieza.fin_de_parrafo
+            && piezas
+                .get(i + 1)
+                .is_some_and(|siguiente| siguiente.tramo.idioma == pieza.tramo.idioma);
+        if !sigue {
+            grupos.push(&piezas[inicio..=i]);
+            inicio = i + 1;
+        }
+    }
+    grupos
+}
+
+/// Sintetiza las piezas en orden y las envía al hilo de audio mientras la
+/// narración siga siendo la actual.
+fn enviar_piezas(
+    narracion: u64,
+    fragmentos: &[Pieza],
+    preparacion: Preparacion,
+) -> Result<usize, String> {
+    let silencio_parrafo = Duration::from_millis(config::actual().silencio_parrafo_ms);
+    let grupos = agrupar(fragmentos, tts::motor_en_flujo());
+
+    let mut enviados = 0;
+    for (i, grupo) in grupos.iter().enumerate() {
+        if cancelada(narracion) {
+            break;
+        }
+
+        // El grupo acaba donde acaba su última pieza
+        let Some(fragmento) = grupo.last() else {
+            continue;
+        };
+        let frases: Vec<&str> = grupo.iter().map(|p| p.tramo.texto.as_str()).collect();
+        let idioma = fragmento.tramo.idioma.as_deref();
+        let mut audio = match tts::sintetizar_en_flujo(&frases, idioma, preparacion) {
+            Ok(audio) => audio,
+            Err(e) if enviados == 0 => return Err(e),
+            Err(e) => {
+                log(&format!("Fragmento omitido: {}", e));
+                continue;
+            }
+        };
+
+        // La síntesis puede tardar: no enviar nada si ya se canceló
+        if cancelada(narracion) {
+            break;
+        }
+
+        // A mitad de frase el cambio de voz no debe sonar como una pausa
+        if !fragmento.fin_de_frase {
+            if let AudioSintetizado::Completo(completo) = &mut audio {
+                completo.recortar_silencio_final(MARGEN_ENTRE_TRAMOS);
+            }
+        }
+
+        let fin_generacion = audio.fin_generacion();
+        let comando = if enviados == 0 {
+            ComandoAudio::Reproducir { narracion, audio }
+        } else {
+            ComandoAudio::Encolar { narracion, audio }
+        };
+        enviar_comando(comando);
+        enviados += 1;
+
+        // El silencio se encola ya: suena mientras se sintetiza lo siguiente
+        if fragmento.fin_de_parrafo && i + 1 < grupos.len() && !silencio_parrafo.is_zero() {
+            enviar_comando(ComandoAudio::Encolar {
+                narracion,
+                audio: AudioSintetizado::Completo(Audio::silencio(silencio_parrafo)),
+            });
+            enviados += 1;
+        }
+
+        // En streaming el grupo sigue generándose mientras suena: el
+        // siguiente se lanza en cuanto el motor termina con este, mientras
+        // aún suena, sin dos síntesis a la vez
+        if let Some(fin) = fin_generacion {
+            fin.esperar();
+        }
+    }
+
+    if cancelada(narracion) {
+        log(&format!(
+            "Narración {} cancelada tras {} de {} fragmentos",
+            narracion,
+            enviados,
+            fragmentos.len()
+        ));
+    }
+    Ok(enviados)
+}
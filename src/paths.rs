@@ -0,0 +1,218 @@
+//! Rutas por defecto según la plataforma.
+//!
+//! - **Windows**: instalación portable. Piper y el log viven junto al
+//!   ejecutable (p. ej. `C:\TrayNarrator\`); la configuración se busca ahí
+//!   y, si no existe, en `%APPDATA%\TrayNarrator\`.
+//! - **Linux y otros Unix**: directorios XDG (`$XDG_CONFIG_HOME`,
+//!   `$XDG_DATA_HOME`, `$XDG_STATE_HOME`) con sus valores por defecto bajo
+//!   `$HOME` cuando las variables no están definidas.
+//!
+//! En todas las plataformas el WAV temporal va al directorio temporal del
+//! sistema.
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Nombre de la carpeta de la aplicación dentro de los directorios del sistema
+#[cfg(windows)]
+const NOMBRE_DIR_APP: &str = "TrayNarrator";
+#[cfg(not(windows))]
+const NOMBRE_DIR_APP: &str = "tray_narrator";
+
+/// Nombre del ejecutable de Piper
+#[cfg(windows)]
+const NOMBRE_PIPER: &str = "piper.exe";
+#[cfg(not(windows))]
+const NOMBRE_PIPER: &str = "piper";
+
+/// Modelo de voz incluido en las releases
+const NOMBRE_MODELO_DEFECTO: &str = "es_ES-sharvard-medium.onnx";
+
+/// Directorio que contiene el ejecutable de TrayNarrator.
+pub fn dir_ejecutable() -> PathBuf {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_default()
+}
+
+/// Lee una variable de entorno con una ruta absoluta.
+fn ruta_de_entorno(variable: &str) -> Option<PathBuf> {
+    ruta_absoluta(env::var_os(variable))
+}
+
+/// El valor de una variable como ruta, si es absoluta.
+///
+/// La especificación XDG pide ignorar las rutas relativas.
+fn ruta_absoluta(valor: Option<OsString>) -> Option<PathBuf> {
+    valor.map(PathBuf::from).filter(|ruta| ruta.is_absolute())
+}
+
+/// Resuelve un directorio XDG: `$variable` o `$HOME/<relativa>`.
+///
+/// Sin `$HOME` se recurre al directorio del ejecutable.
+#[cfg(not(windows))]
+fn dir_xdg(variable: &str, relativa_a_home: &str) -> PathBuf {
+    resolver_xdg(variable, relativa_a_home, ruta_de_entorno)
+}
+
+/// `dir_xdg` leyendo las variables con `entorno`.
+#[cfg(not(windows))]
+fn resolver_xdg(
+    variable: &str,
+    relativa_a_home: &str,
+    entorno: impl Fn(&str) -> Option<PathBuf>,
+) -> PathBuf {
+    entorno(variable)
+        .or_else(|| entorno("HOME").map(|home| home.join(relativa_a_home)))
+        .map(|base| base.join(NOMBRE_DIR_APP))
+        .unwrap_or_else(dir_ejecutable)
+}
+
+/// Directorio de configuración del usuario.
+#[cfg(windows)]
+pub fn dir_config() -> PathBuf {
+    ruta_de_entorno("APPDATA")
+        .map(|base| base.join(NOMBRE_DIR_APP))
+        .unwrap_or_else(dir_ejecutable)
+}
+
+/// Directorio de configuración del usuario.
+#[cfg(not(windows))]
+pub fn dir_config() -> PathBuf {
+    dir_xdg("XDG_CONFIG_HOME", ".config")
+}
+
+/// Directorio de datos: contiene la carpeta `piper/` con el motor y las voces.
+#[cfg(windows)]
+pub fn dir_datos() -> PathBuf {
+    dir_ejecutable()
+}
+
+/// Directorio de datos: contiene la carpeta `piper/` con el motor y las voces.
+#[cfg(not(windows))]
+pub fn dir_datos() -> PathBuf {
+    dir_xdg("XDG_DATA_HOME", ".local/share")
+}
+
+/// Directorio de estado: log y datos que deben sobrevivir entre ejecuciones.
+#[cfg(windows)]
+pub fn dir_estado() -> PathBuf {
+    dir_ejecutable()
+}
+
+/// Directorio de estado: log y datos que deben sobrevivir entre ejecuciones.
+#[cfg(not(windows))]
+pub fn dir_estado() -> PathBuf {
+    dir_xdg("XDG_STATE_HOME", ".local/state")
+}
+
+/// Ruta por defecto del archivo de configuración.
+///
+/// En Windows se prefiere el archivo junto al ejecutable si existe, para
+/// que la instalación portable siga funcionando igual que antes.
+pub fn ruta_config_defecto(nombre_archivo: &str) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let portable = dir_ejecutable().join(nombre_archivo);
+        if portable.exists() {
+            return portable;
+        }
+    }
+    dir_config().join(nombre_archivo)
+}
+
+/// Ruta por defecto del ejecutable de Piper.
+pub fn ruta_piper_defecto() -> PathBuf {
+    dir_datos().join("piper").join(NOMBRE_PIPER)
+}
+
+/// Ruta por defecto del modelo de voz.
+pub fn ruta_modelo_defecto() -> PathBuf {
+    dir_datos().join("piper").join(NOMBRE_MODELO_DEFECTO)
+}
+
+/// Carpeta por defecto donde se buscan voces de Piper (la de Piper).
+pub fn dir_voces_defecto() -> PathBuf {
+    dir_datos().join("piper")
+}
+
+/// Ruta por defecto del WAV temporal, en el directorio temporal del sistema.
+///
+/// En Unix `/tmp` es compartido, así que se añade el usuario al nombre para
+/// que dos sesiones no compitan por el mismo archivo.
+pub fn ruta_temp_wav_defecto() -> PathBuf {
+    env::temp_dir().join(nombre_temp_wav(env::var("USER").ok().as_deref()))
+}
+
+/// Nombre del WAV temporal para `usuario` (`$USER`).
+fn nombre_temp_wav(usuario: Option<&str>) -> String {
+    match usuario {
+        Some(usuario) if cfg!(not(windows)) && !usuario.is_empty() => {
+            format!("tray_narrator-{}.wav", usuario)
+        }
+        _ => "tray_narrator.wav".to_string(),
+    }
+}
+
+/// Ruta por defecto del archivo de log.
+pub fn ruta_log_defecto() -> PathBuf {
+    dir_estado().join("log.txt")
+}
+
+// Las rutas de Windows salen del ejecutable y de %APPDATA%: solo se prueban
+// las de Unix
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directorios_xdg() {
+        // Entorno simulado: no se tocan las variables del proceso
+        let entorno = |variables: &'static [(&'static str, &'static str)]| {
+            move |variable: &str| {
+                ruta_absoluta(
+                    variables
+                        .iter()
+                        .find(|(nombre, _)| *nombre == variable)
+                        .map(|(_, valor)| OsString::from(valor)),
+                )
+            }
+        };
+
+        let completo = entorno(&[("HOME", "/home/ana"), ("XDG_CONFIG_HOME", "/cfg")]);
+        assert_eq!(
+            resolver_xdg("XDG_CONFIG_HOME", ".config", &completo),
+            PathBuf::from("/cfg/tray_narrator")
+        );
+        // Sin la variable, bajo $HOME
+        assert_eq!(
+            resolver_xdg("XDG_STATE_HOME", ".local/state", &completo),
+            PathBuf::from("/home/ana/.local/state/tray_narrator")
+        );
+        // Las rutas relativas se ignoran
+        let relativa = entorno(&[("HOME", "/home/ana"), ("XDG_DATA_HOME", "datos")]);
+        assert_eq!(
+            resolver_xdg("XDG_DATA_HOME", ".local/share", &relativa),
+            PathBuf::from("/home/ana/.local/share/tray_narrator")
+        );
+        // Sin $HOME, junto al ejecutable
+        let vacio = entorno(&[("HOME", "")]);
+        assert_eq!(
+            resolver_xdg("XDG_CONFIG_HOME", ".config", &vacio),
+            dir_ejecutable()
+        );
+    }
+
+    #[test]
+    fn wav_temporal_por_usuario() {
+        assert_eq!(nombre_temp_wav(Some("ana")), "tray_narrator-ana.wav");
+        assert_eq!(nombre_temp_wav(Some("")), "tray_narrator.wav");
+        assert_eq!(nombre_temp_wav(None), "tray_narrator.wav");
+        assert_eq!(
+            ruta_temp_wav_defecto().parent(),
+            Some(env::temp_dir().as_path())
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Persistencia del estado elegido por el usuario entre ejecuciones.
+//!
+//! Se guarda en `estado.toml`, en el directorio de estado de la plataforma
+//! (ver `paths`). El archivo se reescribe entero en cada cambio: es pequeño
+//! y los cambios son poco frecuentes (una pulsación de hotkey o del tray).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ModoCodigo, NivelPuntuacion, VELOCIDAD_MAXIMA_X100, VELOCIDAD_MINIMA_X100};
+use crate::logging::log;
+use crate::paths;
+
+/// Nombre del archivo de estado
+const NOMBRE_ARCHIVO_ESTADO: &str = "estado.toml";
+
+/// Estado que sobrevive a un reinicio.
+///
+/// Todos los campos son opcionales: un archivo antiguo o parcial se lee sin
+/// error y los campos ausentes se quedan con el valor de la configuración.
+/// El volumen no está: no se cambia en ejecución, solo en la configuración
+/// o en el perfil, que ya se recuerda.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EstadoPersistido {
+    /// Última velocidad elegida (multiplicador × 100)
+    pub velocidad: Option<u32>,
+    /// Nombre del perfil activo
+    pub perfil: Option<String>,
+    /// Modelo de voz elegido en el tray
+    pub voz: Option<PathBuf>,
+    /// Hablante elegido para cada modelo multi-hablante (ruta → nombre)
+    pub hablantes: BTreeMap<String, String>,
+    /// Último nivel de puntuación elegido
+    pub puntuacion: Option<NivelPuntuacion>,
+    /// Último modo de lectura de código elegido
+    pub modo_codigo: Option<ModoCodigo>,
+}
+
+lazy_static! {
+    /// Copia en memoria de lo guardado en disco.
+    static ref ESTADO: Mutex<EstadoPersistido> = Mutex::new(EstadoPersistido::default());
+}
+
+/// Solo se guarda cuando está activa: el modo `speak` no debe pisar lo que
+/// el usuario eligió en el tray.
+static PERSISTENCIA_ACTIVA: AtomicBool = AtomicBool::new(false);
+
+/// Ruta del archivo de estado.
+fn ruta_archivo_estado() -> PathBuf {
+    paths::dir_estado().join(NOMBRE_ARCHIVO_ESTADO)
+}
+
+/// Lee el estado guardado y activa el guardado automático.
+///
+/// Un archivo ausente o corrupto no es un error: se registra en el log y se
+/// empieza con un estado vacío.
+pub fn cargar() -> EstadoPersistido {
+    let estado = leer(&ruta_archivo_estado());
+    *ESTADO.lock() = estado.clone();
+    PERSISTENCIA_ACTIVA.store(true, Ordering::SeqCst);
+    estado
+}
+
+/// Lee y valida el estado guardado en `ruta`.
+fn leer(ruta: &Path) -> EstadoPersistido {
+    let mut estado = match fs::read_to_string(ruta) {
+        Ok(contenido) => toml::from_str(&contenido).unwrap_or_else(|e| {
+            log(&format!(
+                "Estado guardado ilegible en {}, se ignora: {}",
+                ruta.display(),
+                e
+            ));
+            EstadoPersistido::default()
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => EstadoPersistido::default(),
+        Err(e) => {
+            log(&format!("Error leyendo {}: {}", ruta.display(), e));
+            EstadoPersistido::default()
+        }
+    };
+
+    // Un valor fuera de rango indica un archivo editado a mano o corrupto
+    if let Some(velocidad) = estado.velocidad {
+        if !(VELOCIDAD_MINIMA_X100..=VELOCIDAD_MAXIMA_X100).contains(&velocidad) {
+            log(&format!("Velocidad guardada fuera de rango: {}", velocidad));
+            estado.velocidad = None;
+        }
+    }
+    estado
+}
+
+/// Escribe el estado en disco.
+fn guardar(estado: &EstadoPersistido) {
+    let ruta = ruta_archivo_estado();
+    if let Err(e) = escribir(&ruta, estado) {
+        log(&format!(
+            "Error guardando estado en {}: {}",
+            ruta.display(),
+            e
+        ));
+    }
+}
+
+/// Escribe el estado en `ruta` de forma atómica (archivo temporal + rename),
+/// para que un corte a mitad de escritura no deje un archivo a medias.
+fn escribir(ruta: &Path, estado: &EstadoPersistido) -> Result<(), String> {
+    toml::to_string(estado)
+        .map_err(|e| e.to_string())
+        .and_then(|contenido| {
+            if let Some(dir) = ruta.parent() {
+                fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            let temporal = ruta.with_extension("toml.tmp");
+            fs::write(&temporal, contenido).map_err(|e| e.to_string())?;
+            fs::rename(&temporal, ruta).map_err(|e| e.to_string())
+        })
+}
+
+/// Aplica un cambio al estado y lo guarda si ha cambiado algo.
+fn actualizar(cambio: impl FnOnce(&mut EstadoPersistido)) {
+    if !PERSISTENCIA_ACTIVA.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let mut estado = ESTADO.lock();
+    let anterior = estado.clone();
+    cambio(&mut estado);
+    if *estado != anterior {
+        guardar(&estado);
+    }
+}
+
+/// Recuerda la velocidad elegida.
+pub fn guardar_velocidad(multiplicador_x100: u32) {
+    actualizar(|estado| estado.velocidad = Some(multiplicador_x100));
+}
+
+/// Recuerda el perfil activo (`None` = sin perfil).
+pub fn guardar_perfil(nombre: Option<&str>) {
+    actualizar(|estado| estado.perfil = nombre.map(str::to_string));
+}
+
+/// Recuerda la voz elegida en el tray (`None` = la de la configuración).
+pub fn guardar_voz(ruta: Option<&Path>) {
+    actualizar(|estado| estado.voz = ruta.map(Path::to_path_buf));
+}
+
+/// Recuerda el nivel de puntuación elegido.
+pub fn guardar_puntuacion(nivel: NivelPuntuacion) {
+    actualizar(|estado| estado.puntuacion = Some(nivel));
+}
+
+/// Recuerda el modo de lectura de código elegido.
+pub fn guardar_modo_codigo(modo: ModoCodigo) {
+    actualizar(|estado| estado.modo_codigo = Some(modo));
+}
+
+/// Recuerda el hablante elegido para un modelo (`None` = el por defecto).
+pub fn guardar_hablante(modelo: &Path, nombre: Option<&str>) {
+    let clave = modelo.to_string_lossy().into_owned();
+    actualizar(|estado| match nombre {
+        Some(nombre) => {
+            estado.hablantes.insert(clave, nombre.to_string());
+        }
+        None => {
+            estado.hablantes.remove(&clave);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Directorio temporal propio de cada prueba.
+    fn dir_prueba(nombre: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "tray_narrator_prueba_{}_{}",
+            std::process::id(),
+            nombre
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn ida_y_vuelta() {
+        let ruta = dir_prueba("estado_ida")
+            .join("sub")
+            .join(NOMBRE_ARCHIVO_ESTADO);
+        let estado = EstadoPersistido {
+            velocidad: Some(150),
+            perfil: Some("Noche".to_string()),
+            voz: Some(PathBuf::from("voces/es_ES-x-medium.onnx")),
+            hablantes: BTreeMap::from([("libritts.onnx".to_string(), "p239".to_string())]),
+            puntuacion: Some(NivelPuntuacion::Todos),
+            modo_codigo: Some(ModoCodigo::Siempre),
+        };
+        // Crea los directorios que falten
+        escribir(&ruta, &estado).unwrap();
+        assert_eq!(leer(&ruta), estado);
+        assert!(!ruta.with_extension("toml.tmp").exists());
+
+        escribir(&ruta, &EstadoPersistido::default()).unwrap();
+        assert_eq!(leer(&ruta), EstadoPersistido::default());
+    }
+
+    #[test]
+    fn archivo_ausente_o_ilegible() {
+        let dir = dir_prueba("estado_roto");
+        let ruta = dir.join(NOMBRE_ARCHIVO_ESTADO);
+        assert_eq!(leer(&ruta), EstadoPersistido::default());
+
+        fs::create_dir_all(&dir).unwrap();
+        for basura in [
+            "velocidad = [",
+            "\u{0}\u{1}binario",
+            "velocidad = \"rápida\"",
+        ] {
+            fs::write(&ruta, basura).unwrap();
+            assert_eq!(leer(&ruta), EstadoPersistido::default(), "{:?}", basura);
+        }
+
+        // Parcial o de otra versión: se conserva lo que se entiende
+        fs::write(
+            &ruta,
+            "perfil = \"Noche\"\nvelocidad = 5000\nvolumen = 0.5\n",
+        )
+        .unwrap();
+        let estado = leer(&ruta);
+        assert_eq!(estado.perfil.as_deref(), Some("Noche"));
+        assert_eq!(estado.velocidad, None);
+    }
+}
@@ -0,0 +1,337 @@
+//! Perfiles con nombre (`[[perfiles]]` en la configuración).
+//!
+//! El perfil activo se recuerda por nombre. Su voz, hablante, prosodia y
+//! volumen se consultan en la configuración actual en cada lectura, así que
+//! editar el perfil en `tray_narrator.toml` tiene efecto sin volver a
+//! activarlo. La velocidad, el nivel de puntuación y el modo de lectura de
+//! código en cambio se aplican solo al activarlo: después el usuario los
+//! sigue ajustando con los hotkeys o el menú.
+
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+
+use crate::config::{self, Config, Motor, Perfil, Prosodia};
+use crate::language;
+use crate::logging::log;
+use crate::persistence;
+use crate::state::{establecer_modo_codigo, establecer_nivel_puntuacion, establecer_velocidad};
+use crate::voices;
+
+lazy_static! {
+    /// Nombre del perfil activo (`None` = solo la configuración general).
+    static ref PERFIL_ACTIVO: RwLock<Option<String>> = RwLock::new(None);
+}
+
+/// Perfil activo dentro de una configuración concreta. Si una recarga lo ha
+/// eliminado, se comporta como si no hubiera perfil.
+fn perfil_en(config: &Config) -> Option<&Perfil> {
+    let activo = PERFIL_ACTIVO.read();
+    let nombre = activo.as_deref()?;
+    config.perfiles.iter().find(|p| p.nombre == nombre)
+}
+
+/// Nombre del perfil activo, si existe en la configuración actual.
+pub fn perfil_activo() -> Option<String> {
+    perfil_en(&config::actual()).map(|p| p.nombre.clone())
+}
+
+/// Activa un perfil por nombre y aplica su velocidad, nivel de puntuación y
+/// modo de lectura de código. Si el perfil tiene voz o hablante propios,
+/// sustituyen a los elegidos en el tray.
+///
+/// Devuelve `false` si no hay ningún perfil con ese nombre.
+pub fn activar_perfil(nombre: &str) -> bool {
+    let config = config::actual();
+    let Some(perfil) = config.perfiles.iter().find(|p| p.nombre == nombre) else {
+        log(&format!("Perfil desconocido: {}", nombre));
+        return false;
+    };
+
+    *PERFIL_ACTIVO.write() = Some(perfil.nombre.clone());
+    persistence::guardar_perfil(Some(&perfil.nombre));
+    if let Some(velocidad) = perfil.velocidad_x100() {
+        establecer_velocidad(velocidad);
+    }
+    if let Some(nivel) = perfil.puntuacion {
+        establecer_nivel_puntuacion(nivel);
+    }
+    if let Some(modo) = perfil.modo_codigo {
+        establecer_modo_codigo(modo);
+    }
+    if perfil.voz.is_some() {
+        voices::olvidar_voz();
+    }
+    if perfil.hablante.is_some() {
+        voices::olvidar_hablante(&voz_actual());
+    }
+    log(&format!("Perfil activo: {}", perfil.nombre));
+    true
+}
+
+/// Vuelve a la configuración general sin perfil. La velocidad no cambia.
+pub fn desactivar_perfil() {
+    *PERFIL_ACTIVO.write() = None;
+    persistence::guardar_perfil(None);
+    log("Perfil desactivado");
+}
+
+/// Restaura el perfil guardado al arrancar, sin aplicar su velocidad, nivel
+/// de puntuación ni modo de código (los guardados ya reflejan el último
+/// ajuste del usuario).
+///
+/// Devuelve el perfil, para usar sus valores si no hay otros guardados.
+pub fn restaurar_perfil(nombre: &str) -> Option<Perfil> {
+    let config = config::actual();
+    match config.perfiles.iter().find(|p| p.nombre == nombre) {
+        Some(perfil) => {
+            *PERFIL_ACTIVO.write() = Some(perfil.nombre.clone());
+            log(&format!("Perfil restaurado: {}", perfil.nombre));
+            Some(perfil.clone())
+        }
+        None => {
+            log(&format!("El perfil guardado '{}' ya no existe", nombre));
+            None
+        }
+    }
+}
+
+/// Activa el perfil siguiente al actual, volviendo al primero tras el último.
+pub fn ciclar_perfil() {
+    let config = config::actual();
+    if config.perfiles.is_empty() {
+        log("No hay perfiles definidos");
+        return;
+    }
+
+    let siguiente = match perfil_en(&config) {
+        Some(actual) => {
+            let idx = config
+                .perfiles
+                .iter()
+                .position(|p| p.nombre == actual.nombre)
+                .unwrap_or(0);
+            (idx + 1) % config.perfiles.len()
+        }
+        None => 0,
+    };
+    activar_perfil(&config.perfiles[siguiente].nombre);
+}
+
+/// Voz para la próxima lectura: `--voice`, la elegida en el tray (solo con
+/// Piper), la del perfil activo o la del motor configurado (`ruta_modelo` /
+/// `voz_espeak`), en ese orden.
+pub fn voz_actual() -> PathBuf {
+    let config = config::actual();
+    if !config::voz_forzada() {
+        if config.motor == Motor::Piper {
+            if let Some(voz) = voices::voz_elegida() {
+                return voz;
+            }
+        }
+        if let Some(voz) = perfil_en(&config).and_then(|p| p.voz.clone()) {
+            return voz;
+        }
+    }
+    config.voz_motor()
+}
+
+/// Voz para leer un texto en `idioma`: la asignada a ese idioma en
+/// `voces_idioma`, salvo que la voz por defecto (`voz_actual`) ya lo hable o
+/// que se haya forzado con `--voice`. Sin idioma, la voz por defecto.
+pub fn voz_para_idioma(idioma: Option<&str>) -> PathBuf {
+    let voz = voz_actual();
+    let Some(idioma) = idioma else {
+        return voz;
+    };
+    if config::voz_forzada() || language::idioma_de_voz(&voz).as_deref() == Some(idioma) {
+        return voz;
+    }
+
+    let config = config::actual();
+    config
+        .voces_idioma
+        .iter()
+        .find(|(codigo, _)| language::normalizar(codigo) == idioma)
+        .map_or(voz, |(_, voz_idioma)| voz_idioma.clone())
+}
+
+/// ID de hablante para la próxima lectura con el modelo `voz` (solo Piper):
+/// el elegido en el tray para ese modelo, el del perfil activo o `hablante`
+/// de la configuración. `None` = el hablante por defecto del modelo.
+///
+/// Falla si el nombre elegido no existe en el modelo.
+pub fn hablante_actual(voz: &Path) -> Result<Option<u32>, String> {
+    let config = config::actual();
+    if config.motor != Motor::Piper {
+        return Ok(None);
+    }
+    let nombre = voices::hablante_elegido(voz)
+        .or_else(|| perfil_en(&config).and_then(|p| p.hablante.clone()))
+        .or_else(|| config.hablante.clone());
+    match nombre {
+        Some(nombre) => voices::id_hablante(voz, &nombre),
+        None => Ok(None),
+    }
+}
+
+/// Prosodia de Piper para el modelo `voz`: cada parámetro se toma del perfil
+/// activo, de `[prosodia_voces]` para ese modelo, de `[prosodia]` o de la
+/// sección `inference` del modelo, en ese orden. Los que sigan sin valor
+/// quedan con el valor por defecto de Piper.
+pub fn prosodia_actual(voz: &Path) -> Prosodia {
+    let config = config::actual();
+    if config.motor != Motor::Piper {
+        return Prosodia::default();
+    }
+    let del_modelo = voices::info_voz(voz)
+        .map(|info| info.prosodia)
+        .unwrap_or_default();
+    let de_la_voz = voz
+        .file_stem()
+        .and_then(|n| config.prosodia_voces.get(n.to_string_lossy().as_ref()))
+        .copied()
+        .unwrap_or_default();
+    let del_perfil = perfil_en(&config).map(|p| p.prosodia).unwrap_or_default();
+    del_perfil
+        .sobre(de_la_voz)
+        .sobre(config.prosodia)
+        .sobre(del_modelo)
+}
+
+/// Volumen de reproducción: el del perfil activo o el general.
+pub fn volumen_actual() -> f32 {
+    let config = config::actual();
+    perfil_en(&config)
+        .and_then(|p| p.volumen)
+        .unwrap_or(config.volumen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const LIBRITTS: &str = r#"{
+        "language": { "code": "en_US" },
+        "num_speakers": 3,
+        "speaker_id_map": { "p225": 0, "p226": 1, "p239": 2 }
+    }"#;
+
+    /// Activa una configuración con tres perfiles; las voces van en `dir`.
+    fn configurar(dir: &Path) {
+        let voz = |nombre: &str| dir.join(nombre).to_string_lossy().replace('\\', "/");
+        let contenido = format!(
+            r#"
+motor = "piper"
+ruta_modelo = "{}"
+hablante = "p225"
+volumen = 0.9
+
+[voces_idioma]
+en = "{}"
+
+[[perfiles]]
+nombre = "Uno"
+voz = "{}"
+hablante = "p226"
+volumen = 0.5
+
+[[perfiles]]
+nombre = "Dos"
+
+[[perfiles]]
+nombre = "Tres"
+volumen = 0.3
+"#,
+            voz("es_ES-base-medium.onnx"),
+            voz("en_US-libritts-high.onnx"),
+            voz("en_US-perfil-high.onnx"),
+        );
+        let ruta = dir.join("config.toml");
+        fs::write(&ruta, contenido).unwrap();
+        config::cargar_config(&ruta);
+    }
+
+    #[test]
+    fn precedencia_idiomas_y_ciclo() {
+        let _bloqueo = config::BLOQUEO_PRUEBAS.lock();
+        let dir = std::env::temp_dir().join(format!(
+            "tray_narrator_prueba_{}_perfiles",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for modelo in ["en_US-libritts-high.onnx", "en_US-perfil-high.onnx"] {
+            fs::write(dir.join(format!("{}.json", modelo)), LIBRITTS).unwrap();
+        }
+        config::establecer_sobrescrituras(config::Sobrescrituras::default());
+        configurar(&dir);
+        desactivar_perfil();
+        voices::olvidar_voz();
+
+        let base = dir.join("es_ES-base-medium.onnx");
+        let ingles = dir.join("en_US-libritts-high.onnx");
+        let del_perfil = dir.join("en_US-perfil-high.onnx");
+
+        // Sin perfil: la configuración
+        assert_eq!(voz_actual(), base);
+        assert_eq!(volumen_actual(), 0.9);
+        assert_eq!(hablante_actual(&ingles), Ok(Some(0)));
+
+        // El perfil sobre la configuración
+        assert!(activar_perfil("Uno"));
+        assert_eq!(perfil_activo().as_deref(), Some("Uno"));
+        assert_eq!(voz_actual(), del_perfil);
+        assert_eq!(volumen_actual(), 0.5);
+        assert_eq!(hablante_actual(&ingles), Ok(Some(1)));
+        assert!(!activar_perfil("Cuatro"));
+        assert_eq!(perfil_activo().as_deref(), Some("Uno"));
+
+        // Lo elegido en el tray sobre el perfil
+        voices::elegir_voz(&ingles);
+        voices::elegir_hablante(&ingles, "p239");
+        assert_eq!(voz_actual(), ingles);
+        assert_eq!(hablante_actual(&ingles), Ok(Some(2)));
+        voices::olvidar_hablante(&ingles);
+        // Activar un perfil con voz propia olvida la elegida
+        assert!(activar_perfil("Uno"));
+        assert_eq!(voz_actual(), del_perfil);
+
+        // Voz por idioma: la del perfil ya habla inglés
+        assert_eq!(voz_para_idioma(Some("en")), del_perfil);
+        assert_eq!(voz_para_idioma(None), del_perfil);
+        desactivar_perfil();
+        assert_eq!(voz_para_idioma(Some("en")), ingles);
+        assert_eq!(voz_para_idioma(Some("es")), base);
+        assert_eq!(voz_para_idioma(Some("fr")), base);
+
+        // `--voice` manda sobre todo
+        config::establecer_sobrescrituras(config::Sobrescrituras {
+            voz: Some(PathBuf::from("forzada.onnx")),
+            motor: None,
+        });
+        assert!(activar_perfil("Uno"));
+        assert_eq!(voz_actual(), PathBuf::from("forzada.onnx"));
+        assert_eq!(voz_para_idioma(Some("en")), PathBuf::from("forzada.onnx"));
+        config::establecer_sobrescrituras(config::Sobrescrituras::default());
+        configurar(&dir);
+
+        // Ciclo: del último vuelve al primero
+        for esperado in ["Dos", "Tres", "Uno", "Dos"] {
+            ciclar_perfil();
+            assert_eq!(perfil_activo().as_deref(), Some(esperado));
+        }
+        assert_eq!(volumen_actual(), 0.9);
+        desactivar_perfil();
+        ciclar_perfil();
+        assert_eq!(perfil_activo().as_deref(), Some("Uno"));
+
+        // Un perfil que desaparece en una recarga deja de aplicarse
+        fs::write(dir.join("config.toml"), "volumen = 0.7").unwrap();
+        config::recargar_config(&dir.join("config.toml"));
+        assert_eq!(perfil_activo(), None);
+        assert_eq!(volumen_actual(), 0.7);
+        desactivar_perfil();
+    }
+}
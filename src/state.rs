@@ -1,23 +1,41 @@
-//! Estado global, comandos de audio y funciones de control de velocidad.
+//! Estado global, comandos de audio y funciones de control de velocidad y de
+//! nivel de puntuación.
 
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::sync::mpsc::Sender;
 
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 
-use crate::config::{VELOCIDADES_PRESET, VELOCIDAD_INICIAL};
+use crate::config::{
+    self, formatear_velocidad, ModoCodigo, NivelPuntuacion, VELOCIDAD_MAXIMA_X100,
+    VELOCIDAD_MINIMA_X100,
+};
 use crate::logging::log;
+use crate::persistence;
+use crate::tts::AudioSintetizado;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // COMANDOS Y ESTADO GLOBAL
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Comandos que se envían al hilo de audio
+/// Comandos que se envían al hilo de audio.
+///
+/// Los comandos con audio llevan el número de la narración que los generó; el
+/// hilo de audio descarta los de narraciones ya canceladas.
 #[derive(Debug)]
 pub enum ComandoAudio {
-    /// Reproducir el archivo WAV temporal
-    Reproducir,
+    /// Reproducir el primer fragmento de una narración (sustituye al que esté
+    /// sonando)
+    Reproducir {
+        narracion: u64,
+        audio: AudioSintetizado,
+    },
+    /// Añadir un fragmento a continuación del que está sonando
+    Encolar {
+        narracion: u64,
+        audio: AudioSintetizado,
+    },
     /// Detener la reproducción actual
     Detener,
     /// Pausar/Reanudar la reproducción
@@ -32,8 +50,26 @@ pub const ESTADO_PAUSADO: u8 = 2;
 /// Estado atómico de reproducción
 pub static ESTADO_AUDIO: AtomicU8 = AtomicU8::new(ESTADO_IDLE);
 
-/// Velocidad actual (length_scale × 100)
-pub static VELOCIDAD_ACTUAL: AtomicU32 = AtomicU32::new(VELOCIDAD_INICIAL);
+/// Velocidad actual (multiplicador × 100, p. ej. 135 = x1.35). Al arrancar se
+/// fija al valor guardado o al preset por defecto de la configuración.
+pub static VELOCIDAD_ACTUAL: AtomicU32 = AtomicU32::new(100);
+
+/// Nivel de puntuación actual (índice en `NivelPuntuacion::NIVELES`). Al
+/// arrancar se fija al valor guardado o al de la configuración.
+pub static NIVEL_PUNTUACION: AtomicU8 = AtomicU8::new(NivelPuntuacion::Algunos as u8);
+
+/// Modo de lectura de código actual (índice en `ModoCodigo::MODOS`). Al
+/// arrancar se fija al valor guardado o al de la configuración.
+pub static MODO_CODIGO: AtomicU8 = AtomicU8::new(ModoCodigo::Auto as u8);
+
+/// Número de comandos `Reproducir` y `Encolar` atendidos por el hilo de audio
+/// (con o sin éxito). Permite esperar a que un comando concreto se haya
+/// procesado.
+pub static REPRODUCCIONES_ATENDIDAS: AtomicU32 = AtomicU32::new(0);
+
+/// Número de la narración en curso. Cada narración nueva (o cancelación) lo
+/// incrementa, lo que invalida los fragmentos pendientes de la anterior.
+pub static NARRACION_ACTUAL: AtomicU64 = AtomicU64::new(0);
 
 /// Flag para indicar que la aplicación debe terminar
 pub static DEBE_SALIR: AtomicBool = AtomicBool::new(false);
@@ -55,59 +91,124 @@ pub fn enviar_comando(comando: ComandoAudio) {
     }
 }
 
-/// Obtiene la velocidad actual como float (length_scale para Piper)
+/// Obtiene la velocidad actual como multiplicador (1.0 = normal)
 pub fn obtener_velocidad() -> f32 {
     VELOCIDAD_ACTUAL.load(Ordering::SeqCst) as f32 / 100.0
 }
 
-/// Establece la velocidad a un valor absoluto de length_scale × 100.
-pub fn establecer_velocidad(length_scale_x100: u32) {
+/// Establece la velocidad a un multiplicador × 100, limitado al rango admitido.
+///
+/// El nuevo valor se guarda para restaurarlo en el próximo arranque.
+pub fn establecer_velocidad(multiplicador_x100: u32) {
+    let nueva = multiplicador_x100.clamp(VELOCIDAD_MINIMA_X100, VELOCIDAD_MAXIMA_X100);
     let actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
-    if length_scale_x100 != actual {
-        VELOCIDAD_ACTUAL.store(length_scale_x100, Ordering::SeqCst);
-        let etiqueta = etiqueta_velocidad_actual();
+    if nueva != actual {
+        VELOCIDAD_ACTUAL.store(nueva, Ordering::SeqCst);
+        persistence::guardar_velocidad(nueva);
         log(&format!(
             "Velocidad establecida: {} (length_scale: {:.2})",
-            etiqueta,
-            length_scale_x100 as f32 / 100.0
+            etiqueta_velocidad_actual(),
+            1.0 / obtener_velocidad()
         ));
     }
 }
 
-/// Devuelve el índice del preset actual en `VELOCIDADES_PRESET`.
-/// Si el valor no coincide exactamente, devuelve el más cercano.
-pub fn indice_preset_actual() -> usize {
+/// Devuelve el índice del preset que coincide con la velocidad actual, o
+/// `None` si es un valor personalizado (ajuste fino o `--speed`).
+pub fn indice_preset_actual() -> Option<usize> {
     let actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
-    VELOCIDADES_PRESET
+    config::actual()
+        .velocidades_preset
         .iter()
-        .enumerate()
-        .min_by_key(|(_, (_, ls))| (*ls as i32 - actual as i32).unsigned_abs())
-        .map(|(i, _)| i)
-        .unwrap_or(0)
+        .position(|p| p.multiplicador_x100() == actual)
 }
 
-/// Devuelve la etiqueta del preset de velocidad actual (ej: "x1.25").
-pub fn etiqueta_velocidad_actual() -> &'static str {
-    let idx = indice_preset_actual();
-    VELOCIDADES_PRESET[idx].0
+/// Devuelve la etiqueta de la velocidad actual: la del preset si coincide con
+/// uno, o el multiplicador (ej: "x1.35").
+pub fn etiqueta_velocidad_actual() -> String {
+    match indice_preset_actual() {
+        Some(idx) => config::actual().velocidades_preset[idx].etiqueta(),
+        None => formatear_velocidad(VELOCIDAD_ACTUAL.load(Ordering::SeqCst)),
+    }
 }
 
-/// Cicla al siguiente preset más rápido (mayor multiplicador → menor length_scale).
-pub fn velocidad_preset_mas_rapido() {
-    let idx = indice_preset_actual();
-    if idx + 1 < VELOCIDADES_PRESET.len() {
-        let (etiqueta, ls) = VELOCIDADES_PRESET[idx + 1];
-        establecer_velocidad(ls);
-        log(&format!("Hotkey: Velocidad → {}", etiqueta));
+/// Sube la velocidad: al siguiente preset más rápido o, con ajuste fino, un paso.
+pub fn velocidad_mas_rapido() {
+    let config = config::actual();
+    let actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
+    let nueva = if config.ajuste_fino {
+        Some(actual + config.paso_velocidad_x100())
+    } else {
+        config
+            .velocidades_preset
+            .iter()
+            .map(|p| p.multiplicador_x100())
+            .find(|&v| v > actual)
+    };
+
+    if let Some(nueva) = nueva {
+        establecer_velocidad(nueva);
+        log(&format!(
+            "Hotkey: Velocidad → {}",
+            etiqueta_velocidad_actual()
+        ));
     }
 }
 
-/// Cicla al siguiente preset más lento (menor multiplicador → mayor length_scale).
-pub fn velocidad_preset_mas_lento() {
-    let idx = indice_preset_actual();
-    if idx > 0 {
-        let (etiqueta, ls) = VELOCIDADES_PRESET[idx - 1];
-        establecer_velocidad(ls);
-        log(&format!("Hotkey: Velocidad → {}", etiqueta));
+/// Baja la velocidad: al preset anterior más lento o, con ajuste fino, un paso.
+pub fn velocidad_mas_lento() {
+    let config = config::actual();
+    let actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
+    let nueva = if config.ajuste_fino {
+        Some(actual.saturating_sub(config.paso_velocidad_x100()))
+    } else {
+        config
+            .velocidades_preset
+            .iter()
+            .map(|p| p.multiplicador_x100())
+            .rfind(|&v| v < actual)
+    };
+
+    if let Some(nueva) = nueva {
+        establecer_velocidad(nueva);
+        log(&format!(
+            "Hotkey: Velocidad → {}",
+            etiqueta_velocidad_actual()
+        ));
+    }
+}
+
+/// Nivel de puntuación con el que se preparan las lecturas.
+pub fn nivel_puntuacion() -> NivelPuntuacion {
+    let indice = NIVEL_PUNTUACION.load(Ordering::SeqCst) as usize;
+    NivelPuntuacion::NIVELES[indice.min(NivelPuntuacion::NIVELES.len() - 1)]
+}
+
+/// Cambia el nivel de puntuación y lo guarda para el próximo arranque.
+pub fn establecer_nivel_puntuacion(nivel: NivelPuntuacion) {
+    let anterior = NIVEL_PUNTUACION.swap(nivel as u8, Ordering::SeqCst);
+    if anterior != nivel as u8 {
+        persistence::guardar_puntuacion(nivel);
+        log(&format!("Puntuación establecida: {}", nivel.etiqueta()));
+    }
+}
+
+/// Pasa al nivel de puntuación siguiente, volviendo a "Ninguno" tras "Todos".
+pub fn ciclar_nivel_puntuacion() {
+    establecer_nivel_puntuacion(nivel_puntuacion().siguiente());
+}
+
+/// Cuándo se leen las selecciones como código.
+pub fn modo_codigo() -> ModoCodigo {
+    let indice = MODO_CODIGO.load(Ordering::SeqCst) as usize;
+    ModoCodigo::MODOS[indice.min(ModoCodigo::MODOS.len() - 1)]
+}
+
+/// Cambia el modo de lectura de código y lo guarda para el próximo arranque.
+pub fn establecer_modo_codigo(modo: ModoCodigo) {
+    let anterior = MODO_CODIGO.swap(modo as u8, Ordering::SeqCst);
+    if anterior != modo as u8 {
+        persistence::guardar_modo_codigo(modo);
+        log(&format!("Modo código establecido: {}", modo.etiqueta()));
     }
 }
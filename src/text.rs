@@ -0,0 +1,436 @@
+//! Procesado del texto antes de sintetizarlo.
+//!
+//! La división en fragmentos (el narrador sintetiza frase a frase para que la
+//! primera empiece a sonar sin esperar al resto), la limpieza del marcado
+//! ([`limpiar_marcado`]), la normalización de números, fechas y abreviaturas
+//! ([`normalizar`]) y la lectura de los signos según el nivel de puntuación
+//! ([`verbalizar_signos`]), precedidas de los emojis y los caracteres
+//! especiales ([`nombrar_emojis`], [`depurar_unicode`]). Aparte, el deletreo carácter a carácter
+//! ([`deletrear`]) y la lectura de código fuente ([`leer_codigo`]).
+
+mod code;
+mod emojis_es;
+mod markup;
+mod normalize;
+mod numbers;
+mod punctuation;
+mod spell;
+mod unicode;
+
+pub use code::{leer_codigo, parece_codigo};
+pub use markup::limpiar_marcado;
+pub use normalize::{normalizar, Idioma};
+pub use punctuation::verbalizar_signos;
+pub use spell::deletrear;
+pub use unicode::{depurar_unicode, nombrar_emojis};
+
+/// Longitud máxima de un fragmento (en caracteres). Una frase más larga se
+/// corta en la última pausa natural (coma, punto y coma, espacio) antes del
+/// límite, para que la primera síntesis siga siendo rápida.
+const MAX_CARACTERES_FRAGMENTO: usize = 300;
+
+/// Abreviaturas frecuentes cuyo punto no termina la frase (en minúsculas).
+const ABREVIATURAS: &[&str] = &[
+    "sr", "sra", "srta", "dr", "dra", "d", "dña", "ud", "uds", "lic", "ing", "prof", "etc", "pág",
+    "págs", "núm", "nº", "art", "cap", "vol", "ej", "aprox", "av", "mr", "mrs", "ms", "st", "vs",
+    "e.g", "i.e",
+];
+
+/// Signos que terminan una frase.
+fn es_fin_de_frase(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…')
+}
+
+/// Signos de cierre que pertenecen a la frase anterior (`"¿Vienes?" dijo.`).
+fn es_cierre(c: char) -> bool {
+    matches!(c, '"' | '\'' | '»' | '”' | '’' | ')' | ']')
+}
+
+/// Una línea que termina una frase y ocupa menos de esta fracción del ancho
+/// de su bloque cierra el párrafo (el resto de un párrafo de un PDF).
+const FRACCION_LINEA_CORTA: f32 = 0.75;
+
+/// Guiones que parten una palabra al final de la línea (normal, de división
+/// silábica y blando).
+const GUIONES: &[char] = &['-', '‐', '\u{ad}'];
+
+/// Divide el texto en párrafos y cada párrafo en fragmentos para sintetizar,
+/// uno por frase. Los párrafos vacíos se descartan.
+pub fn dividir_parrafos(texto: &str) -> Vec<Vec<String>> {
+    parrafos(texto)
+        .iter()
+        .map(|parrafo| {
+            let mut fragmentos = Vec::new();
+            for frase in frases_de(parrafo) {
+                partir_frase_larga(&frase, &mut fragmentos);
+            }
+            fragmentos
+        })
+        .filter(|fragmentos| !fragmentos.is_empty())
+        .collect()
+}
+
+/// Párrafos del texto en una sola línea cada uno.
+///
+/// Las líneas en blanco siempre separan párrafos. Dentro de un bloque se
+/// deshace el ajuste de línea del texto copiado de un PDF: una palabra
+/// partida con guion se vuelve a unir ("nece-\nsario") y las demás líneas se
+/// unen con un espacio, salvo que una línea termine una frase y sea corta o
+/// la siguiente esté sangrada, que es donde empieza otro párrafo. Cada
+/// elemento de una lista ("- uno", "2. dos") empieza también un párrafo.
+fn parrafos(texto: &str) -> Vec<String> {
+    let mut parrafos = Vec::new();
+    let mut bloque = Vec::new();
+    for linea in texto.lines() {
+        if linea.trim().is_empty() {
+            reunir_lineas(&bloque, &mut parrafos);
+            bloque.clear();
+        } else {
+            bloque.push(linea.trim_end());
+        }
+    }
+    reunir_lineas(&bloque, &mut parrafos);
+    parrafos
+}
+
+/// Une las líneas de un bloque sin líneas en blanco en uno o más párrafos.
+fn reunir_lineas(lineas: &[&str], parrafos: &mut Vec<String>) {
+    let ancho = lineas
+        .iter()
+        .map(|l| l.trim().chars().count())
+        .max()
+        .unwrap_or(0);
+    let sangria_base = lineas.iter().map(|l| sangria(l)).min().unwrap_or(0);
+
+    let mut actual = String::new();
+    for (i, linea) in lineas.iter().enumerate() {
+        let contenido = linea.trim();
+        if i > 0 {
+            let anterior = lineas[i - 1].trim();
+            let corta = (anterior.chars().count() as f32) < ancho as f32 * FRACCION_LINEA_CORTA;
+            let guion = anterior
+                .strip_suffix(GUIONES)
+                .and_then(|resto| resto.chars().next_back())
+                .is_some_and(char::is_alphabetic);
+
+            if marca_de_lista(contenido).is_some()
+                || termina_frase(anterior) && (corta || sangria(linea) > sangria_base)
+            {
+                cerrar_parrafo(std::mem::take(&mut actual), parrafos);
+            } else if guion && contenido.chars().next().is_some_and(char::is_lowercase) {
+                // "nece-" + "sario": fuera el guion, sin espacio
+                actual.pop();
+            } else {
+                actual.push(' ');
+            }
+        }
+        actual.push_str(contenido);
+    }
+
+    cerrar_parrafo(actual, parrafos);
+}
+
+/// Añade el párrafo si no está vacío.
+fn cerrar_parrafo(mut parrafo: String, parrafos: &mut Vec<String>) {
+    // El guion blando solo indica dónde se puede partir la palabra
+    parrafo.retain(|c| c != '\u{ad}');
+    if !parrafo.is_empty() {
+        parrafos.push(parrafo);
+    }
+}
+
+/// Espacios al principio de la línea (un tabulador cuenta como cuatro).
+fn sangria(linea: &str) -> usize {
+    linea
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Marca de elemento de lista al principio de la línea ("- ", "* ", "• ",
+/// "2. ", "3) "): su longitud en caracteres, sin el espacio que la sigue.
+fn marca_de_lista(linea: &str) -> Option<usize> {
+    let contenido = linea.trim_start();
+    let espacios = linea.chars().count() - contenido.chars().count();
+    let largo = if contenido.starts_with(['-', '*', '+', '•']) {
+        1
+    } else {
+        let cifras = contenido.chars().take_while(char::is_ascii_digit).count();
+        let cierre = contenido.chars().nth(cifras);
+        if !(1..=3).contains(&cifras) || !matches!(cierre, Some('.' | ')')) {
+            return None;
+        }
+        cifras + 1
+    };
+    contenido
+        .chars()
+        .nth(largo)
+        .is_some_and(char::is_whitespace)
+        .then_some(espacios + largo)
+}
+
+/// `true` si la línea termina con un signo de fin de frase o dos puntos,
+/// aunque le sigan comillas o paréntesis de cierre.
+fn termina_frase(linea: &str) -> bool {
+    linea
+        .trim_end_matches(es_cierre)
+        .chars()
+        .next_back()
+        .is_some_and(|c| es_fin_de_frase(c) || c == ':')
+}
+
+/// Divide un párrafo en frases. Un signo de fin de frase solo corta si le
+/// sigue un espacio y la siguiente palabra no empieza en minúscula, y si no
+/// cierra una abreviatura conocida ("Sr. García", "3.5", "p. ej. esto"). El
+/// punto del número de un elemento de lista ("1. Primero") tampoco corta.
+fn frases_de(parrafo: &str) -> Vec<String> {
+    let caracteres: Vec<char> = parrafo.chars().collect();
+    let mut frases = Vec::new();
+    let mut inicio = 0;
+    let mut i = marca_de_lista(parrafo).unwrap_or(0);
+
+    while i < caracteres.len() {
+        if !es_fin_de_frase(caracteres[i]) {
+            i += 1;
+            continue;
+        }
+
+        let signo = i;
+        // Incluir los signos repetidos ("?!", "...") y las comillas de cierre
+        let mut fin = i + 1;
+        while fin < caracteres.len()
+            && (es_fin_de_frase(caracteres[fin]) || es_cierre(caracteres[fin]))
+        {
+            fin += 1;
+        }
+
+        let siguiente = caracteres[fin..].iter().position(|c| !c.is_whitespace());
+        let corta = match siguiente {
+            // Fin del párrafo: lo recoge el final del bucle
+            None => false,
+            // Sin espacio después ("3.5", "www.ejemplo.com")
+            Some(0) => false,
+            Some(desplazamiento) => {
+                let abreviatura =
+                    caracteres[signo] == '.' && termina_en_abreviatura(&caracteres[inicio..signo]);
+                !caracteres[fin + desplazamiento].is_lowercase() && !abreviatura
+            }
+        };
+
+        if corta {
+            let frase: String = caracteres[inicio..fin].iter().collect();
+            frases.push(frase.trim().to_string());
+            inicio = fin;
+        }
+        i = fin;
+    }
+
+    let resto: String = caracteres[inicio..].iter().collect();
+    if !resto.trim().is_empty() {
+        frases.push(resto.trim().to_string());
+    }
+    frases
+}
+
+/// `true` si el texto anterior a un punto termina en una abreviatura.
+fn termina_en_abreviatura(antes: &[char]) -> bool {
+    let palabra: String = antes
+        .iter()
+        .rev()
+        .take_while(|c| c.is_alphanumeric() || **c == '.' || **c == 'º')
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect::<String>()
+        .to_lowercase();
+
+    // Iniciales sueltas ("J. R. R. Tolkien")
+    palabra.chars().count() == 1 && palabra.chars().all(char::is_alphabetic)
+        || ABREVIATURAS.contains(&palabra.as_str())
+}
+
+/// Añade la frase a `fragmentos`, partiéndola si supera la longitud máxima.
+fn partir_frase_larga(frase: &str, fragmentos: &mut Vec<String>) {
+    let mut resto = frase.trim();
+    while resto.chars().count() > MAX_CARACTERES_FRAGMENTO {
+        let limite = resto
+            .char_indices()
+            .nth(MAX_CARACTERES_FRAGMENTO)
+            .map_or(resto.len(), |(idx, _)| idx);
+        let cabeza = &resto[..limite];
+
+        // Preferir una pausa natural; si no hay, el último espacio; si
+        // tampoco, cortar en el límite
+        let corte = cabeza
+            .rfind([',', ';', ':'])
+            .map(|idx| idx + 1)
+            .or_else(|| cabeza.rfind(char::is_whitespace))
+            .filter(|&idx| idx > 0)
+            .unwrap_or(limite);
+
+        let fragmento = resto[..corte].trim();
+        if !fragmento.is_empty() {
+            fragmentos.push(fragmento.to_string());
+        }
+        resto = resto[corte..].trim_start();
+    }
+    if !resto.is_empty() {
+        fragmentos.push(resto.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn une_lineas_partidas() {
+        // Palabra partida con guion y ajuste de línea a mitad de frase
+        assert_eq!(
+            parrafos("Es nece-\nsario leer el texto\ncompleto antes de seguir."),
+            ["Es necesario leer el texto completo antes de seguir."]
+        );
+        // Un guion entre palabras se conserva si sigue una mayúscula
+        assert_eq!(parrafos("Madrid-\nBarcelona"), ["Madrid- Barcelona"]);
+    }
+
+    #[test]
+    fn separa_parrafos() {
+        assert_eq!(
+            parrafos("Primer párrafo.\n\n  \nSegundo párrafo."),
+            ["Primer párrafo.", "Segundo párrafo."]
+        );
+        // Sangría tras una frase terminada: otro párrafo
+        assert_eq!(
+            parrafos("Una línea larga que termina en punto.\n    Otra que empieza sangrada."),
+            [
+                "Una línea larga que termina en punto.",
+                "Otra que empieza sangrada."
+            ]
+        );
+        // Línea corta que cierra frase: fin del párrafo de un PDF
+        assert_eq!(
+            parrafos("Esta línea ocupa todo el ancho del bloque\nde texto.\nY esta empieza otro."),
+            [
+                "Esta línea ocupa todo el ancho del bloque de texto.",
+                "Y esta empieza otro."
+            ]
+        );
+    }
+
+    #[test]
+    fn listas() {
+        assert_eq!(
+            parrafos("1. Primero\n2. Segundo\n3) Tercero"),
+            ["1. Primero", "2. Segundo", "3) Tercero"]
+        );
+        assert_eq!(
+            parrafos("Compra:\n- item uno\n- item dos\n• item tres"),
+            ["Compra:", "- item uno", "- item dos", "• item tres"]
+        );
+        // Un número o un guion que no son marcas de lista no cortan
+        assert_eq!(
+            parrafos("en el año\n2024 hubo\n-5 grados"),
+            ["en el año 2024 hubo -5 grados"]
+        );
+        assert_eq!(
+            dividir_parrafos("1. Primero\n2. Segundo. Con dos frases."),
+            [vec!["1. Primero"], vec!["2. Segundo.", "Con dos frases."]]
+        );
+        assert_eq!(marca_de_lista("  12) doce"), Some(5));
+        assert_eq!(marca_de_lista("1.5 litros"), None);
+        assert_eq!(marca_de_lista("1234. Fin"), None);
+    }
+
+    #[test]
+    fn frases() {
+        assert_eq!(
+            frases_de("Hola. ¿Vienes? ¡Sí!"),
+            ["Hola.", "¿Vienes?", "¡Sí!"]
+        );
+        // Abreviaturas, iniciales y decimales no cortan
+        assert_eq!(
+            frases_de("El Sr. García vino, p. ej. ayer. J. R. R. Tolkien pesa 3.5 kg."),
+            [
+                "El Sr. García vino, p. ej. ayer.",
+                "J. R. R. Tolkien pesa 3.5 kg."
+            ]
+        );
+        // Ni un punto seguido de minúscula o sin espacio detrás
+        assert_eq!(
+            frases_de("Visita www.ejemplo.com. luego sigue. Fin"),
+            ["Visita www.ejemplo.com. luego sigue.", "Fin"]
+        );
+        // Los puntos suspensivos y las comillas o paréntesis de cierre van
+        // con la frase que terminan
+        assert_eq!(
+            frases_de("Pues... Ya veremos… \"¿Vienes?\" Dijo. (Eso es todo.) Adiós."),
+            [
+                "Pues...",
+                "Ya veremos…",
+                "\"¿Vienes?\"",
+                "Dijo.",
+                "(Eso es todo.)",
+                "Adiós."
+            ]
+        );
+        assert_eq!(frases_de("   "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn abreviaturas() {
+        let antes = |texto: &str| texto.chars().collect::<Vec<_>>();
+        assert!(termina_en_abreviatura(&antes("Hola, Sr")));
+        assert!(termina_en_abreviatura(&antes("unos 3 etc")));
+        assert!(termina_en_abreviatura(&antes("como p. ej")));
+        assert!(termina_en_abreviatura(&antes("el nº")));
+        assert!(termina_en_abreviatura(&antes("J. R")));
+        assert!(termina_en_abreviatura(&antes("i.e")));
+        assert!(!termina_en_abreviatura(&antes("fin de la casa")));
+        assert!(!termina_en_abreviatura(&antes("")));
+    }
+
+    #[test]
+    fn frases_largas() {
+        let mut fragmentos = Vec::new();
+        partir_frase_larga("  Corta.  ", &mut fragmentos);
+        assert_eq!(fragmentos, ["Corta."]);
+
+        // Se corta en la última coma antes del límite
+        let frase = format!("{}, {}", "a".repeat(250), "b ".repeat(100));
+        let mut fragmentos = Vec::new();
+        partir_frase_larga(&frase, &mut fragmentos);
+        assert_eq!(fragmentos.len(), 2);
+        assert_eq!(fragmentos[0], format!("{},", "a".repeat(250)));
+        assert!(fragmentos[1].starts_with("b b"));
+
+        // Sin pausas, en el último espacio; sin espacios, en el límite
+        let frase = "palabra ".repeat(50);
+        let mut fragmentos = Vec::new();
+        partir_frase_larga(&frase, &mut fragmentos);
+        assert!(fragmentos
+            .iter()
+            .all(|f| f.chars().count() <= MAX_CARACTERES_FRAGMENTO && f.ends_with("palabra")));
+        assert_eq!(fragmentos.join(" "), frase.trim());
+
+        let mut fragmentos = Vec::new();
+        partir_frase_larga(&"ñ".repeat(650), &mut fragmentos);
+        let largos: Vec<usize> = fragmentos.iter().map(|f| f.chars().count()).collect();
+        assert_eq!(largos, [300, 300, 50]);
+    }
+
+    #[test]
+    fn sangria_y_fin_de_frase() {
+        assert_eq!(sangria("    cuatro"), 4);
+        assert_eq!(sangria("\t\tocho"), 8);
+        assert_eq!(sangria("cero"), 0);
+
+        assert!(termina_frase("Fin."));
+        assert!(termina_frase("¿Vienes?\u{bb}"));
+        assert!(termina_frase("(como se dijo.)"));
+        assert!(termina_frase("Lo siguiente:"));
+        assert!(!termina_frase("sigue la frase"));
+        assert!(!termina_frase("coma,"));
+    }
+}
@@ -0,0 +1,444 @@
+//! Lectura de código fuente.
+//!
+//! Leído como prosa, `snake_case_names`, `camelCase`, `->` o la sangría
+//! suenan a ruido. En modo código cada línea se lee como una frase: los
+//! identificadores se parten en palabras, los operadores se nombran, las
+//! cadenas largas se resumen, los comentarios se anuncian y se avisa de los
+//! cambios de nivel de sangría. Los paréntesis, corchetes y la puntuación se
+//! dejan para el nivel de puntuación (ver [`super::verbalizar_signos`]).
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::Idioma;
+
+/// Las cadenas de más caracteres se resumen ("cadena de 120 caracteres").
+const LITERAL_MAXIMO: usize = 40;
+
+/// Ancho de un tabulador en la sangría.
+const ANCHO_TABULADOR: usize = 4;
+
+/// Operadores con nombre, de más largo a más corto para que `==` no se lea
+/// como dos `=`.
+const OPERADORES: &[(&str, &str, &str)] = &[
+    ("===", "igual igual igual", "triple equals"),
+    ("!==", "distinto distinto", "strictly not equals"),
+    ("...", "puntos suspensivos", "ellipsis"),
+    ("->", "flecha", "arrow"),
+    ("=>", "flecha doble", "fat arrow"),
+    ("::", "doble dos puntos", "double colon"),
+    ("==", "igual igual", "equals equals"),
+    ("!=", "distinto de", "not equals"),
+    ("<=", "menor o igual que", "less or equal"),
+    (">=", "mayor o igual que", "greater or equal"),
+    ("&&", "y", "and"),
+    ("||", "o", "or"),
+    ("+=", "más igual", "plus equals"),
+    ("-=", "menos igual", "minus equals"),
+    ("*=", "por igual", "times equals"),
+    ("/=", "entre igual", "divide equals"),
+    ("%=", "módulo igual", "mod equals"),
+    ("++", "más más", "plus plus"),
+    ("--", "menos menos", "minus minus"),
+    ("<<", "desplazamiento a la izquierda", "shift left"),
+    (">>", "desplazamiento a la derecha", "shift right"),
+    ("**", "elevado a", "power"),
+    ("/*", "abre comentario", "open comment"),
+    ("*/", "cierra comentario", "close comment"),
+    ("..", "hasta", "to"),
+    ("=", "igual", "equals"),
+    ("+", "más", "plus"),
+    ("-", "menos", "minus"),
+    ("*", "asterisco", "star"),
+    ("/", "barra", "slash"),
+    ("%", "módulo", "mod"),
+    ("<", "menor que", "less than"),
+    (">", "mayor que", "greater than"),
+    ("!", "no", "not"),
+    ("&", "ampersand", "ampersand"),
+    ("|", "barra vertical", "pipe"),
+    ("^", "circunflejo", "caret"),
+    ("~", "virgulilla", "tilde"),
+    ("?", "interrogación", "question mark"),
+    (".", "punto", "dot"),
+    ("@", "arroba", "at"),
+    ("$", "dólar", "dollar"),
+    ("#", "almohadilla", "hash"),
+    ("\\", "barra invertida", "backslash"),
+];
+
+/// Palabras clave que abren una línea de código (seguidas de espacio o `(`).
+const PALABRAS_CLAVE: &[&str] = &[
+    "fn",
+    "def",
+    "function",
+    "func",
+    "class",
+    "struct",
+    "impl",
+    "enum",
+    "trait",
+    "interface",
+    "let",
+    "const",
+    "var",
+    "import",
+    "from",
+    "use",
+    "return",
+    "pub",
+    "public",
+    "private",
+    "static",
+    "if",
+    "elif",
+    "else",
+    "for",
+    "while",
+    "match",
+    "switch",
+    "case",
+    "package",
+    "#include",
+    "#define",
+];
+
+lazy_static! {
+    /// Operadores que casi solo aparecen en código.
+    static ref RE_OPERADOR_CODIGO: Regex =
+        Regex::new(r"->|=>|::|==|!=|&&|\|\||\+=|-=|\(\);|\)\s*\{").unwrap();
+    /// Llamada a un identificador en snake_case o camelCase: `leer_archivo(`.
+    static ref RE_LLAMADA: Regex =
+        Regex::new(r"\b[a-z][a-z0-9]*(?:_[a-z0-9]+|[A-Z][a-z0-9]*)+\s*\(").unwrap();
+    /// Un único identificador compuesto (`snake_case`, `camelCase`).
+    static ref RE_IDENTIFICADOR: Regex =
+        Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*(?:_[A-Za-z0-9]|[a-z][A-Z])[A-Za-z0-9_]*$").unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DETECCIÓN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Si una línea (sin sangría) tiene aspecto de código.
+fn linea_de_codigo(linea: &str) -> bool {
+    if linea.ends_with(';') || linea.ends_with('{') || linea.ends_with('}') {
+        return true;
+    }
+    if RE_OPERADOR_CODIGO.is_match(linea) || RE_LLAMADA.is_match(linea) {
+        return true;
+    }
+    let empieza_con_clave = PALABRAS_CLAVE.iter().any(|clave| {
+        linea
+            .strip_prefix(clave)
+            .is_some_and(|resto| resto.starts_with([' ', '(']))
+    });
+    empieza_con_clave && linea.contains(['(', ')', '=', ':', '{']) && !linea.ends_with('.')
+}
+
+/// Si el texto parece código: al menos la mitad de sus líneas lo parecen (y
+/// más de una, salvo que sea una sola línea), o es un único identificador
+/// compuesto.
+pub fn parece_codigo(texto: &str) -> bool {
+    let lineas: Vec<&str> = texto
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    if let [unica] = lineas.as_slice() {
+        if RE_IDENTIFICADOR.is_match(unica) {
+            return true;
+        }
+    }
+
+    let de_codigo = lineas.iter().filter(|l| linea_de_codigo(l)).count();
+    !lineas.is_empty() && de_codigo * 2 >= lineas.len() && (de_codigo >= 2 || lineas.len() == 1)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LECTURA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Textos fijos de la lectura en cada idioma.
+struct Frases {
+    nivel: &'static str,
+    comentario: &'static str,
+    cadena: &'static str,
+}
+
+fn frases(idioma: Idioma) -> Frases {
+    match idioma {
+        Idioma::Es => Frases {
+            nivel: "nivel",
+            comentario: "comentario",
+            cadena: "cadena de {} caracteres",
+        },
+        Idioma::En => Frases {
+            nivel: "level",
+            comentario: "comment",
+            cadena: "string of {} characters",
+        },
+    }
+}
+
+/// Parte un identificador en palabras: `snake_case` por los guiones bajos y
+/// `camelCase` por las mayúsculas ("HTTPServer" → "HTTP server", "utf8" →
+/// "utf 8"). Las siglas se dejan en mayúsculas y el resto en minúsculas.
+fn partir_identificador(identificador: &str) -> Vec<String> {
+    let mut palabras = Vec::new();
+    for parte in identificador.split('_').filter(|p| !p.is_empty()) {
+        let caracteres: Vec<char> = parte.chars().collect();
+        let mut palabra = String::new();
+        for (i, &c) in caracteres.iter().enumerate() {
+            if let Some(&anterior) = i.checked_sub(1).and_then(|j| caracteres.get(j)) {
+                let siguiente_minuscula = caracteres.get(i + 1).is_some_and(|s| s.is_lowercase());
+                let corte = (anterior.is_lowercase() && c.is_uppercase())
+                    || (anterior.is_uppercase() && c.is_uppercase() && siguiente_minuscula)
+                    || (anterior.is_alphabetic() && c.is_ascii_digit())
+                    || (anterior.is_ascii_digit() && c.is_alphabetic());
+                if corte {
+                    palabras.push(std::mem::take(&mut palabra));
+                }
+            }
+            palabra.push(c);
+        }
+        palabras.push(palabra);
+    }
+
+    palabras
+        .into_iter()
+        .map(|p| {
+            let sigla = p.chars().count() > 1 && p.chars().all(|c| !c.is_lowercase());
+            if sigla {
+                p
+            } else {
+                p.to_lowercase()
+            }
+        })
+        .collect()
+}
+
+/// Nombre del operador más largo que empieza en `resto`, con su longitud en
+/// caracteres.
+fn operador(resto: &[char], idioma: Idioma) -> Option<(&'static str, usize)> {
+    OPERADORES.iter().find_map(|&(simbolo, es, en)| {
+        let largo = simbolo.chars().count();
+        let coincide = resto.len() >= largo && simbolo.chars().zip(resto).all(|(a, &b)| a == b);
+        coincide.then_some((if idioma == Idioma::Es { es } else { en }, largo))
+    })
+}
+
+/// Lee una línea sin sangría.
+fn leer_linea(linea: &str, idioma: Idioma) -> String {
+    let frases = frases(idioma);
+    let caracteres: Vec<char> = linea.chars().collect();
+    let mut salida: Vec<String> = Vec::new();
+
+    // Comentario de línea al estilo de Python o de la shell
+    if let Some(resto) = linea.strip_prefix("# ") {
+        return format!("{} {}", frases.comentario, resto.trim());
+    }
+
+    let mut i = 0;
+    while i < caracteres.len() {
+        let c = caracteres[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if caracteres[i..].starts_with(&['/', '/']) {
+            let resto: String = caracteres[i + 2..].iter().collect();
+            salida.push(frases.comentario.to_string());
+            salida.push(resto.trim_start_matches('/').trim().to_string());
+            break;
+        } else if matches!(c, '"' | '\'' | '`') {
+            // Cadena hasta la comilla de cierre (sin contar las escapadas);
+            // una comilla sin cierre (`'a` en Rust) se deja al nivel de
+            // puntuación
+            let mut fin = None;
+            let mut j = i + 1;
+            while j < caracteres.len() {
+                match caracteres[j] {
+                    '\\' => j += 1,
+                    cierre if cierre == c => {
+                        fin = Some(j);
+                        break;
+                    }
+                    _ => {}
+                }
+                j += 1;
+            }
+            match fin {
+                Some(fin) => {
+                    let largo = fin - i - 1;
+                    if largo > LITERAL_MAXIMO {
+                        salida.push(frases.cadena.replace("{}", &largo.to_string()));
+                    } else {
+                        salida.push(caracteres[i..=fin].iter().collect());
+                    }
+                    i = fin + 1;
+                }
+                None => {
+                    salida.push(c.to_string());
+                    i += 1;
+                }
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let inicio = i;
+            while i < caracteres.len() && (caracteres[i].is_alphanumeric() || caracteres[i] == '_')
+            {
+                i += 1;
+            }
+            let identificador: String = caracteres[inicio..i].iter().collect();
+            salida.extend(partir_identificador(&identificador));
+            // `println!`, `vec!`: la exclamación de una macro no se lee
+            if caracteres.get(i) == Some(&'!') && caracteres.get(i + 1) != Some(&'=') {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() {
+            let inicio = i;
+            while i < caracteres.len()
+                && (caracteres[i].is_ascii_alphanumeric()
+                    || (caracteres[i] == '.'
+                        && caracteres.get(i + 1).is_some_and(char::is_ascii_digit)))
+            {
+                i += 1;
+            }
+            salida.push(caracteres[inicio..i].iter().collect());
+        } else if let Some((nombre, largo)) = operador(&caracteres[i..], idioma) {
+            salida.push(nombre.to_string());
+            i += largo;
+        } else {
+            // Paréntesis, corchetes, comas…: según el nivel de puntuación
+            salida.push(c.to_string());
+            i += 1;
+        }
+    }
+
+    salida.retain(|s| !s.is_empty());
+    salida.join(" ")
+}
+
+/// Ancho de la sangría de una línea.
+fn sangria(linea: &str) -> usize {
+    linea
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { ANCHO_TABULADOR } else { 1 })
+        .sum()
+}
+
+/// Convierte código en frases para leer: una por línea, con los párrafos
+/// separados por las líneas en blanco. Cuando cambia la sangría, la línea
+/// empieza con su nivel ("nivel 2"), contado desde la sangría de la primera
+/// línea.
+pub fn leer_codigo(texto: &str, idioma: Idioma) -> Vec<Vec<String>> {
+    let frases = frases(idioma);
+    let mut parrafos: Vec<Vec<String>> = Vec::new();
+    let mut actual: Vec<String> = Vec::new();
+    // Sangrías abiertas, de la más externa a la más interna
+    let mut niveles: Vec<usize> = Vec::new();
+
+    for linea in texto.lines() {
+        if linea.trim().is_empty() {
+            if !actual.is_empty() {
+                parrafos.push(std::mem::take(&mut actual));
+            }
+            continue;
+        }
+
+        let ancho = sangria(linea);
+        let anterior = niveles.len();
+        while niveles.last().is_some_and(|&n| n > ancho) {
+            niveles.pop();
+        }
+        if niveles.last() != Some(&ancho) {
+            niveles.push(ancho);
+        }
+        // La primera línea fija el nivel 0 y no se anuncia
+        let cambio = anterior != 0 && niveles.len() != anterior;
+
+        let leida = leer_linea(linea.trim(), idioma);
+        if leida.is_empty() {
+            continue;
+        }
+        actual.push(if cambio {
+            format!("{} {}, {}", frases.nivel, niveles.len() - 1, leida)
+        } else {
+            leida
+        });
+    }
+    if !actual.is_empty() {
+        parrafos.push(actual);
+    }
+    parrafos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deteccion() {
+        assert!(parece_codigo("let x = foo(1);\nif x > 2 {\n    return;\n}"));
+        assert!(parece_codigo(
+            "def leer(ruta):\n    return open(ruta).read()"
+        ));
+        assert!(parece_codigo("snake_case_names"));
+        assert!(parece_codigo("getUserName"));
+        assert!(!parece_codigo(
+            "Hola, ¿cómo estás? Te escribo por lo de mañana."
+        ));
+        assert!(!parece_codigo(
+            "For example, see the docs.\nThen restart the server (if needed)."
+        ));
+    }
+
+    #[test]
+    fn identificadores() {
+        assert_eq!(
+            partir_identificador("snake_case_names"),
+            ["snake", "case", "names"]
+        );
+        assert_eq!(partir_identificador("getUserName"), ["get", "user", "name"]);
+        assert_eq!(partir_identificador("HTTPServer"), ["HTTP", "server"]);
+        assert_eq!(partir_identificador("MAX_SIZE"), ["MAX", "SIZE"]);
+        assert_eq!(partir_identificador("utf8"), ["utf", "8"]);
+    }
+
+    #[test]
+    fn operadores_y_cadenas() {
+        assert_eq!(
+            leer_linea("fn leer_todo(x: &str) -> usize {", Idioma::Es),
+            "fn leer todo ( x : ampersand str ) flecha usize {"
+        );
+        assert_eq!(
+            leer_linea("if a != b && c >= 1.5 {", Idioma::En),
+            "if a not equals b and c greater or equal 1.5 {"
+        );
+        assert_eq!(
+            leer_linea("println!(\"hola\"); // saluda", Idioma::Es),
+            "println ( \"hola\" ) ; comentario saluda"
+        );
+        let larga = format!("let s = \"{}\";", "x".repeat(50));
+        assert_eq!(
+            leer_linea(&larga, Idioma::Es),
+            "let s igual cadena de 50 caracteres ;"
+        );
+    }
+
+    #[test]
+    fn sangria_y_parrafos() {
+        let codigo = "fn main() {\n    if x {\n        y();\n    }\n\n    z();\n}";
+        assert_eq!(
+            leer_codigo(codigo, Idioma::Es),
+            [
+                vec![
+                    "fn main ( ) {",
+                    "nivel 1, if x {",
+                    "nivel 2, y ( ) ;",
+                    "nivel 1, }"
+                ],
+                vec!["z ( ) ;", "nivel 0, }"],
+            ]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Nombres cortos en español de los emojis más habituales en los chats.
+//!
+//! Tabla provisional escrita a mano, no la de CLDR: unos noventa emojis.
+//! `scripts/generar-emojis-es.py` la sustituye por la generada desde
+//! `annotations/es.xml` y `annotationsDerived/es.xml`, con el mismo formato.
+
+/// Nombre de cada emoji, sin selectores de variante, ordenados para
+/// buscar con `binary_search_by`.
+pub const NOMBRES_ES: &[(&str, &str)] = &[
+    ("⏰", "despertador"),
+    ("☀", "sol"),
+    ("☕", "bebida caliente"),
+    ("⚠", "advertencia"),
+    ("✅", "botón de marca de verificación"),
+    ("✔", "marca de verificación"),
+    ("✨", "chispas"),
+    ("❌", "marca de cruz"),
+    ("❓", "signo de interrogación rojo"),
+    ("❗", "signo de exclamación rojo"),
+    ("❤", "corazón rojo"),
+    ("➡", "flecha hacia la derecha"),
+    ("⭐", "estrella"),
+    ("🌟", "estrella brillante"),
+    ("🎁", "regalo"),
+    ("🎂", "tarta de cumpleaños"),
+    ("🎉", "cañón de confeti"),
+    ("🎯", "diana"),
+    ("🏁", "bandera de cuadros"),
+    ("🏆", "trofeo"),
+    ("🏳", "bandera blanca"),
+    ("🏳‍🌈", "bandera del arcoíris"),
+    ("🏴", "bandera negra"),
+    ("🏴‍☠", "bandera pirata"),
+    ("🐛", "insecto"),
+    ("👀", "ojos"),
+    ("👋", "mano saludando"),
+    ("👌", "señal de aprobación con la mano"),
+    ("👍", "pulgar hacia arriba"),
+    ("👎", "pulgar hacia abajo"),
+    ("👏", "manos aplaudiendo"),
+    ("👦", "niño"),
+    ("👧", "niña"),
+    ("👨", "hombre"),
+    ("👩", "mujer"),
+    ("👶", "bebé"),
+    ("💔", "corazón roto"),
+    ("💙", "corazón azul"),
+    ("💚", "corazón verde"),
+    ("💛", "corazón amarillo"),
+    ("💜", "corazón morado"),
+    ("💡", "bombilla"),
+    ("💪", "bíceps flexionado"),
+    ("💬", "bocadillo de diálogo"),
+    ("💯", "cien puntos"),
+    ("💻", "ordenador portátil"),
+    ("📅", "calendario"),
+    ("📌", "chincheta"),
+    ("📎", "clip"),
+    ("📝", "cuaderno con lápiz"),
+    ("🔥", "fuego"),
+    ("🖤", "corazón negro"),
+    ("😀", "cara sonriendo"),
+    ("😁", "cara radiante con ojos sonrientes"),
+    ("😂", "cara llorando de risa"),
+    ("😃", "cara sonriendo con ojos grandes"),
+    ("😄", "cara sonriendo con ojos sonrientes"),
+    ("😅", "cara sonriendo con sudor frío"),
+    ("😆", "cara sonriendo con los ojos cerrados"),
+    ("😇", "cara sonriendo con aureola"),
+    ("😉", "cara guiñando el ojo"),
+    ("😊", "cara feliz con ojos sonrientes"),
+    ("😍", "cara sonriendo con ojos de corazón"),
+    ("😎", "cara sonriendo con gafas de sol"),
+    ("😐", "cara neutral"),
+    ("😕", "cara de confusión"),
+    ("😘", "cara lanzando un beso"),
+    ("😞", "cara decepcionada"),
+    ("😠", "cara enfadada"),
+    ("😢", "cara llorando"),
+    ("😬", "cara haciendo una mueca"),
+    ("😭", "cara llorando fuerte"),
+    ("😱", "cara gritando de miedo"),
+    ("😴", "cara durmiendo"),
+    ("🙂", "cara sonriendo ligeramente"),
+    ("🙃", "cara al revés"),
+    ("🙈", "mono con los ojos tapados"),
+    ("🙌", "manos levantadas celebrando"),
+    ("🙏", "manos en oración"),
+    ("🚀", "cohete"),
+    ("🚩", "bandera triangular"),
+    ("🤍", "corazón blanco"),
+    ("🤔", "cara pensativa"),
+    ("🤝", "apretón de manos"),
+    ("🤣", "cara revolviéndose de la risa"),
+    ("🤦", "persona con la mano en la frente"),
+    ("🤯", "cabeza explotando"),
+    ("🤷", "persona encogida de hombros"),
+    ("🥳", "cara de fiesta"),
+    ("🧑", "persona"),
+    ("🧡", "corazón naranja"),
+];
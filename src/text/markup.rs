@@ -0,0 +1,351 @@
+//! Limpieza del marcado del texto copiado.
+//!
+//! Lo que se copia de GitHub, Confluence o Wikipedia trae Markdown, HTML o
+//! wikitexto: sin limpiar, la voz lee "almohadilla", "asterisco asterisco",
+//! "corchete uno" o URLs enteras. Aquí se reduce a texto legible. Los
+//! títulos, elementos de lista y filas de tabla pasan a ser párrafos propios,
+//! que el narrador lee con una pausa.
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+/// Quita el marcado de Markdown, HTML y wikitexto, las llamadas a notas
+/// ("[1]") y reduce las URLs a su dominio.
+pub fn limpiar_marcado(texto: &str) -> String {
+    let mut texto = texto.replace("\r\n", "\n");
+    if RE_HTML_DETECTAR.is_match(&texto) {
+        texto = limpiar_html(&texto);
+    }
+    texto = decodificar_entidades(&texto);
+    texto = limpiar_wiki(&texto);
+    // Antes que Markdown: "[2][3]" parecería un enlace por referencia
+    texto = RE_CITA.replace_all(&texto, "").into_owned();
+    texto = limpiar_lineas_markdown(&texto);
+    texto = limpiar_en_linea(&texto);
+    abreviar_urls(&texto)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HTML
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    /// Etiquetas habituales: solo con ellas se trata el texto como HTML, para
+    /// no tocar comparaciones como "a < b > c"
+    static ref RE_HTML_DETECTAR: Regex = Regex::new(
+        r"(?i)</?(?:p|br|div|span|a|b|i|u|em|strong|h[1-6]|li|ul|ol|table|tr|td|th|code|pre|blockquote|img|hr|sup|sub|ref)\b[^>]*>"
+    )
+    .unwrap();
+    static ref RE_HTML_OCULTO: Regex = Regex::new(
+        r"(?is)<!--.*?-->|<(?:script|style|head)\b.*?</(?:script|style|head)>|<ref\b[^>]*/>|<ref\b[^>]*>.*?</ref>"
+    )
+    .unwrap();
+    static ref RE_HTML_BLOQUE: Regex = Regex::new(
+        r"(?i)</?(?:p|div|br|h[1-6]|li|tr|blockquote|pre|table|ul|ol|hr|section|article|header|footer)\b[^>]*>"
+    )
+    .unwrap();
+    static ref RE_HTML_CELDA: Regex = Regex::new(r"(?i)</t[dh]>").unwrap();
+    static ref RE_HTML_ETIQUETA: Regex = Regex::new(r"<[^>]+>").unwrap();
+    static ref RE_ENTIDAD: Regex = Regex::new(r"&(#\d+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+}
+
+/// Quita las etiquetas: los bloques (párrafos, títulos, `<br>`, filas)
+/// separan párrafos; las celdas se separan con comas; el contenido de
+/// `<script>`, `<style>`, comentarios y `<ref>` se descarta.
+fn limpiar_html(texto: &str) -> String {
+    let texto = RE_HTML_OCULTO.replace_all(texto, "");
+    let texto = RE_HTML_CELDA.replace_all(&texto, ", ");
+    let texto = RE_HTML_BLOQUE.replace_all(&texto, "\n\n");
+    RE_HTML_ETIQUETA.replace_all(&texto, "").into_owned()
+}
+
+/// `&amp;`, `&nbsp;`, `&#233;`… Las entidades desconocidas se dejan igual.
+fn decodificar_entidades(texto: &str) -> String {
+    RE_ENTIDAD
+        .replace_all(texto, |caps: &Captures| {
+            let nombre = &caps[1];
+            let caracter = if let Some(hex) = nombre
+                .strip_prefix("#x")
+                .or_else(|| nombre.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(decimal) = nombre.strip_prefix('#') {
+                decimal.parse().ok().and_then(char::from_u32)
+            } else {
+                match nombre {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    "ndash" => Some('–'),
+                    "mdash" => Some('—'),
+                    "hellip" => Some('…'),
+                    "laquo" => Some('«'),
+                    "raquo" => Some('»'),
+                    "lsquo" => Some('‘'),
+                    "rsquo" => Some('’'),
+                    "ldquo" => Some('“'),
+                    "rdquo" => Some('”'),
+                    _ => None,
+                }
+            };
+            caracter.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// WIKITEXTO
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    static ref RE_WIKI_PLANTILLA: Regex = Regex::new(r"\{\{[^{}]*\}\}").unwrap();
+    static ref RE_WIKI_ENLACE: Regex = Regex::new(r"\[\[(?:[^\]|]*\|)?([^\]|]*)\]\]").unwrap();
+    static ref RE_WIKI_EXTERNO: Regex = Regex::new(r"\[(?:https?://|www\.)\S+ ([^\]]+)\]").unwrap();
+    static ref RE_WIKI_ENFASIS: Regex = Regex::new(r"'{2,5}").unwrap();
+    static ref RE_WIKI_TITULO: Regex = Regex::new(r"(?m)^=+\s*(.*?)\s*=+[ \t]*$").unwrap();
+}
+
+/// Máximo de niveles de plantillas anidadas (`{{a|{{b}}}}`) que se quitan.
+const MAX_ANIDAMIENTO_PLANTILLAS: usize = 5;
+
+/// `[[destino|texto]]` → "texto", `'''negrita'''` → "negrita", `== Título ==`
+/// como párrafo y las plantillas (`{{...}}`) fuera.
+fn limpiar_wiki(texto: &str) -> String {
+    let mut texto = texto.to_string();
+    for _ in 0..MAX_ANIDAMIENTO_PLANTILLAS {
+        let sin_plantillas = RE_WIKI_PLANTILLA.replace_all(&texto, "").into_owned();
+        if sin_plantillas == texto {
+            break;
+        }
+        texto = sin_plantillas;
+    }
+    let texto = RE_WIKI_ENLACE.replace_all(&texto, "$1");
+    let texto = RE_WIKI_EXTERNO.replace_all(&texto, "$1");
+    let texto = RE_WIKI_ENFASIS.replace_all(&texto, "");
+    RE_WIKI_TITULO
+        .replace_all(&texto, "\n\n$1\n\n")
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MARKDOWN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    static ref RE_MD_VALLA: Regex = Regex::new(r"^\s*(?:```|~~~)").unwrap();
+    static ref RE_MD_TITULO: Regex = Regex::new(r"^\s{0,3}#{1,6}\s+(.*?)(?:\s+#+)?\s*$").unwrap();
+    static ref RE_MD_REGLA: Regex = Regex::new(r"^\s*(?:(?:[-*_]\s*){3,}|=+\s*)$").unwrap();
+    static ref RE_MD_LISTA: Regex =
+        Regex::new(r"^\s*(?:[-*+•]|\d{1,3}[.)])\s+(?:\[[ xX]\]\s+)?(.*)$").unwrap();
+    static ref RE_MD_CITA: Regex = Regex::new(r"^\s*(?:>\s?)+").unwrap();
+    static ref RE_MD_SEPARADOR_TABLA: Regex = Regex::new(r"^\s*\|?\s*:?-{3,}").unwrap();
+    static ref RE_MD_DEFINICION: Regex = Regex::new(r"^\s*\[[^\]]+\]:\s+\S+").unwrap();
+    static ref RE_MD_IMAGEN: Regex = Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap();
+    static ref RE_MD_ENLACE: Regex = Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap();
+    static ref RE_MD_ENLACE_REFERENCIA: Regex = Regex::new(r"\[([^\]]+)\]\[[^\]]*\]").unwrap();
+    static ref RE_MD_AUTOENLACE: Regex = Regex::new(r"<((?:https?://|www\.)[^>\s]+)>").unwrap();
+    static ref RE_MD_CODIGO: Regex = Regex::new(r"`+([^`]+)`+").unwrap();
+    static ref RE_MD_NEGRITA: Regex = Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap();
+    static ref RE_MD_CURSIVA: Regex = Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*").unwrap();
+    /// Guiones bajos solo entre separadores, para no tocar `snake_case`
+    static ref RE_MD_CURSIVA_GUION: Regex =
+        Regex::new(r"(^|[^\w])_([^_\s](?:[^_]*[^_\s])?)_([^\w]|$)").unwrap();
+    static ref RE_MD_TACHADO: Regex = Regex::new(r"~~([^~]+)~~").unwrap();
+}
+
+/// Estructura de línea: títulos, listas, citas, tablas, reglas y vallas de
+/// código. Los títulos, elementos de lista y filas quedan como párrafos.
+fn limpiar_lineas_markdown(texto: &str) -> String {
+    let mut resultado = String::with_capacity(texto.len());
+    for linea in texto.lines() {
+        // Antes que las tablas: "---" también empieza como un separador
+        if RE_MD_REGLA.is_match(linea) {
+            resultado.push_str("\n\n");
+            continue;
+        }
+        if RE_MD_VALLA.is_match(linea)
+            || RE_MD_SEPARADOR_TABLA.is_match(linea)
+            || RE_MD_DEFINICION.is_match(linea)
+        {
+            continue;
+        }
+
+        let linea = RE_MD_CITA.replace(linea, "");
+        if let Some(caps) = RE_MD_TITULO.captures(&linea) {
+            parrafo_propio(&mut resultado, &caps[1]);
+        } else if let Some(caps) = RE_MD_LISTA.captures(&linea) {
+            parrafo_propio(&mut resultado, &caps[1]);
+        } else if linea.trim_start().starts_with('|') {
+            let celdas: Vec<&str> = linea
+                .split('|')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .collect();
+            parrafo_propio(&mut resultado, &celdas.join(", "));
+        } else {
+            resultado.push_str(&linea);
+            resultado.push('\n');
+        }
+    }
+    resultado
+}
+
+/// Añade `contenido` como un párrafo aparte.
+fn parrafo_propio(resultado: &mut String, contenido: &str) {
+    resultado.push_str("\n\n");
+    resultado.push_str(contenido.trim());
+    resultado.push_str("\n\n");
+}
+
+/// Marca el sitio de un fragmento de código mientras se limpia el resto
+/// (carácter de uso privado, que no aparece en el texto copiado).
+const MARCA_CODIGO: char = '\u{E000}';
+
+lazy_static! {
+    static ref RE_MARCA_CODIGO: Regex = Regex::new("\u{E000}(\\d+)\u{E000}").unwrap();
+}
+
+/// Marcado dentro de la línea: enlaces, imágenes, código, énfasis. El
+/// código se aparta antes de quitar el énfasis, que no debe tocar sus
+/// asteriscos ni guiones bajos (`x*y*z`, `mi_variable_larga`).
+fn limpiar_en_linea(texto: &str) -> String {
+    let mut codigos: Vec<String> = Vec::new();
+    let texto = RE_MD_CODIGO.replace_all(texto, |caps: &Captures| {
+        codigos.push(caps[1].to_string());
+        format!("{}{}{}", MARCA_CODIGO, codigos.len() - 1, MARCA_CODIGO)
+    });
+
+    let texto = RE_MD_IMAGEN.replace_all(&texto, "$1");
+    let texto = RE_MD_ENLACE.replace_all(&texto, "$1");
+    let texto = RE_MD_ENLACE_REFERENCIA.replace_all(&texto, "$1");
+    let texto = RE_MD_AUTOENLACE.replace_all(&texto, "$1");
+    let texto = RE_MD_NEGRITA.replace_all(&texto, "$1$2");
+    let texto = RE_MD_CURSIVA.replace_all(&texto, "$1");
+    let texto = RE_MD_CURSIVA_GUION.replace_all(&texto, "$1$2$3");
+    let texto = RE_MD_TACHADO.replace_all(&texto, "$1");
+
+    RE_MARCA_CODIGO
+        .replace_all(&texto, |caps: &Captures| {
+            let indice: usize = caps[1].parse().unwrap_or(usize::MAX);
+            codigos.get(indice).cloned().unwrap_or_default()
+        })
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CITAS Y URLS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    /// Llamadas a notas de Wikipedia y similares: "[1]", "[2, 3]", "[a]",
+    /// "[nota 4]", "[cita requerida]"
+    static ref RE_CITA: Regex = Regex::new(
+        r"(?i)[ \t]*\[\s*(?:\d{1,3}(?:\s*[,–-]\s*\d{1,3})*|[a-z]|(?:nota|note|n\.)\s*\d+|cita requerida|se necesita cita|citation needed|clarification needed|\?)\s*\]"
+    )
+    .unwrap();
+    static ref RE_URL: Regex = Regex::new(r"\b(?:https?://|www\.)[^\s<>()\[\]]+").unwrap();
+}
+
+/// Signos que terminan la frase tras una URL y no forman parte de ella.
+const PUNTUACION_FINAL_URL: &[char] = &['.', ',', ';', ':', '!', '?', '"', '\''];
+
+/// Sustituye cada URL por su dominio: "https://github.com/org/repo" →
+/// "github.com".
+fn abreviar_urls(texto: &str) -> String {
+    RE_URL
+        .replace_all(texto, |caps: &Captures| {
+            let url = &caps[0];
+            let sin_puntuacion = url.trim_end_matches(PUNTUACION_FINAL_URL);
+            let puntuacion = &url[sin_puntuacion.len()..];
+            let sin_esquema = sin_puntuacion
+                .split_once("://")
+                .map_or(sin_puntuacion, |(_, resto)| resto);
+            let host = sin_esquema
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or(sin_esquema);
+            let host = host.rsplit('@').next().unwrap_or(host);
+            let host = host.split(':').next().unwrap_or(host);
+            let host = host.strip_prefix("www.").unwrap_or(host);
+            format!("{}{}", host, puntuacion)
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Párrafos del resultado, sin líneas vacías ni espacios sobrantes.
+    fn parrafos(texto: &str) -> Vec<String> {
+        limpiar_marcado(texto)
+            .split("\n\n")
+            .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn markdown() {
+        let texto = "# Instalación\n\nEjecuta **cargo build** y lee [la guía](https://x.dev/guia).\n\n- Primer paso\n- Segundo `paso`\n\n> Nota: usa _siempre_ la versión estable.";
+        assert_eq!(
+            parrafos(texto),
+            [
+                "Instalación",
+                "Ejecuta cargo build y lee la guía.",
+                "Primer paso",
+                "Segundo paso",
+                "Nota: usa siempre la versión estable."
+            ]
+        );
+        // Los guiones bajos de los identificadores no son cursiva
+        assert_eq!(parrafos("usa mi_variable_local"), ["usa mi_variable_local"]);
+        // Ni los asteriscos del código
+        assert_eq!(
+            parrafos("Calcula `x*y*z` y **después** `a_b_` o *esto*"),
+            ["Calcula x*y*z y después a_b_ o esto"]
+        );
+        assert_eq!(
+            parrafos("Lee [`config.toml`](https://x.dev) y `**no**`"),
+            ["Lee config.toml y **no**"]
+        );
+    }
+
+    #[test]
+    fn tablas_markdown() {
+        let texto = "| Clave | Valor |\n|-------|-------|\n| volumen | 1.0 |";
+        assert_eq!(parrafos(texto), ["Clave, Valor", "volumen, 1.0"]);
+    }
+
+    #[test]
+    fn html() {
+        let texto = "<h2>Título</h2><p>Uno &amp; dos<br>tres</p><script>x()</script><!-- nada -->";
+        assert_eq!(parrafos(texto), ["Título", "Uno & dos", "tres"]);
+        // Sin etiquetas conocidas no es HTML
+        assert_eq!(parrafos("si a < b y c > d"), ["si a < b y c > d"]);
+    }
+
+    #[test]
+    fn wiki_y_citas() {
+        let texto = "== Historia ==\nFue fundada por '''[[Juan Pérez|Pérez]]''' en 1900.[1]{{cita}} Más tarde creció.[2][cita requerida]";
+        assert_eq!(
+            parrafos(texto),
+            [
+                "Historia",
+                "Fue fundada por Pérez en 1900. Más tarde creció."
+            ]
+        );
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            parrafos("Está en https://github.com/org/repo/issues/3."),
+            ["Está en github.com."]
+        );
+        assert_eq!(parrafos("Ver www.ejemplo.es/ruta?a=1"), ["Ver ejemplo.es"]);
+    }
+}
@@ -0,0 +1,1144 @@
+//! Normalización del texto antes de sintetizarlo.
+//!
+//! Piper lee mal muchas cifras y abreviaturas ("3.5 %", "12/03/2026",
+//! "1.000.000 €", "Sr."). Estas reglas las convierten en palabras según el
+//! idioma del texto. Se aplican en orden, de las más específicas a la más
+//! general (los números sueltos), y cada una se puede desactivar en
+//! `[normalizacion]`.
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use super::numbers::{self, Concordancia};
+use crate::config::Normalizacion;
+
+/// Idiomas con reglas de normalización.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idioma {
+    Es,
+    En,
+}
+
+impl Idioma {
+    /// Idioma a partir de su código ISO 639-1 (`None` si no tiene reglas).
+    pub fn desde_codigo(codigo: &str) -> Option<Self> {
+        match codigo {
+            "es" => Some(Idioma::Es),
+            "en" => Some(Idioma::En),
+            _ => None,
+        }
+    }
+}
+
+/// Regla: transforma el texto completo en un idioma.
+type Regla = fn(&str, Idioma) -> String;
+
+/// Aplica las reglas activas de `reglas` al texto.
+pub fn normalizar(texto: &str, idioma: Idioma, reglas: &Normalizacion) -> String {
+    let pasos: [(bool, Regla); 9] = [
+        (reglas.abreviaturas, abreviaturas),
+        (reglas.fechas, fechas),
+        (reglas.horas, horas),
+        (reglas.monedas, monedas),
+        (reglas.porcentajes, porcentajes),
+        (reglas.unidades, unidades),
+        (reglas.ordinales, ordinales),
+        (reglas.romanos, romanos),
+        (reglas.numeros, numeros),
+    ];
+
+    let mut texto = marcar_negativos(texto);
+    for (activa, regla) in pasos {
+        if activa {
+            texto = regla(&texto, idioma);
+        }
+    }
+    texto
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LECTURA DE NÚMEROS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Signo menos (U+2212): marca los números negativos para no confundirlos
+/// con guiones ("10-20", "COVID-19").
+const MENOS: char = '−';
+
+/// Número con separadores de miles o decimales ("1.000,5", "−3").
+const NUMERO: &str = r"−?\d+(?:[.,]\d+)*";
+
+lazy_static! {
+    static ref RE_NEGATIVO: Regex = Regex::new(r"(^|[\s(\[])-(\d)").unwrap();
+}
+
+/// Cambia por `MENOS` el guion de los números negativos: solo al principio o
+/// tras un espacio o un paréntesis.
+fn marcar_negativos(texto: &str) -> String {
+    RE_NEGATIVO
+        .replace_all(texto, format!("${{1}}{}${{2}}", MENOS))
+        .into_owned()
+}
+
+/// Número leído del texto.
+#[derive(Debug, PartialEq)]
+struct Numero {
+    negativo: bool,
+    entero: u64,
+    /// Cifras tras el separador decimal, tal cual ("05")
+    decimales: Option<String>,
+}
+
+/// Interpreta los separadores según el idioma: en español "." separa miles
+/// y "," decimales; en inglés al revés. Un separador de miles que no agrupa
+/// de tres en tres ("3.5" en español) se toma como decimal. Se aceptan
+/// también los miles del otro idioma si agrupan sin ambigüedad
+/// ("1,000,000" en español). Devuelve `None`
+/// si no es un número ("1.2.3") o es demasiado grande.
+fn parsear(texto: &str, idioma: Idioma) -> Option<Numero> {
+    let (negativo, texto) = match texto.strip_prefix(MENOS) {
+        Some(resto) => (true, resto),
+        None => (false, texto),
+    };
+    let (miles, decimal) = match idioma {
+        Idioma::Es => ('.', ','),
+        Idioma::En => (',', '.'),
+    };
+
+    // Miles con el separador del otro idioma ("1,000,000" en un texto en
+    // español): solo si hay más de un grupo de tres, que no puede ser decimal
+    let grupos_ajenos: Vec<&str> = texto.split(decimal).collect();
+    if grupos_ajenos.len() > 2
+        && grupos_ajenos[0].len() <= 3
+        && grupos_ajenos[1..].iter().all(|g| g.len() == 3)
+        && !texto.contains(miles)
+    {
+        return parsear(&grupos_ajenos.join(&miles.to_string()), idioma)
+            .map(|numero| Numero { negativo, ..numero });
+    }
+
+    let (entero, decimales) = match texto.split_once(decimal) {
+        Some((entero, decimales)) => (entero, Some(decimales)),
+        None => match texto.split_once(miles) {
+            // Un único separador que no agrupa miles: decimal
+            Some((entero, resto)) if !resto.contains(miles) && resto.len() != 3 => {
+                (entero, Some(resto))
+            }
+            _ => (texto, None),
+        },
+    };
+
+    let grupos: Vec<&str> = entero.split(miles).collect();
+    let agrupado =
+        grupos.len() == 1 || (grupos[0].len() <= 3 && grupos[1..].iter().all(|g| g.len() == 3));
+    let decimales_validos = decimales.map_or(true, |d| {
+        !d.is_empty() && d.chars().all(|c| c.is_ascii_digit())
+    });
+    if !agrupado || !decimales_validos || grupos.iter().any(|g| g.is_empty()) {
+        return None;
+    }
+
+    let entero: u64 = grupos.concat().parse().ok()?;
+    (entero <= numbers::MAXIMO_CARDINAL).then(|| Numero {
+        negativo,
+        entero,
+        decimales: decimales.map(str::to_string),
+    })
+}
+
+fn cardinal(n: u64, idioma: Idioma, concordancia: Concordancia) -> String {
+    match idioma {
+        Idioma::Es => numbers::cardinal_es(n, concordancia),
+        Idioma::En => numbers::cardinal_en(n),
+    }
+}
+
+/// Cifras sueltas: "cero cero siete".
+fn cifras(texto: &str, idioma: Idioma) -> String {
+    texto
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| cardinal(u64::from(d), idioma, Concordancia::Neutra))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parte decimal: en español como número ("coma cero cinco", "coma catorce")
+/// si es corta; en inglés siempre cifra a cifra ("point one four").
+fn leer_decimales(decimales: &str, idioma: Idioma) -> String {
+    let sin_ceros = decimales.trim_start_matches('0');
+    let ceros = decimales.len() - sin_ceros.len();
+    match idioma {
+        Idioma::Es if !sin_ceros.is_empty() && sin_ceros.len() <= 3 => {
+            let mut partes = vec!["cero"; ceros].join(" ");
+            if !partes.is_empty() {
+                partes.push(' ');
+            }
+            partes.push_str(&numbers::cardinal_es(
+                sin_ceros.parse().unwrap_or(0),
+                Concordancia::Neutra,
+            ));
+            partes
+        }
+        _ => cifras(decimales, idioma),
+    }
+}
+
+/// Lee un número tal como aparece en el texto. Los que no se pueden
+/// interpretar como cantidad se leen por partes ("1.2.3": "uno punto dos
+/// punto tres") o cifra a cifra (ceros a la izquierda, números muy largos).
+fn leer_numero(texto: &str, idioma: Idioma, concordancia: Concordancia) -> String {
+    let sin_signo = texto.trim_start_matches(MENOS);
+    if sin_signo.len() > 1
+        && sin_signo.starts_with('0')
+        && sin_signo.chars().all(|c| c.is_ascii_digit())
+    {
+        return cifras(sin_signo, idioma);
+    }
+
+    let Some(numero) = parsear(texto, idioma) else {
+        if sin_signo.chars().all(|c| c.is_ascii_digit()) {
+            return cifras(sin_signo, idioma);
+        }
+        let punto = match idioma {
+            Idioma::Es => " punto ",
+            Idioma::En => " point ",
+        };
+        return sin_signo
+            .split(['.', ','])
+            .map(|parte| leer_numero(parte, idioma, Concordancia::Neutra))
+            .collect::<Vec<_>>()
+            .join(punto);
+    };
+
+    let mut lectura = String::new();
+    if numero.negativo {
+        lectura.push_str(match idioma {
+            Idioma::Es => "menos ",
+            Idioma::En => "minus ",
+        });
+    }
+    match &numero.decimales {
+        Some(decimales) => {
+            lectura.push_str(&cardinal(numero.entero, idioma, Concordancia::Neutra));
+            lectura.push_str(match idioma {
+                Idioma::Es => " coma ",
+                Idioma::En => " point ",
+            });
+            lectura.push_str(&leer_decimales(decimales, idioma));
+        }
+        None => lectura.push_str(&cardinal(numero.entero, idioma, concordancia)),
+    }
+    lectura
+}
+
+/// Concordancia para un número seguido de un sustantivo.
+fn concordancia(femenino: bool) -> Concordancia {
+    if femenino {
+        Concordancia::Femenina
+    } else {
+        Concordancia::Masculina
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ABREVIATURAS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const ABREVIATURAS_ES: &[(&str, &str)] = &[
+    (r"\bSr\.", "señor"),
+    (r"\bSra\.", "señora"),
+    (r"\bSrta\.", "señorita"),
+    (r"\bSres\.", "señores"),
+    (r"\bDr\.", "doctor"),
+    (r"\bDra\.", "doctora"),
+    (r"\bDña\.", "doña"),
+    (r"\bUd\.", "usted"),
+    (r"\bUds\.", "ustedes"),
+    (r"\bLic\.", "licenciado"),
+    (r"\bIng\.", "ingeniero"),
+    (r"\bProf\.", "profesor"),
+    (r"\bp\.\s?ej\.", "por ejemplo"),
+    (r"\betc\.", "etcétera"),
+    (r"\bpágs\.", "páginas"),
+    (r"\bpág\.", "página"),
+    (r"\bnúm\.", "número"),
+    (r"\bn\.?º", "número"),
+    (r"\bart\.", "artículo"),
+    (r"\bcap\.", "capítulo"),
+    (r"\bvol\.", "volumen"),
+    (r"\baprox\.", "aproximadamente"),
+    (r"\bAvda?\.", "avenida"),
+    (r"\btel\.", "teléfono"),
+    (r"\bmáx\.", "máximo"),
+    (r"\bmín\.", "mínimo"),
+    (r"\bvs\.", "contra"),
+    (r"\bEE\.\s?UU\.", "Estados Unidos"),
+    (r"\ba\.\s?C\.", "antes de Cristo"),
+    (r"\bd\.\s?C\.", "después de Cristo"),
+];
+
+const ABREVIATURAS_EN: &[(&str, &str)] = &[
+    (r"\bMr\.", "mister"),
+    (r"\bMrs\.", "missus"),
+    (r"\bMs\.", "miz"),
+    (r"\bDr\.", "doctor"),
+    (r"\bProf\.", "professor"),
+    (r"\bJr\.", "junior"),
+    (r"\bSr\.", "senior"),
+    (r"\betc\.", "et cetera"),
+    (r"\be\.\s?g\.", "for example"),
+    (r"\bi\.\s?e\.", "that is"),
+    (r"\bvs\.", "versus"),
+    (r"\bapprox\.", "approximately"),
+    (r"\bdept\.", "department"),
+    (r"\bNo\.\s?(\d)", "number $1"),
+];
+
+lazy_static! {
+    static ref RE_ABREVIATURAS_ES: Vec<(Regex, &'static str)> = compilar(ABREVIATURAS_ES);
+    static ref RE_ABREVIATURAS_EN: Vec<(Regex, &'static str)> = compilar(ABREVIATURAS_EN);
+}
+
+fn compilar(lista: &[(&str, &'static str)]) -> Vec<(Regex, &'static str)> {
+    lista
+        .iter()
+        .map(|(patron, sustitucion)| (Regex::new(patron).unwrap(), *sustitucion))
+        .collect()
+}
+
+/// "Sr." → "señor", "p. ej." → "por ejemplo", "e.g." → "for example".
+fn abreviaturas(texto: &str, idioma: Idioma) -> String {
+    let lista = match idioma {
+        Idioma::Es => &*RE_ABREVIATURAS_ES,
+        Idioma::En => &*RE_ABREVIATURAS_EN,
+    };
+    let mut texto = texto.to_string();
+    for (regex, sustitucion) in lista {
+        texto = regex.replace_all(&texto, *sustitucion).into_owned();
+    }
+    texto
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// FECHAS Y HORAS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const MESES_ES: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+
+const MESES_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+lazy_static! {
+    static ref RE_FECHA: Regex =
+        Regex::new(r"\b(\d{1,2})([/-])(\d{1,2})([/-])(\d{4}|\d{2})\b").unwrap();
+    static ref RE_FECHA_ISO: Regex = Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap();
+    static ref RE_HORA: Regex =
+        Regex::new(r"\b([01]?\d|2[0-3]):([0-5]\d)(?::([0-5]\d))?(?:\s?h\b)?").unwrap();
+}
+
+/// Fecha en palabras, o `None` si el día o el mes no son válidos.
+fn leer_fecha(dia: u64, mes: u64, anio: &str, idioma: Idioma) -> Option<String> {
+    if !(1..=12).contains(&mes) || !(1..=31).contains(&dia) {
+        return None;
+    }
+    let valor_anio: u64 = anio.parse().ok()?;
+    let mes = (mes - 1) as usize;
+
+    Some(match idioma {
+        // "12 de marzo de 2026", "12 de marzo del 26"
+        Idioma::Es => {
+            let dia = numbers::cardinal_es(dia, Concordancia::Neutra);
+            let anio_leido = numbers::cardinal_es(valor_anio, Concordancia::Neutra);
+            let de = if anio.len() == 2 { "del" } else { "de" };
+            format!("{} de {} {} {}", dia, MESES_ES[mes], de, anio_leido)
+        }
+        // "March twelfth, twenty twenty-six"
+        Idioma::En => format!(
+            "{} {}, {}",
+            MESES_EN[mes],
+            numbers::ordinal_en(dia),
+            numbers::anio_en(valor_anio)
+        ),
+    })
+}
+
+/// "12/03/2026" → "doce de marzo de dos mil veintiséis". En inglés se toma
+/// el orden estadounidense (mes/día) salvo que el primer número no pueda
+/// ser un mes. También fechas ISO ("2026-03-12").
+fn fechas(texto: &str, idioma: Idioma) -> String {
+    let texto = RE_FECHA_ISO.replace_all(texto, |caps: &Captures| {
+        let (anio, mes, dia) = (&caps[1], &caps[2], &caps[3]);
+        leer_fecha(
+            dia.parse().unwrap_or(0),
+            mes.parse().unwrap_or(0),
+            anio,
+            idioma,
+        )
+        .unwrap_or_else(|| caps[0].to_string())
+    });
+
+    RE_FECHA
+        .replace_all(&texto, |caps: &Captures| {
+            // Los dos separadores deben coincidir ("1/2-2026" no es fecha)
+            if caps[2] != caps[4] {
+                return caps[0].to_string();
+            }
+            let primero: u64 = caps[1].parse().unwrap_or(0);
+            let segundo: u64 = caps[3].parse().unwrap_or(0);
+            let (dia, mes) = match idioma {
+                Idioma::En if primero <= 12 => (segundo, primero),
+                _ => (primero, segundo),
+            };
+            // Si no es una fecha válida se leen los números sueltos, sin
+            // dejar las barras al paso de los signos
+            leer_fecha(dia, mes, &caps[5], idioma)
+                .unwrap_or_else(|| format!("{} {} {}", &caps[1], &caps[3], &caps[5]))
+        })
+        .into_owned()
+}
+
+/// "14:30" → "catorce y treinta", "9:05" → "nine oh five".
+fn horas(texto: &str, idioma: Idioma) -> String {
+    RE_HORA
+        .replace_all(texto, |caps: &Captures| {
+            let hora: u64 = caps[1].parse().unwrap_or(0);
+            let minutos: u64 = caps[2].parse().unwrap_or(0);
+            let segundos: Option<u64> = caps.get(3).and_then(|s| s.as_str().parse().ok());
+
+            match (idioma, segundos) {
+                (Idioma::Es, Some(segundos)) => format!(
+                    "{} horas, {} minutos y {} segundos",
+                    numbers::cardinal_es(hora, Concordancia::Femenina),
+                    numbers::cardinal_es(minutos, Concordancia::Masculina),
+                    numbers::cardinal_es(segundos, Concordancia::Masculina)
+                ),
+                (Idioma::Es, None) => {
+                    let hora = numbers::cardinal_es(hora, Concordancia::Femenina);
+                    match minutos {
+                        0 => format!("{} en punto", hora),
+                        _ => format!(
+                            "{} y {}",
+                            hora,
+                            numbers::cardinal_es(minutos, Concordancia::Neutra)
+                        ),
+                    }
+                }
+                (Idioma::En, Some(segundos)) => format!(
+                    "{} hours, {} minutes and {} seconds",
+                    numbers::cardinal_en(hora),
+                    numbers::cardinal_en(minutos),
+                    numbers::cardinal_en(segundos)
+                ),
+                (Idioma::En, None) => {
+                    let hora_leida = numbers::cardinal_en(hora);
+                    match minutos {
+                        0 if hora <= 12 => format!("{} o'clock", hora_leida),
+                        0 => format!("{} hundred", hora_leida),
+                        1..=9 => format!("{} oh {}", hora_leida, numbers::cardinal_en(minutos)),
+                        _ => format!("{} {}", hora_leida, numbers::cardinal_en(minutos)),
+                    }
+                }
+            }
+        })
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CANTIDADES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Moneda: símbolo o código ISO y sus nombres en singular y plural.
+struct Moneda {
+    simbolos: &'static [&'static str],
+    /// (singular, plural, femenino) en español
+    es: (&'static str, &'static str, bool),
+    /// Céntimos en español (singular, plural)
+    centimos_es: (&'static str, &'static str),
+    en: (&'static str, &'static str),
+    centimos_en: (&'static str, &'static str),
+}
+
+const MONEDAS: &[Moneda] = &[
+    Moneda {
+        simbolos: &["€", "EUR"],
+        es: ("euro", "euros", false),
+        centimos_es: ("céntimo", "céntimos"),
+        en: ("euro", "euros"),
+        centimos_en: ("cent", "cents"),
+    },
+    Moneda {
+        simbolos: &["$", "USD"],
+        es: ("dólar", "dólares", false),
+        centimos_es: ("centavo", "centavos"),
+        en: ("dollar", "dollars"),
+        centimos_en: ("cent", "cents"),
+    },
+    Moneda {
+        simbolos: &["£", "GBP"],
+        es: ("libra", "libras", true),
+        centimos_es: ("penique", "peniques"),
+        en: ("pound", "pounds"),
+        centimos_en: ("penny", "pence"),
+    },
+];
+
+lazy_static! {
+    static ref RE_MONEDA_ANTES: Regex = Regex::new(&format!(r"([€$£])\s?({})", NUMERO)).unwrap();
+    static ref RE_MONEDA_DESPUES: Regex =
+        Regex::new(&format!(r"({})\s?(€|\$|£|\bEUR\b|\bUSD\b|\bGBP\b)", NUMERO)).unwrap();
+    static ref RE_PORCENTAJE: Regex = Regex::new(&format!(r"({})\s?%", NUMERO)).unwrap();
+}
+
+/// Importe en palabras: "un millón de euros", "three dollars and fifty
+/// cents". `None` si la cantidad no se puede interpretar.
+fn leer_importe(cantidad: &str, simbolo: &str, idioma: Idioma) -> Option<String> {
+    let moneda = MONEDAS.iter().find(|m| m.simbolos.contains(&simbolo))?;
+    let numero = parsear(cantidad, idioma)?;
+
+    // Céntimos: una o dos cifras ("3,5 €" son 3 euros con 50)
+    let centimos = match numero.decimales.as_deref() {
+        None => 0,
+        Some(d) if d.len() == 1 => d.parse::<u64>().ok()? * 10,
+        Some(d) if d.len() == 2 => d.parse().ok()?,
+        Some(_) => {
+            let plural = match idioma {
+                Idioma::Es => moneda.es.1,
+                Idioma::En => moneda.en.1,
+            };
+            return Some(format!(
+                "{} {}",
+                leer_numero(cantidad, idioma, Concordancia::Neutra),
+                plural
+            ));
+        }
+    };
+
+    let menos = match (numero.negativo, idioma) {
+        (false, _) => "",
+        (true, Idioma::Es) => "menos ",
+        (true, Idioma::En) => "minus ",
+    };
+    let forma = |n: u64, (singular, plural): (&'static str, &'static str)| {
+        if n == 1 {
+            singular
+        } else {
+            plural
+        }
+    };
+
+    let lectura = match idioma {
+        Idioma::Es => {
+            let (singular, plural, femenino) = moneda.es;
+            let entero = format!(
+                "{}{}",
+                numbers::cardinal_es(numero.entero, concordancia(femenino)),
+                // "un millón de euros"
+                if numero.entero >= 1_000_000 && numero.entero % 1_000_000 == 0 {
+                    " de"
+                } else {
+                    ""
+                }
+            );
+            let entero = format!("{} {}", entero, forma(numero.entero, (singular, plural)));
+            let centimos_leidos = format!(
+                "{} {}",
+                numbers::cardinal_es(centimos, Concordancia::Masculina),
+                forma(centimos, moneda.centimos_es)
+            );
+            match (numero.entero, centimos) {
+                (_, 0) => entero,
+                (0, _) => centimos_leidos,
+                _ => format!("{} con {}", entero, centimos_leidos),
+            }
+        }
+        Idioma::En => {
+            let entero = format!(
+                "{} {}",
+                numbers::cardinal_en(numero.entero),
+                forma(numero.entero, moneda.en)
+            );
+            let centimos_leidos = format!(
+                "{} {}",
+                numbers::cardinal_en(centimos),
+                forma(centimos, moneda.centimos_en)
+            );
+            match (numero.entero, centimos) {
+                (_, 0) => entero,
+                (0, _) => centimos_leidos,
+                _ => format!("{} and {}", entero, centimos_leidos),
+            }
+        }
+    };
+    Some(format!("{}{}", menos, lectura))
+}
+
+/// "1.000.000 €" → "un millón de euros", "$3.50" → "three dollars and fifty
+/// cents".
+fn monedas(texto: &str, idioma: Idioma) -> String {
+    let texto = RE_MONEDA_ANTES.replace_all(texto, |caps: &Captures| {
+        leer_importe(&caps[2], &caps[1], idioma).unwrap_or_else(|| caps[0].to_string())
+    });
+    RE_MONEDA_DESPUES
+        .replace_all(&texto, |caps: &Captures| {
+            leer_importe(&caps[1], &caps[2], idioma).unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// "3,5 %" → "tres coma cinco por ciento".
+fn porcentajes(texto: &str, idioma: Idioma) -> String {
+    RE_PORCENTAJE
+        .replace_all(texto, |caps: &Captures| {
+            let numero = leer_numero(&caps[1], idioma, Concordancia::Neutra);
+            match idioma {
+                Idioma::Es => format!("{} por ciento", numero),
+                Idioma::En => format!("{} percent", numero),
+            }
+        })
+        .into_owned()
+}
+
+/// Unidad de medida: símbolo, nombres en español (singular, plural,
+/// femenino) y en inglés (singular, plural).
+type Unidad = (
+    &'static str,
+    (&'static str, &'static str, bool),
+    (&'static str, &'static str),
+);
+
+const UNIDADES: &[Unidad] = &[
+    (
+        "km/h",
+        ("kilómetro por hora", "kilómetros por hora", false),
+        ("kilometer per hour", "kilometers per hour"),
+    ),
+    (
+        "m/s",
+        ("metro por segundo", "metros por segundo", false),
+        ("meter per second", "meters per second"),
+    ),
+    (
+        "km",
+        ("kilómetro", "kilómetros", false),
+        ("kilometer", "kilometers"),
+    ),
+    (
+        "cm",
+        ("centímetro", "centímetros", false),
+        ("centimeter", "centimeters"),
+    ),
+    (
+        "mm",
+        ("milímetro", "milímetros", false),
+        ("millimeter", "millimeters"),
+    ),
+    ("m", ("metro", "metros", false), ("meter", "meters")),
+    (
+        "kg",
+        ("kilogramo", "kilogramos", false),
+        ("kilogram", "kilograms"),
+    ),
+    (
+        "mg",
+        ("miligramo", "miligramos", false),
+        ("milligram", "milligrams"),
+    ),
+    ("g", ("gramo", "gramos", false), ("gram", "grams")),
+    (
+        "ml",
+        ("mililitro", "mililitros", false),
+        ("milliliter", "milliliters"),
+    ),
+    ("l", ("litro", "litros", false), ("liter", "liters")),
+    ("L", ("litro", "litros", false), ("liter", "liters")),
+    (
+        "°C",
+        ("grado Celsius", "grados Celsius", false),
+        ("degree Celsius", "degrees Celsius"),
+    ),
+    (
+        "ºC",
+        ("grado Celsius", "grados Celsius", false),
+        ("degree Celsius", "degrees Celsius"),
+    ),
+    (
+        "°F",
+        ("grado Fahrenheit", "grados Fahrenheit", false),
+        ("degree Fahrenheit", "degrees Fahrenheit"),
+    ),
+    (
+        "KB",
+        ("kilobyte", "kilobytes", false),
+        ("kilobyte", "kilobytes"),
+    ),
+    (
+        "MB",
+        ("megabyte", "megabytes", false),
+        ("megabyte", "megabytes"),
+    ),
+    (
+        "GB",
+        ("gigabyte", "gigabytes", false),
+        ("gigabyte", "gigabytes"),
+    ),
+    (
+        "TB",
+        ("terabyte", "terabytes", false),
+        ("terabyte", "terabytes"),
+    ),
+    (
+        "ms",
+        ("milisegundo", "milisegundos", false),
+        ("millisecond", "milliseconds"),
+    ),
+    ("s", ("segundo", "segundos", false), ("second", "seconds")),
+    ("min", ("minuto", "minutos", false), ("minute", "minutes")),
+    ("h", ("hora", "horas", true), ("hour", "hours")),
+    (
+        "kW",
+        ("kilovatio", "kilovatios", false),
+        ("kilowatt", "kilowatts"),
+    ),
+    ("W", ("vatio", "vatios", false), ("watt", "watts")),
+    (
+        "GHz",
+        ("gigahercio", "gigahercios", false),
+        ("gigahertz", "gigahertz"),
+    ),
+    (
+        "MHz",
+        ("megahercio", "megahercios", false),
+        ("megahertz", "megahertz"),
+    ),
+    (
+        "kHz",
+        ("kilohercio", "kilohercios", false),
+        ("kilohertz", "kilohertz"),
+    ),
+    ("Hz", ("hercio", "hercios", false), ("hertz", "hertz")),
+];
+
+lazy_static! {
+    static ref RE_UNIDAD: Regex = {
+        // Los símbolos más largos primero: "km/h" antes que "km" y "m"
+        let mut simbolos: Vec<&str> = UNIDADES.iter().map(|(s, _, _)| *s).collect();
+        simbolos.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        let alternativas: Vec<String> = simbolos.iter().map(|s| regex::escape(s)).collect();
+        Regex::new(&format!(r"({})\s?({})\b", NUMERO, alternativas.join("|"))).unwrap()
+    };
+}
+
+/// "5 km" → "cinco kilómetros", "1 h" → "una hora".
+fn unidades(texto: &str, idioma: Idioma) -> String {
+    RE_UNIDAD
+        .replace_all(texto, |caps: &Captures| {
+            let Some((_, es, en)) = UNIDADES.iter().find(|(s, _, _)| *s == &caps[2]) else {
+                return caps[0].to_string();
+            };
+            let singular = parsear(&caps[1], idioma)
+                .is_some_and(|n| n.entero == 1 && n.decimales.is_none() && !n.negativo);
+            let (nombre, concordancia_numero) = match idioma {
+                Idioma::Es => (if singular { es.0 } else { es.1 }, concordancia(es.2)),
+                Idioma::En => (if singular { en.0 } else { en.1 }, Concordancia::Neutra),
+            };
+            format!(
+                "{} {}",
+                leer_numero(&caps[1], idioma, concordancia_numero),
+                nombre
+            )
+        })
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ORDINALES Y NÚMEROS ROMANOS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    static ref RE_ORDINAL_ES: Regex =
+        Regex::new(r"\b(\d{1,3})(?:\.?([ºª])|(er|ra|ro|do|da)\b)").unwrap();
+    static ref RE_ORDINAL_EN: Regex =
+        Regex::new(r"\b(\d{1,3})(?:(st|nd|rd|th)\b|\.?[ºª])").unwrap();
+    static ref RE_ROMANO: Regex = Regex::new(r"\b(\p{L}+)(\s+)([IVXLCDM]+)\b").unwrap();
+}
+
+/// Palabras tras las que el ordinal masculino no se apocopa ("el 1.º de
+/// la lista": "primero", no "primer").
+const NO_APOCOPE_ES: &[&str] = &[
+    "a", "al", "con", "de", "del", "e", "en", "o", "para", "por", "u", "y",
+];
+
+/// "1º" → "primero", "3.º puesto" → "tercer puesto", "2ª" → "segunda",
+/// "1er" → "primer", "21st" → "twenty-first".
+fn ordinales(texto: &str, idioma: Idioma) -> String {
+    match idioma {
+        Idioma::Es => RE_ORDINAL_ES.replace_all(texto, |caps: &Captures| {
+            let n: u64 = caps[1].parse().unwrap_or(0);
+            let sufijo = caps
+                .get(2)
+                .or_else(|| caps.get(3))
+                .map_or("", |s| s.as_str());
+            let femenino = matches!(sufijo, "ª" | "ra" | "da");
+            // Ante un sustantivo, "primer"/"tercer"
+            let fin = caps.get(0).map_or(0, |m| m.end());
+            let ante_sustantivo = texto[fin..].strip_prefix(' ').is_some_and(|resto| {
+                let palabra: String = resto.chars().take_while(|c| c.is_alphabetic()).collect();
+                !palabra.is_empty() && !NO_APOCOPE_ES.contains(&palabra.to_lowercase().as_str())
+            });
+            let apocope = sufijo == "er" || (!femenino && ante_sustantivo);
+            numbers::ordinal_es(n, femenino, apocope)
+        }),
+        Idioma::En => RE_ORDINAL_EN.replace_all(texto, |caps: &Captures| {
+            numbers::ordinal_en(caps[1].parse().unwrap_or(0))
+        }),
+    }
+    .into_owned()
+}
+
+/// Palabras tras las que un número romano se lee como cardinal.
+const PALABRAS_ROMANO_ES: &[&str] = &[
+    "siglo",
+    "siglos",
+    "capítulo",
+    "tomo",
+    "volumen",
+    "parte",
+    "acto",
+    "libro",
+    "título",
+    "canto",
+    "escena",
+    "fase",
+    "nivel",
+];
+
+const PALABRAS_ROMANO_EN: &[&str] = &[
+    "chapter", "volume", "part", "act", "book", "section", "war", "phase", "level", "scene",
+];
+
+/// Mayor número romano que se lee tras un nombre propio ("Juan XXIII"): así
+/// no se confunden siglas como "CD" o "XL" con números.
+const MAXIMO_ROMANO_NOMBRE: u64 = 30;
+
+/// "siglo XXI" → "siglo veintiuno", "Felipe II" → "Felipe segundo", "Henry
+/// VIII" → "Henry the eighth". Solo tras una palabra clave o un nombre
+/// propio, para no tocar siglas ni el pronombre inglés "I".
+fn romanos(texto: &str, idioma: Idioma) -> String {
+    RE_ROMANO
+        .replace_all(texto, |caps: &Captures| {
+            let (palabra, espacio, romano) = (&caps[1], &caps[2], &caps[3]);
+            let sin_cambios = caps[0].to_string();
+            let Some(valor) = numbers::valor_romano(romano) else {
+                return sin_cambios;
+            };
+
+            let clave = palabra.to_lowercase();
+            let palabras_clave = match idioma {
+                Idioma::Es => PALABRAS_ROMANO_ES,
+                Idioma::En => PALABRAS_ROMANO_EN,
+            };
+            let lectura = if palabras_clave.contains(&clave.as_str()) {
+                cardinal(valor, idioma, Concordancia::Neutra)
+            } else {
+                let mut letras = palabra.chars();
+                let nombre_propio = letras.next().is_some_and(char::is_uppercase)
+                    && letras.clone().next().is_some()
+                    && letras.all(char::is_lowercase);
+                if !nombre_propio
+                    || valor > MAXIMO_ROMANO_NOMBRE
+                    || (idioma == Idioma::En && romano == "I")
+                {
+                    return sin_cambios;
+                }
+                match idioma {
+                    // Los reyes y papas se leen con ordinal hasta el décimo
+                    Idioma::Es if valor <= 10 => numbers::ordinal_es(valor, false, false),
+                    Idioma::Es => numbers::cardinal_es(valor, Concordancia::Neutra),
+                    Idioma::En => format!("the {}", numbers::ordinal_en(valor)),
+                }
+            };
+            format!("{}{}{}", palabra, espacio, lectura)
+        })
+        .into_owned()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// NÚMEROS SUELTOS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+lazy_static! {
+    static ref RE_NUMERO: Regex = Regex::new(NUMERO).unwrap();
+    static ref RE_SERIE_CIFRAS: Regex = Regex::new(r"\b\d+(?:-\d+)+\b").unwrap();
+}
+
+/// Tramos de cifras unidas por guiones que no son un intervalo ni un número
+/// suelto ("555-1234", "91-555-12-34"): teléfonos, referencias. Se dejan
+/// tal cual; "10-20" o "1990-2000" sí se leen.
+fn series_de_cifras(texto: &str) -> Vec<std::ops::Range<usize>> {
+    RE_SERIE_CIFRAS
+        .find_iter(texto)
+        .filter(|serie| {
+            let grupos: Vec<usize> = serie.as_str().split('-').map(str::len).collect();
+            grupos.len() > 2 || (grupos.iter().all(|&g| g >= 3) && grupos[0] != grupos[1])
+        })
+        .map(|serie| serie.range())
+        .collect()
+}
+
+/// Años en inglés que se leen como tales ("in 1999": "nineteen ninety-nine").
+const ANIOS_EN: std::ops::RangeInclusive<u64> = 1100..=2099;
+
+/// Cualquier número que quede: "3.5" → "tres coma cinco", "1999" →
+/// "nineteen ninety-nine". Ante una palabra, en español se usa la forma
+/// "un"/"veintiún" ("1 archivo" → "un archivo").
+fn numeros(texto: &str, idioma: Idioma) -> String {
+    let series = series_de_cifras(texto);
+    RE_NUMERO
+        .replace_all(texto, |caps: &Captures| {
+            let coincidencia = caps.get(0).map_or(0..0, |m| m.range());
+            let numero = &texto[coincidencia.clone()];
+            if series
+                .iter()
+                .any(|serie| serie.contains(&coincidencia.start))
+            {
+                return numero.to_string();
+            }
+            let antes = texto[..coincidencia.start].chars().next_back();
+            let despues = texto[coincidencia.end..].chars().next();
+            let ante_palabra = texto[coincidencia.end..]
+                .strip_prefix(' ')
+                .and_then(|resto| resto.chars().next())
+                .is_some_and(char::is_alphabetic);
+
+            let lectura = match idioma {
+                Idioma::En
+                    if numero.len() == 4 && numero.parse().is_ok_and(|n| ANIOS_EN.contains(&n)) =>
+                {
+                    numbers::anio_en(numero.parse().unwrap_or(0))
+                }
+                _ => {
+                    let concordancia = if ante_palabra {
+                        Concordancia::Masculina
+                    } else {
+                        Concordancia::Neutra
+                    };
+                    leer_numero(numero, idioma, concordancia)
+                }
+            };
+
+            // Separar de las letras pegadas ("mp3", "4K")
+            let mut resultado = String::new();
+            if antes.is_some_and(char::is_alphabetic) {
+                resultado.push(' ');
+            }
+            resultado.push_str(&lectura);
+            if despues.is_some_and(char::is_alphabetic) {
+                resultado.push(' ');
+            }
+            resultado
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn es(texto: &str) -> String {
+        normalizar(texto, Idioma::Es, &Normalizacion::default())
+    }
+
+    fn en(texto: &str) -> String {
+        normalizar(texto, Idioma::En, &Normalizacion::default())
+    }
+
+    #[test]
+    fn numeros_es() {
+        assert_eq!(es("Tengo 3 gatos"), "Tengo tres gatos");
+        assert_eq!(es("Llegaron 21 personas"), "Llegaron veintiún personas");
+        assert_eq!(es("Son 1.000.000"), "Son un millón");
+        assert_eq!(es("Vale 3,14"), "Vale tres coma catorce");
+        assert_eq!(es("Vale 3.5"), "Vale tres coma cinco");
+        assert_eq!(es("Hace -5 grados"), "Hace menos cinco grados");
+        assert_eq!(es("Páginas 10-20"), "Páginas diez-veinte");
+        assert_eq!(es("Agente 007"), "Agente cero cero siete");
+        assert_eq!(es("Versión 1.2.3"), "Versión uno punto dos punto tres");
+        assert_eq!(es("COVID-19"), "COVID-diecinueve");
+        assert_eq!(es("un mp3"), "un mp tres");
+        assert_eq!(es("Son 1,000,000 dólares"), "Son un millón dólares");
+        assert_eq!(es("Llama al 555-1234"), "Llama al 555-1234");
+        assert_eq!(
+            es("Los años 1990-2000"),
+            "Los años mil novecientos noventa-dos mil"
+        );
+    }
+
+    #[test]
+    fn numeros_en() {
+        assert_eq!(en("I have 3 cats"), "I have three cats");
+        assert_eq!(
+            en("About 1,500 users"),
+            "About one thousand five hundred users"
+        );
+        assert_eq!(en("Pi is 3.14"), "Pi is three point one four");
+        assert_eq!(en("Back in 1999"), "Back in nineteen ninety-nine");
+        assert_eq!(en("It costs 1.000.000 €"), "It costs one million euros");
+        assert_eq!(en("Call 555-1234"), "Call 555-1234");
+        assert_eq!(en("Call 91-555-12-34"), "Call 91-555-12-34");
+    }
+
+    #[test]
+    fn ordinales_es_en() {
+        assert_eq!(es("el 1º de la lista"), "el primero de la lista");
+        assert_eq!(es("la 2ª planta"), "la segunda planta");
+        assert_eq!(es("el 3.º puesto"), "el tercer puesto");
+        assert_eq!(es("el 3.º de la fila"), "el tercero de la fila");
+        assert_eq!(es("el 1er día"), "el primer día");
+        assert_eq!(en("the 21st century"), "the twenty-first century");
+        assert_eq!(en("on the 3rd floor"), "on the third floor");
+        assert_eq!(en("the 1º place"), "the first place");
+    }
+
+    #[test]
+    fn fechas_es_en() {
+        assert_eq!(
+            es("el 12/03/2026"),
+            "el doce de marzo de dos mil veintiséis"
+        );
+        assert_eq!(es("el 1-5-26"), "el uno de mayo del veintiséis");
+        assert_eq!(
+            es("el 2026-03-12"),
+            "el doce de marzo de dos mil veintiséis"
+        );
+        assert_eq!(en("on 03/12/2026"), "on March twelfth, twenty twenty-six");
+        assert_eq!(
+            en("on 25/12/2026"),
+            "on December twenty-fifth, twenty twenty-six"
+        );
+        // Mes imposible: no es una fecha
+        assert_eq!(es("el 12/13/2026"), "el doce trece dos mil veintiséis");
+        assert_eq!(es("el 10/20/2030"), "el diez veinte dos mil treinta");
+    }
+
+    #[test]
+    fn horas_es_en() {
+        assert_eq!(es("a las 14:30"), "a las catorce y treinta");
+        assert_eq!(es("a la 1:15 h"), "a la una y quince");
+        assert_eq!(es("a las 9:00"), "a las nueve en punto");
+        assert_eq!(
+            es("en 01:02:03"),
+            "en una horas, dos minutos y tres segundos"
+        );
+        assert_eq!(en("at 9:05"), "at nine oh five");
+        assert_eq!(en("at 10:00"), "at ten o'clock");
+        assert_eq!(en("at 14:30"), "at fourteen thirty");
+    }
+
+    #[test]
+    fn monedas_es_en() {
+        assert_eq!(es("cuesta 1.000.000 €"), "cuesta un millón de euros");
+        assert_eq!(es("cuesta 21 €"), "cuesta veintiún euros");
+        assert_eq!(es("cuesta 1 €"), "cuesta un euro");
+        assert_eq!(
+            es("cuesta 3,50 €"),
+            "cuesta tres euros con cincuenta céntimos"
+        );
+        assert_eq!(es("cuesta £21"), "cuesta veintiuna libras");
+        assert_eq!(es("cuesta 0,05 EUR"), "cuesta cinco céntimos");
+        assert_eq!(en("costs $3.50"), "costs three dollars and fifty cents");
+        assert_eq!(en("costs 1 USD"), "costs one dollar");
+    }
+
+    #[test]
+    fn porcentajes_es_en() {
+        assert_eq!(es("sube un 3.5 %"), "sube un tres coma cinco por ciento");
+        assert_eq!(es("el 100%"), "el cien por ciento");
+        assert_eq!(en("up 12.5%"), "up twelve point five percent");
+    }
+
+    #[test]
+    fn unidades_es_en() {
+        assert_eq!(es("a 5 km"), "a cinco kilómetros");
+        assert_eq!(es("pesa 1 kg"), "pesa un kilogramo");
+        assert_eq!(es("dura 1 h"), "dura una hora");
+        assert_eq!(es("a 120 km/h"), "a ciento veinte kilómetros por hora");
+        assert_eq!(es("hace 20 °C"), "hace veinte grados Celsius");
+        assert_eq!(es("ocupa 1,5 GB"), "ocupa uno coma cinco gigabytes");
+        // Sin número delante, la letra no es una unidad
+        assert_eq!(es("5 manzanas"), "cinco manzanas");
+        assert_eq!(en("a 5 km run"), "a five kilometers run");
+        assert_eq!(en("1 h"), "one hour");
+    }
+
+    #[test]
+    fn abreviaturas_es_en() {
+        assert_eq!(es("el Sr. García"), "el señor García");
+        assert_eq!(es("p. ej. esto"), "por ejemplo esto");
+        assert_eq!(es("libros, etc."), "libros, etcétera");
+        assert_eq!(es("la pág. 5"), "la página cinco");
+        assert_eq!(es("en EE. UU."), "en Estados Unidos");
+        assert_eq!(en("Mr. Smith"), "mister Smith");
+        assert_eq!(en("e.g. this"), "for example this");
+        assert_eq!(en("No. 5"), "number five");
+    }
+
+    #[test]
+    fn romanos_es_en() {
+        assert_eq!(es("el siglo XXI"), "el siglo veintiuno");
+        assert_eq!(es("Felipe II"), "Felipe segundo");
+        assert_eq!(es("Juan XXIII"), "Juan veintitrés");
+        assert_eq!(es("el capítulo IV"), "el capítulo cuatro");
+        // Siglas que parecen números romanos
+        assert_eq!(es("un CD"), "un CD");
+        assert_eq!(es("Talla XL"), "Talla XL");
+        assert_eq!(en("Henry VIII"), "Henry the eighth");
+        assert_eq!(en("World War II"), "World War two");
+        assert_eq!(en("Then I left"), "Then I left");
+    }
+
+    #[test]
+    fn reglas_desactivables() {
+        let sin_numeros = Normalizacion {
+            numeros: false,
+            ..Normalizacion::default()
+        };
+        assert_eq!(
+            normalizar("Son 3 gatos y 5 km", Idioma::Es, &sin_numeros),
+            "Son 3 gatos y cinco kilómetros"
+        );
+
+        let sin_abreviaturas = Normalizacion {
+            abreviaturas: false,
+            ..Normalizacion::default()
+        };
+        assert_eq!(
+            normalizar("el Sr. García", Idioma::Es, &sin_abreviaturas),
+            "el Sr. García"
+        );
+    }
+}
@@ -0,0 +1,516 @@
+//! Números en palabras, en español e inglés.
+
+/// Mayor número que se lee como cantidad; los más largos (teléfonos,
+/// identificadores) se leen cifra a cifra.
+pub const MAXIMO_CARDINAL: u64 = 999_999_999_999_999;
+
+/// Forma del "uno" final en español.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Concordancia {
+    /// "uno", "veintiuno": el número solo
+    Neutra,
+    /// "un", "veintiún": ante un sustantivo masculino
+    Masculina,
+    /// "una", "veintiuna", "doscientas": ante un sustantivo femenino
+    Femenina,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ESPAÑOL
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const UNIDADES_ES: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+
+const DECENAS_ES: [&str; 10] = [
+    "",
+    "diez",
+    "veinte",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+
+const CENTENAS_ES: [&str; 10] = [
+    "",
+    "ciento",
+    "doscientos",
+    "trescientos",
+    "cuatrocientos",
+    "quinientos",
+    "seiscientos",
+    "setecientos",
+    "ochocientos",
+    "novecientos",
+];
+
+/// Número en palabras en español (`n` ≤ `MAXIMO_CARDINAL`).
+pub fn cardinal_es(n: u64, concordancia: Concordancia) -> String {
+    if n == 0 {
+        return "cero".to_string();
+    }
+
+    let billones = n / 1_000_000_000_000;
+    let millones = n / 1_000_000 % 1_000_000;
+    let resto = n % 1_000_000;
+
+    // Los millones y billones son masculinos: "veintiún millones"
+    let mut partes = Vec::new();
+    match billones {
+        0 => {}
+        1 => partes.push("un billón".to_string()),
+        _ => partes.push(format!(
+            "{} billones",
+            menor_millon_es(billones, Concordancia::Masculina)
+        )),
+    }
+    match millones {
+        0 => {}
+        1 => partes.push("un millón".to_string()),
+        _ => partes.push(format!(
+            "{} millones",
+            menor_millon_es(millones, Concordancia::Masculina)
+        )),
+    }
+    if resto > 0 {
+        partes.push(menor_millon_es(resto, concordancia));
+    }
+    partes.join(" ")
+}
+
+fn menor_millon_es(n: u64, concordancia: Concordancia) -> String {
+    let miles = n / 1000;
+    let resto = n % 1000;
+
+    let mut partes = Vec::new();
+    match miles {
+        0 => {}
+        1 => partes.push("mil".to_string()),
+        // "veintiún mil", "doscientas mil personas"
+        _ => {
+            let concordancia_miles = if concordancia == Concordancia::Femenina {
+                Concordancia::Femenina
+            } else {
+                Concordancia::Masculina
+            };
+            partes.push(format!("{} mil", menor_mil_es(miles, concordancia_miles)));
+        }
+    }
+    if resto > 0 {
+        partes.push(menor_mil_es(resto, concordancia));
+    }
+    partes.join(" ")
+}
+
+fn menor_mil_es(n: u64, concordancia: Concordancia) -> String {
+    if n == 100 {
+        return "cien".to_string();
+    }
+    let centenas = (n / 100) as usize;
+    let resto = n % 100;
+
+    let mut partes = Vec::new();
+    if centenas > 0 {
+        let centena = CENTENAS_ES[centenas];
+        if concordancia == Concordancia::Femenina && centenas > 1 {
+            partes.push(centena.replace("ientos", "ientas"));
+        } else {
+            partes.push(centena.to_string());
+        }
+    }
+    if resto > 0 {
+        partes.push(menor_cien_es(resto, concordancia));
+    }
+    partes.join(" ")
+}
+
+fn menor_cien_es(n: u64, concordancia: Concordancia) -> String {
+    let uno = |neutra: &str, masculina: &str, femenina: &str| -> String {
+        match concordancia {
+            Concordancia::Neutra => neutra,
+            Concordancia::Masculina => masculina,
+            Concordancia::Femenina => femenina,
+        }
+        .to_string()
+    };
+
+    match n {
+        1 => uno("uno", "un", "una"),
+        21 => uno("veintiuno", "veintiún", "veintiuna"),
+        0..=29 => UNIDADES_ES[n as usize].to_string(),
+        _ => {
+            let decena = DECENAS_ES[(n / 10) as usize];
+            match n % 10 {
+                0 => decena.to_string(),
+                1 => format!("{} y {}", decena, uno("uno", "un", "una")),
+                unidad => format!("{} y {}", decena, UNIDADES_ES[unidad as usize]),
+            }
+        }
+    }
+}
+
+const ORDINALES_ES: [&str; 10] = [
+    "", "primero", "segundo", "tercero", "cuarto", "quinto", "sexto", "séptimo", "octavo", "noveno",
+];
+
+const DECENAS_ORDINALES_ES: [&str; 10] = [
+    "",
+    "décimo",
+    "vigésimo",
+    "trigésimo",
+    "cuadragésimo",
+    "quincuagésimo",
+    "sexagésimo",
+    "septuagésimo",
+    "octogésimo",
+    "nonagésimo",
+];
+
+/// Ordinal en español ("tercero", "vigésima primera"). Con `apocope`, la
+/// forma ante sustantivo ("primer", "tercer"). De 100 en adelante se usa el
+/// cardinal, como en el habla corriente.
+pub fn ordinal_es(n: u64, femenino: bool, apocope: bool) -> String {
+    let masculino = match n {
+        0 => return "cero".to_string(),
+        1..=9 => ORDINALES_ES[n as usize].to_string(),
+        11 => "undécimo".to_string(),
+        12 => "duodécimo".to_string(),
+        13..=19 => {
+            // "decimotercero", "decimoctavo"
+            let unidad = ORDINALES_ES[(n % 10) as usize];
+            let unidad = unidad.strip_prefix('o').unwrap_or(unidad);
+            format!("decimo{}", unidad)
+        }
+        10..=99 => {
+            let decena = DECENAS_ORDINALES_ES[(n / 10) as usize];
+            match n % 10 {
+                0 => decena.to_string(),
+                unidad => format!("{} {}", decena, ORDINALES_ES[unidad as usize]),
+            }
+        }
+        100 => "centésimo".to_string(),
+        _ => {
+            let concordancia = if femenino {
+                Concordancia::Femenina
+            } else {
+                Concordancia::Neutra
+            };
+            return cardinal_es(n, concordancia);
+        }
+    };
+
+    if femenino {
+        // Cada palabra concuerda: "vigésima primera"
+        return masculino
+            .split(' ')
+            .map(|palabra| format!("{}a", palabra.strip_suffix('o').unwrap_or(palabra)))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    if apocope && (masculino.ends_with("primero") || masculino.ends_with("tercero")) {
+        return masculino[..masculino.len() - 1].to_string();
+    }
+    masculino
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// INGLÉS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const UNIDADES_EN: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const DECENAS_EN: [&str; 10] = [
+    "", "ten", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ESCALAS_EN: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Número en palabras en inglés (`n` ≤ `MAXIMO_CARDINAL`).
+pub fn cardinal_en(n: u64) -> String {
+    if n == 0 {
+        return "zero".to_string();
+    }
+
+    let mut partes = Vec::new();
+    let mut resto = n;
+    for (escala, nombre) in ESCALAS_EN {
+        if resto >= escala {
+            partes.push(format!("{} {}", menor_mil_en(resto / escala), nombre));
+            resto %= escala;
+        }
+    }
+    if resto > 0 {
+        partes.push(menor_mil_en(resto));
+    }
+    partes.join(" ")
+}
+
+fn menor_mil_en(n: u64) -> String {
+    let centenas = n / 100;
+    let resto = n % 100;
+
+    let mut partes = Vec::new();
+    if centenas > 0 {
+        partes.push(format!("{} hundred", UNIDADES_EN[centenas as usize]));
+    }
+    match resto {
+        0 => {}
+        1..=19 => partes.push(UNIDADES_EN[resto as usize].to_string()),
+        _ => {
+            let decena = DECENAS_EN[(resto / 10) as usize];
+            match resto % 10 {
+                0 => partes.push(decena.to_string()),
+                unidad => partes.push(format!("{}-{}", decena, UNIDADES_EN[unidad as usize])),
+            }
+        }
+    }
+    partes.join(" ")
+}
+
+/// Ordinal en inglés ("third", "twenty-first").
+pub fn ordinal_en(n: u64) -> String {
+    let cardinal = cardinal_en(n);
+    let corte = cardinal.rfind([' ', '-']).map_or(0, |idx| idx + 1);
+    let (cabeza, ultima) = cardinal.split_at(corte);
+
+    let ordinal = match ultima {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match ultima.strip_suffix('y') {
+            Some(raiz) => format!("{}ieth", raiz),
+            None => format!("{}th", ultima),
+        },
+    };
+    format!("{}{}", cabeza, ordinal)
+}
+
+/// Año en inglés como se dice: "nineteen oh five", "twenty twenty-six",
+/// "two thousand three".
+pub fn anio_en(n: u64) -> String {
+    if !(1000..=9999).contains(&n) || n % 1000 < 10 {
+        return cardinal_en(n);
+    }
+    let (siglo, resto) = (n / 100, n % 100);
+    match resto {
+        0 => format!("{} hundred", cardinal_en(siglo)),
+        1..=9 => format!("{} oh {}", cardinal_en(siglo), cardinal_en(resto)),
+        _ => format!("{} {}", cardinal_en(siglo), cardinal_en(resto)),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// NÚMEROS ROMANOS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+const ROMANOS: [(u64, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// Valor de un número romano en forma canónica ("XIV"). Las secuencias que
+/// no lo son ("IIII", "VX") devuelven `None`.
+pub fn valor_romano(texto: &str) -> Option<u64> {
+    if texto.is_empty() {
+        return None;
+    }
+    let mut resto = texto;
+    let mut valor = 0;
+    for (cantidad, simbolo) in ROMANOS {
+        while let Some(siguiente) = resto.strip_prefix(simbolo) {
+            valor += cantidad;
+            resto = siguiente;
+        }
+    }
+    if !resto.is_empty() {
+        return None;
+    }
+    // Forma canónica: al volver a escribirlo debe quedar igual
+    (a_romano(valor) == texto).then_some(valor)
+}
+
+fn a_romano(mut n: u64) -> String {
+    let mut texto = String::new();
+    for (cantidad, simbolo) in ROMANOS {
+        while n >= cantidad {
+            texto.push_str(simbolo);
+            n -= cantidad;
+        }
+    }
+    texto
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cardinales_es() {
+        let casos = [
+            (0, "cero"),
+            (1, "uno"),
+            (16, "dieciséis"),
+            (21, "veintiuno"),
+            (31, "treinta y uno"),
+            (100, "cien"),
+            (101, "ciento uno"),
+            (555, "quinientos cincuenta y cinco"),
+            (1000, "mil"),
+            (2026, "dos mil veintiséis"),
+            (21_000, "veintiún mil"),
+            (1_000_000, "un millón"),
+            (2_500_000, "dos millones quinientos mil"),
+            (1_000_000_000, "mil millones"),
+            (3_000_000_000_000, "tres billones"),
+        ];
+        for (n, esperado) in casos {
+            assert_eq!(cardinal_es(n, Concordancia::Neutra), esperado, "{}", n);
+        }
+    }
+
+    #[test]
+    fn concordancia_es() {
+        assert_eq!(cardinal_es(1, Concordancia::Masculina), "un");
+        assert_eq!(cardinal_es(21, Concordancia::Masculina), "veintiún");
+        assert_eq!(cardinal_es(41, Concordancia::Femenina), "cuarenta y una");
+        assert_eq!(
+            cardinal_es(200_300, Concordancia::Femenina),
+            "doscientas mil trescientas"
+        );
+        assert_eq!(
+            cardinal_es(21_000_000, Concordancia::Femenina),
+            "veintiún millones"
+        );
+    }
+
+    #[test]
+    fn ordinales_es() {
+        assert_eq!(ordinal_es(1, false, false), "primero");
+        assert_eq!(ordinal_es(1, false, true), "primer");
+        assert_eq!(ordinal_es(3, false, true), "tercer");
+        assert_eq!(ordinal_es(2, true, false), "segunda");
+        assert_eq!(ordinal_es(13, false, false), "decimotercero");
+        assert_eq!(ordinal_es(18, false, false), "decimoctavo");
+        assert_eq!(ordinal_es(17, true, false), "decimoséptima");
+        assert_eq!(ordinal_es(21, true, false), "vigésima primera");
+        assert_eq!(ordinal_es(150, false, false), "ciento cincuenta");
+    }
+
+    #[test]
+    fn cardinales_en() {
+        let casos = [
+            (0, "zero"),
+            (13, "thirteen"),
+            (42, "forty-two"),
+            (100, "one hundred"),
+            (1_234, "one thousand two hundred thirty-four"),
+            (1_000_000, "one million"),
+            (7_000_000_005, "seven billion five"),
+        ];
+        for (n, esperado) in casos {
+            assert_eq!(cardinal_en(n), esperado, "{}", n);
+        }
+    }
+
+    #[test]
+    fn ordinales_en() {
+        assert_eq!(ordinal_en(1), "first");
+        assert_eq!(ordinal_en(12), "twelfth");
+        assert_eq!(ordinal_en(20), "twentieth");
+        assert_eq!(ordinal_en(21), "twenty-first");
+        assert_eq!(ordinal_en(103), "one hundred third");
+    }
+
+    #[test]
+    fn anios_en() {
+        assert_eq!(anio_en(2026), "twenty twenty-six");
+        assert_eq!(anio_en(1905), "nineteen oh five");
+        assert_eq!(anio_en(1900), "nineteen hundred");
+        assert_eq!(anio_en(2003), "two thousand three");
+        assert_eq!(anio_en(2000), "two thousand");
+    }
+
+    #[test]
+    fn romanos() {
+        assert_eq!(valor_romano("XIV"), Some(14));
+        assert_eq!(valor_romano("MCMXCIX"), Some(1999));
+        assert_eq!(valor_romano("IIII"), None);
+        assert_eq!(valor_romano("VX"), None);
+        assert_eq!(valor_romano(""), None);
+    }
+}
@@ -0,0 +1,317 @@
+//! Lectura de los signos de puntuación y los símbolos según el nivel elegido.
+//!
+//! Para repasar un texto o revisar código conviene oír las comas, los
+//! paréntesis y los operadores; para leer por encima, no. Cada signo tiene un
+//! nivel a partir del cual se lee por su nombre (en el idioma del texto). Por
+//! debajo de ese nivel, la puntuación se deja para que la voz haga sus pausas
+//! y los símbolos se callan.
+
+use super::Idioma;
+use crate::config::NivelPuntuacion;
+
+/// Qué queda del signo en el texto además de su nombre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Clase {
+    /// Se conserva siempre, también tras su nombre, para que la voz haga la
+    /// pausa o la entonación de la frase (salvo dentro de una palabra)
+    Pausa,
+    /// Se conserva si no se nombra: separa o forma parte de las palabras
+    Conservar,
+    /// Se cambia por un espacio si no se nombra; la voz lo leería a su manera
+    /// o lo pegaría a la palabra
+    Callar,
+}
+
+/// Un signo de la tabla.
+struct Signo {
+    caracter: char,
+    /// Nivel a partir del cual se nombra
+    nivel: NivelPuntuacion,
+    clase: Clase,
+    es: &'static str,
+    en: &'static str,
+}
+
+impl Signo {
+    fn nombre(&self, idioma: Idioma) -> &'static str {
+        match idioma {
+            Idioma::Es => self.es,
+            Idioma::En => self.en,
+        }
+    }
+}
+
+const fn signo(
+    caracter: char,
+    nivel: NivelPuntuacion,
+    clase: Clase,
+    es: &'static str,
+    en: &'static str,
+) -> Signo {
+    Signo {
+        caracter,
+        nivel,
+        clase,
+        es,
+        en,
+    }
+}
+
+use Clase::{Callar, Conservar, Pausa};
+use NivelPuntuacion::{Algunos, Mayoria, Todos};
+
+/// Signos con nombre. Los que no están aquí se dejan como estén.
+const SIGNOS: &[Signo] = &[
+    // Símbolos que no son puntuación
+    signo('@', Algunos, Callar, "arroba", "at"),
+    signo('#', Algunos, Callar, "almohadilla", "hash"),
+    signo('&', Algunos, Callar, "y", "and"),
+    signo('%', Algunos, Callar, "por ciento", "percent"),
+    signo('$', Algunos, Callar, "dólar", "dollar"),
+    signo('€', Algunos, Callar, "euro", "euro"),
+    signo('£', Algunos, Callar, "libra", "pound"),
+    signo('¥', Algunos, Callar, "yen", "yen"),
+    signo('§', Algunos, Callar, "sección", "section"),
+    signo('¶', Algunos, Callar, "calderón", "pilcrow"),
+    signo('©', Algunos, Callar, "copyright", "copyright"),
+    signo('®', Algunos, Callar, "marca registrada", "registered"),
+    signo('™', Algunos, Callar, "marca comercial", "trademark"),
+    signo('°', Algunos, Callar, "grados", "degrees"),
+    signo('•', Algunos, Callar, "viñeta", "bullet"),
+    signo('×', Algunos, Callar, "por", "times"),
+    signo('÷', Algunos, Callar, "entre", "divided by"),
+    signo('±', Algunos, Callar, "más menos", "plus or minus"),
+    signo('−', Algunos, Conservar, "menos", "minus"),
+    signo('≈', Algunos, Callar, "aproximadamente", "approximately"),
+    signo('≠', Algunos, Callar, "distinto de", "not equal to"),
+    signo(
+        '≤',
+        Algunos,
+        Callar,
+        "menor o igual que",
+        "less than or equal to",
+    ),
+    signo(
+        '≥',
+        Algunos,
+        Callar,
+        "mayor o igual que",
+        "greater than or equal to",
+    ),
+    signo('∞', Algunos, Callar, "infinito", "infinity"),
+    signo('‰', Algunos, Callar, "por mil", "per mille"),
+    signo('→', Algunos, Callar, "flecha a la derecha", "right arrow"),
+    signo('←', Algunos, Callar, "flecha a la izquierda", "left arrow"),
+    signo('↑', Algunos, Callar, "flecha arriba", "up arrow"),
+    signo('↓', Algunos, Callar, "flecha abajo", "down arrow"),
+    signo('⇒', Algunos, Callar, "implica", "implies"),
+    signo('★', Algunos, Callar, "estrella", "star"),
+    signo('☆', Algunos, Callar, "estrella", "star"),
+    signo('✓', Algunos, Callar, "marca de verificación", "check mark"),
+    signo('✗', Algunos, Callar, "aspa", "cross mark"),
+    signo('✘', Algunos, Callar, "aspa", "cross mark"),
+    // Agrupación, comillas y operadores
+    signo('(', Mayoria, Conservar, "abre paréntesis", "left paren"),
+    signo(')', Mayoria, Conservar, "cierra paréntesis", "right paren"),
+    signo('[', Mayoria, Callar, "abre corchete", "left bracket"),
+    signo(']', Mayoria, Callar, "cierra corchete", "right bracket"),
+    signo('{', Mayoria, Callar, "abre llave", "left brace"),
+    signo('}', Mayoria, Callar, "cierra llave", "right brace"),
+    signo('"', Mayoria, Conservar, "comillas", "quote"),
+    signo('“', Mayoria, Conservar, "abre comillas", "left quote"),
+    signo('”', Mayoria, Conservar, "cierra comillas", "right quote"),
+    signo('«', Mayoria, Conservar, "abre comillas", "left quote"),
+    signo('»', Mayoria, Conservar, "cierra comillas", "right quote"),
+    signo(
+        '‘',
+        Mayoria,
+        Conservar,
+        "abre comilla simple",
+        "left single quote",
+    ),
+    signo(
+        '’',
+        Mayoria,
+        Conservar,
+        "cierra comilla simple",
+        "right single quote",
+    ),
+    signo('–', Mayoria, Conservar, "guion largo", "en dash"),
+    signo('—', Mayoria, Conservar, "raya", "em dash"),
+    signo('+', Mayoria, Callar, "más", "plus"),
+    signo('=', Mayoria, Callar, "igual", "equals"),
+    signo('<', Mayoria, Callar, "menor que", "less than"),
+    signo('>', Mayoria, Callar, "mayor que", "greater than"),
+    signo('*', Mayoria, Callar, "asterisco", "star"),
+    signo('/', Mayoria, Callar, "barra", "slash"),
+    signo('\\', Mayoria, Callar, "barra invertida", "backslash"),
+    signo('|', Mayoria, Callar, "barra vertical", "bar"),
+    signo('_', Mayoria, Callar, "guion bajo", "underscore"),
+    signo('~', Mayoria, Callar, "virgulilla", "tilde"),
+    signo('^', Mayoria, Callar, "circunflejo", "caret"),
+    signo('`', Mayoria, Callar, "acento grave", "backtick"),
+    // Puntuación de la frase
+    signo('.', Todos, Pausa, "punto", "dot"),
+    signo(',', Todos, Pausa, "coma", "comma"),
+    signo(';', Todos, Pausa, "punto y coma", "semicolon"),
+    signo(':', Todos, Pausa, "dos puntos", "colon"),
+    signo(
+        '¿',
+        Todos,
+        Pausa,
+        "abre interrogación",
+        "inverted question mark",
+    ),
+    signo('?', Todos, Pausa, "cierra interrogación", "question mark"),
+    signo(
+        '¡',
+        Todos,
+        Pausa,
+        "abre exclamación",
+        "inverted exclamation mark",
+    ),
+    signo('!', Todos, Pausa, "cierra exclamación", "exclamation mark"),
+    signo('…', Todos, Pausa, "puntos suspensivos", "ellipsis"),
+    signo('-', Todos, Conservar, "guion", "dash"),
+    signo('\'', Todos, Conservar, "apóstrofo", "apostrophe"),
+];
+
+/// Signo de la tabla para un carácter.
+fn buscar(c: char) -> Option<&'static Signo> {
+    SIGNOS.iter().find(|s| s.caracter == c)
+}
+
+/// Nombre de un signo de la tabla en un idioma (para el deletreo).
+pub(super) fn nombre_signo(c: char, idioma: Idioma) -> Option<&'static str> {
+    buscar(c).map(|signo| signo.nombre(idioma))
+}
+
+/// Apóstrofo dentro de una palabra ("don't", "l’aigua"): es parte de ella y
+/// nunca se nombra.
+fn es_apostrofo(c: char, anterior: Option<char>, siguiente: Option<char>) -> bool {
+    matches!(c, '\'' | '’')
+        && anterior.is_some_and(char::is_alphabetic)
+        && siguiente.is_some_and(char::is_alphabetic)
+}
+
+/// Nombra los signos de `nivel` o inferior y calla los demás símbolos.
+pub fn verbalizar_signos(texto: &str, idioma: Idioma, nivel: NivelPuntuacion) -> String {
+    let caracteres: Vec<char> = texto.chars().collect();
+    let mut salida = String::with_capacity(texto.len());
+
+    let mut i = 0;
+    while i < caracteres.len() {
+        // "..." se lee como unos puntos suspensivos, no como tres puntos
+        let (c, largo) = if caracteres[i..].starts_with(&['.', '.', '.']) {
+            ('…', 3)
+        } else {
+            (caracteres[i], 1)
+        };
+        let original = &caracteres[i..i + largo];
+        let anterior = i.checked_sub(1).map(|j| caracteres[j]);
+        let siguiente = caracteres.get(i + largo).copied();
+        i += largo;
+
+        let signo = match buscar(c) {
+            Some(signo) if !es_apostrofo(c, anterior, siguiente) => signo,
+            _ => {
+                salida.extend(original);
+                continue;
+            }
+        };
+
+        if nivel >= signo.nivel {
+            salida.push(' ');
+            salida.push_str(signo.nombre(idioma));
+            // Dentro de una palabra ("main.rs", "3,5") no hay pausa que hacer
+            let en_palabra = anterior.is_some_and(char::is_alphanumeric)
+                && siguiente.is_some_and(char::is_alphanumeric);
+            if signo.clase == Clase::Pausa && !en_palabra {
+                salida.extend(original);
+            }
+            salida.push(' ');
+        } else if signo.clase == Clase::Callar {
+            salida.push(' ');
+        } else {
+            salida.extend(original);
+        }
+    }
+
+    salida.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn es(texto: &str, nivel: NivelPuntuacion) -> String {
+        verbalizar_signos(texto, Idioma::Es, nivel)
+    }
+
+    #[test]
+    fn ninguno_calla_los_simbolos() {
+        let nivel = NivelPuntuacion::Ninguno;
+        assert_eq!(es("Hola, mundo.", nivel), "Hola, mundo.");
+        assert_eq!(
+            es("usa snake_case y/o **esto**", nivel),
+            "usa snake case y o esto"
+        );
+        assert_eq!(
+            es("escribe a ana@ejemplo.com", nivel),
+            "escribe a ana ejemplo.com"
+        );
+        assert_eq!(es("(entre paréntesis)", nivel), "(entre paréntesis)");
+    }
+
+    #[test]
+    fn algunos_nombra_los_simbolos() {
+        let nivel = NivelPuntuacion::Algunos;
+        assert_eq!(
+            es("escribe a ana@ejemplo.com", nivel),
+            "escribe a ana arroba ejemplo.com"
+        );
+        assert_eq!(es("Tom & Jerry", nivel), "Tom y Jerry");
+        assert_eq!(es("a = b + 1", nivel), "a b 1");
+        assert_eq!(
+            es("Acme™ → ★★★ ✓", nivel),
+            "Acme marca comercial flecha a la derecha estrella estrella estrella marca de verificación"
+        );
+        assert_eq!(
+            verbalizar_signos("© 2026, x ≥ 3", Idioma::En, nivel),
+            "copyright 2026, x greater than or equal to 3"
+        );
+    }
+
+    #[test]
+    fn mayoria_nombra_operadores_y_parentesis() {
+        let nivel = NivelPuntuacion::Mayoria;
+        assert_eq!(es("a = b + 1", nivel), "a igual b más 1");
+        assert_eq!(
+            es("f(x) [1]", nivel),
+            "f abre paréntesis x cierra paréntesis abre corchete 1 cierra corchete"
+        );
+        assert_eq!(es("Hola, mundo.", nivel), "Hola, mundo.");
+    }
+
+    #[test]
+    fn todos_nombra_la_puntuacion() {
+        let nivel = NivelPuntuacion::Todos;
+        assert_eq!(es("Hola, mundo.", nivel), "Hola coma, mundo punto.");
+        assert_eq!(
+            es("¿Vienes?", nivel),
+            "abre interrogación¿ Vienes cierra interrogación?"
+        );
+        assert_eq!(es("Espera...", nivel), "Espera puntos suspensivos...");
+        assert_eq!(es("main.rs", nivel), "main punto rs");
+    }
+
+    #[test]
+    fn apostrofo_dentro_de_palabra() {
+        let nivel = NivelPuntuacion::Todos;
+        assert_eq!(verbalizar_signos("don't", Idioma::En, nivel), "don't");
+        assert_eq!(
+            verbalizar_signos("'quoted'", Idioma::En, nivel),
+            "apostrophe quoted apostrophe"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Deletreo de un texto carácter a carácter.
+//!
+//! Para comprobar cómo se escribe un nombre, un código o una contraseña: cada
+//! carácter se lee por su nombre, con las mayúsculas, las cifras, los
+//! espacios y los símbolos anunciados de forma que no se confundan.
+
+use super::punctuation::nombre_signo;
+use super::Idioma;
+use crate::config::AlfabetoDeletreo;
+
+/// Caracteres por fragmento: el primero empieza a sonar enseguida y cada
+/// grupo termina con una pausa breve que ayuda a no perder la cuenta.
+const CARACTERES_POR_FRAGMENTO: usize = 8;
+
+/// Nombres de las letras de la `a` a la `z`.
+const LETRAS_ES: [&str; 26] = [
+    "a",
+    "be",
+    "ce",
+    "de",
+    "e",
+    "efe",
+    "ge",
+    "hache",
+    "i",
+    "jota",
+    "ka",
+    "ele",
+    "eme",
+    "ene",
+    "o",
+    "pe",
+    "cu",
+    "erre",
+    "ese",
+    "te",
+    "u",
+    "uve",
+    "uve doble",
+    "equis",
+    "i griega",
+    "zeta",
+];
+const LETRAS_EN: [&str; 26] = [
+    "ay",
+    "bee",
+    "see",
+    "dee",
+    "ee",
+    "ef",
+    "gee",
+    "aitch",
+    "eye",
+    "jay",
+    "kay",
+    "el",
+    "em",
+    "en",
+    "oh",
+    "pee",
+    "cue",
+    "ar",
+    "ess",
+    "tee",
+    "you",
+    "vee",
+    "double you",
+    "ex",
+    "why",
+    "zee",
+];
+
+/// Alfabeto de deletreo español ("a de Alicante").
+const FONETICO_ES: [&str; 26] = [
+    "Alicante",
+    "Barcelona",
+    "Carmen",
+    "Dolores",
+    "Enrique",
+    "Francia",
+    "Gerona",
+    "Historia",
+    "Inés",
+    "José",
+    "Kilo",
+    "Lorenzo",
+    "Madrid",
+    "Navarra",
+    "Oviedo",
+    "París",
+    "Querido",
+    "Ramón",
+    "Sábado",
+    "Toledo",
+    "Úbeda",
+    "Valencia",
+    "Washington",
+    "Xilófono",
+    "Yegua",
+    "Zaragoza",
+];
+
+/// Alfabeto de la OTAN.
+const OTAN: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+const CIFRAS_ES: [&str; 10] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const CIFRAS_EN: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Signo diacrítico de una letra acentuada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Marca {
+    Aguda,
+    Grave,
+    Circunflejo,
+    Dieresis,
+    Tilde,
+    Cedilla,
+    Anillo,
+}
+
+impl Marca {
+    fn nombre(self, idioma: Idioma) -> &'static str {
+        match (self, idioma) {
+            (Marca::Aguda, Idioma::Es) => "con tilde",
+            (Marca::Aguda, Idioma::En) => "acute",
+            (Marca::Grave, Idioma::Es) => "con acento grave",
+            (Marca::Grave, Idioma::En) => "grave",
+            (Marca::Circunflejo, Idioma::Es) => "con circunflejo",
+            (Marca::Circunflejo, Idioma::En) => "circumflex",
+            (Marca::Dieresis, Idioma::Es) => "con diéresis",
+            (Marca::Dieresis, Idioma::En) => "umlaut",
+            (Marca::Tilde, Idioma::Es) => "con virgulilla",
+            (Marca::Tilde, Idioma::En) => "tilde",
+            (Marca::Cedilla, Idioma::Es) => "con cedilla",
+            (Marca::Cedilla, Idioma::En) => "cedilla",
+            (Marca::Anillo, Idioma::Es) => "con anillo",
+            (Marca::Anillo, Idioma::En) => "ring",
+        }
+    }
+}
+
+/// Letra base y marca de una minúscula acentuada del alfabeto latino. La
+/// `ñ` es una letra más en español y solo se descompone en inglés.
+fn descomponer(c: char, idioma: Idioma) -> Option<(char, Marca)> {
+    let descompuesta = match c {
+        'á' => ('a', Marca::Aguda),
+        'é' => ('e', Marca::Aguda),
+        'í' => ('i', Marca::Aguda),
+        'ó' => ('o', Marca::Aguda),
+        'ú' => ('u', Marca::Aguda),
+        'ý' => ('y', Marca::Aguda),
+        'à' => ('a', Marca::Grave),
+        'è' => ('e', Marca::Grave),
+        'ì' => ('i', Marca::Grave),
+        'ò' => ('o', Marca::Grave),
+        'ù' => ('u', Marca::Grave),
+        'â' => ('a', Marca::Circunflejo),
+        'ê' => ('e', Marca::Circunflejo),
+        'î' => ('i', Marca::Circunflejo),
+        'ô' => ('o', Marca::Circunflejo),
+        'û' => ('u', Marca::Circunflejo),
+        'ä' => ('a', Marca::Dieresis),
+        'ë' => ('e', Marca::Dieresis),
+        'ï' => ('i', Marca::Dieresis),
+        'ö' => ('o', Marca::Dieresis),
+        'ü' => ('u', Marca::Dieresis),
+        'ÿ' => ('y', Marca::Dieresis),
+        'ã' => ('a', Marca::Tilde),
+        'õ' => ('o', Marca::Tilde),
+        'ñ' if idioma == Idioma::En => ('n', Marca::Tilde),
+        'ç' => ('c', Marca::Cedilla),
+        'å' => ('a', Marca::Anillo),
+        _ => return None,
+    };
+    Some(descompuesta)
+}
+
+/// Nombre de una letra minúscula de la `a` a la `z` (o la `ñ` en español).
+fn nombre_letra(c: char, idioma: Idioma, alfabeto: AlfabetoDeletreo) -> Option<String> {
+    if c == 'ñ' && idioma == Idioma::Es {
+        return Some(match alfabeto {
+            AlfabetoDeletreo::Letras => "eñe".to_string(),
+            AlfabetoDeletreo::Fonetico => "eñe de Ñoño".to_string(),
+            AlfabetoDeletreo::Otan => "eñe".to_string(),
+        });
+    }
+    if !c.is_ascii_lowercase() {
+        return None;
+    }
+
+    let i = (c as u8 - b'a') as usize;
+    let letra = match idioma {
+        Idioma::Es => LETRAS_ES[i],
+        Idioma::En => LETRAS_EN[i],
+    };
+    Some(match (alfabeto, idioma) {
+        (AlfabetoDeletreo::Letras, _) => letra.to_string(),
+        (AlfabetoDeletreo::Fonetico, Idioma::Es) => format!("{} de {}", letra, FONETICO_ES[i]),
+        (AlfabetoDeletreo::Fonetico, Idioma::En) | (AlfabetoDeletreo::Otan, _) => {
+            OTAN[i].to_string()
+        }
+    })
+}
+
+/// Nombre de un carácter; `None` para los que no se leen (`\r`).
+fn nombre_caracter(c: char, idioma: Idioma, alfabeto: AlfabetoDeletreo) -> Option<String> {
+    let es = idioma == Idioma::Es;
+    match c {
+        '\r' => return None,
+        '\n' => return Some(if es { "salto de línea" } else { "line break" }.to_string()),
+        '\t' => return Some(if es { "tabulador" } else { "tab" }.to_string()),
+        c if c.is_whitespace() => return Some(if es { "espacio" } else { "space" }.to_string()),
+        _ => {}
+    }
+    if let Some(cifra) = c.to_digit(10) {
+        let cifras = if es { CIFRAS_ES } else { CIFRAS_EN };
+        return Some(cifras[cifra as usize].to_string());
+    }
+    if let Some(nombre) = nombre_signo(c, idioma) {
+        return Some(nombre.to_string());
+    }
+
+    // Letras: minúscula, con la marca de mayúscula delante
+    let mut minusculas = c.to_lowercase();
+    let minuscula = match (minusculas.next(), minusculas.next()) {
+        (Some(m), None) => m,
+        _ => c,
+    };
+    let nombre = match descomponer(minuscula, idioma) {
+        Some((base, marca)) => nombre_letra(base, idioma, alfabeto)
+            .map(|letra| format!("{} {}", letra, marca.nombre(idioma))),
+        None => nombre_letra(minuscula, idioma, alfabeto),
+    }
+    // Letras de otros alfabetos y símbolos sin nombre: la voz sabrá leerlos
+    .unwrap_or_else(|| c.to_string());
+
+    Some(if c.is_uppercase() {
+        format!("{} {}", if es { "mayúscula" } else { "capital" }, nombre)
+    } else {
+        nombre
+    })
+}
+
+/// Deletrea un texto: devuelve fragmentos de unos pocos caracteres, cada uno
+/// con sus nombres separados por comas y terminado en punto, listos para
+/// sintetizarlos sin más preparación. Se ignoran los espacios de los
+/// extremos (los que arrastra una selección).
+pub fn deletrear(texto: &str, idioma: Idioma, alfabeto: AlfabetoDeletreo) -> Vec<String> {
+    let nombres: Vec<String> = texto
+        .trim()
+        .chars()
+        .filter_map(|c| nombre_caracter(c, idioma, alfabeto))
+        .collect();
+
+    nombres
+        .chunks(CARACTERES_POR_FRAGMENTO)
+        .map(|grupo| format!("{}.", grupo.join(", ")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn es(texto: &str, alfabeto: AlfabetoDeletreo) -> Vec<String> {
+        deletrear(texto, Idioma::Es, alfabeto)
+    }
+
+    #[test]
+    fn letras_cifras_y_simbolos() {
+        assert_eq!(
+            es("Ab-9", AlfabetoDeletreo::Letras),
+            ["mayúscula a, be, guion, nueve."]
+        );
+        assert_eq!(
+            deletrear("x_1 Q", Idioma::En, AlfabetoDeletreo::Letras),
+            ["ex, underscore, one, space, capital cue."]
+        );
+    }
+
+    #[test]
+    fn acentos_y_enie() {
+        assert_eq!(
+            es("Ñañú", AlfabetoDeletreo::Letras),
+            ["mayúscula eñe, a, eñe, u con tilde."]
+        );
+        assert_eq!(
+            deletrear("ñ", Idioma::En, AlfabetoDeletreo::Letras),
+            ["en tilde."]
+        );
+    }
+
+    #[test]
+    fn alfabetos_foneticos() {
+        assert_eq!(
+            es("ab", AlfabetoDeletreo::Fonetico),
+            ["a de Alicante, be de Barcelona."]
+        );
+        assert_eq!(es("aB", AlfabetoDeletreo::Otan), ["Alfa, mayúscula Bravo."]);
+        assert_eq!(
+            deletrear("ab", Idioma::En, AlfabetoDeletreo::Fonetico),
+            ["Alfa, Bravo."]
+        );
+    }
+
+    #[test]
+    fn fragmentos() {
+        let fragmentos = es("  abcdefghij\n", AlfabetoDeletreo::Letras);
+        assert_eq!(
+            fragmentos,
+            ["a, be, ce, de, e, efe, ge, hache.", "i, jota."]
+        );
+        assert!(es("   ", AlfabetoDeletreo::Letras).is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+//! Emojis y caracteres especiales.
+//!
+//! Los mensajes copiados de un chat traen emojis, que Piper se salta o
+//! pronuncia a trozos, y caracteres invisibles (espacios de ancho cero,
+//! marcas de dirección, guiones opcionales) que parten las palabras. Los
+//! emojis se cambian por su nombre en el idioma de la voz o se quitan;
+//! después el texto se pasa a la forma NFC y se quitan los caracteres de
+//! control e invisibles.
+//!
+//! En inglés el nombre es el de CLDR que trae el crate `emojis`. En español
+//! se usa la tabla de `emojis_es`, de momento escrita a mano con los más
+//! habituales en los chats hasta generarla desde CLDR; las banderas, las
+//! teclas y las secuencias (👨‍👩‍👧) que no están se nombran por sus partes, y
+//! el resto por su categoría ("emoji de animal o planta"), para no quitar
+//! nada que el texto quería decir.
+
+use emojis::{Emoji, Group, SkinTone};
+use unicode_normalization::UnicodeNormalization;
+
+use super::emojis_es::NOMBRES_ES;
+use super::punctuation::nombre_signo;
+use super::Idioma;
+use crate::config::ModoEmoji;
+
+/// Nombres en español de los países y regiones de las banderas más
+/// habituales, por su código (ISO 3166 en mayúsculas; las regiones, como
+/// Inglaterra, por su etiqueta en minúsculas). Las demás banderas se leen
+/// con las letras del código.
+const PAISES_ES: &[(&str, &str)] = &[
+    ("AR", "Argentina"),
+    ("AT", "Austria"),
+    ("AU", "Australia"),
+    ("BE", "Bélgica"),
+    ("BO", "Bolivia"),
+    ("BR", "Brasil"),
+    ("CA", "Canadá"),
+    ("CH", "Suiza"),
+    ("CL", "Chile"),
+    ("CN", "China"),
+    ("CO", "Colombia"),
+    ("CR", "Costa Rica"),
+    ("CU", "Cuba"),
+    ("DE", "Alemania"),
+    ("DK", "Dinamarca"),
+    ("DO", "República Dominicana"),
+    ("EC", "Ecuador"),
+    ("EG", "Egipto"),
+    ("ES", "España"),
+    ("EU", "la Unión Europea"),
+    ("FI", "Finlandia"),
+    ("FR", "Francia"),
+    ("GB", "Reino Unido"),
+    ("GR", "Grecia"),
+    ("GT", "Guatemala"),
+    ("HN", "Honduras"),
+    ("IE", "Irlanda"),
+    ("IL", "Israel"),
+    ("IN", "India"),
+    ("IT", "Italia"),
+    ("JP", "Japón"),
+    ("KR", "Corea del Sur"),
+    ("MA", "Marruecos"),
+    ("MX", "México"),
+    ("NI", "Nicaragua"),
+    ("NL", "Países Bajos"),
+    ("NO", "Noruega"),
+    ("NZ", "Nueva Zelanda"),
+    ("PA", "Panamá"),
+    ("PE", "Perú"),
+    ("PL", "Polonia"),
+    ("PR", "Puerto Rico"),
+    ("PT", "Portugal"),
+    ("PY", "Paraguay"),
+    ("RU", "Rusia"),
+    ("SE", "Suecia"),
+    ("SV", "El Salvador"),
+    ("TR", "Turquía"),
+    ("UA", "Ucrania"),
+    ("UN", "las Naciones Unidas"),
+    ("US", "Estados Unidos"),
+    ("UY", "Uruguay"),
+    ("VE", "Venezuela"),
+    ("ZA", "Sudáfrica"),
+    ("gbeng", "Inglaterra"),
+    ("gbsct", "Escocia"),
+    ("gbwls", "Gales"),
+];
+
+/// Caracteres que pueden seguir al primero dentro de un mismo emoji.
+fn es_continuacion(c: char) -> bool {
+    matches!(c,
+        '\u{FE0F}' // selector de presentación como emoji
+        | '\u{200D}' // unión de ancho cero (👩‍💻)
+        | '\u{20E3}' // tecla (1️⃣)
+        | '\u{1F3FB}'..='\u{1F3FF}' // tonos de piel
+        | '\u{E0020}'..='\u{E007F}' // etiquetas (banderas de regiones)
+    )
+}
+
+fn es_indicador_regional(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+/// Caracteres que forman el emoji candidato que empieza en `inicio` (puede
+/// que solo un prefijo sea un emoji). `0` si ahí no puede empezar ninguno.
+fn largo_candidato(caracteres: &[char], inicio: usize) -> usize {
+    let c = caracteres[inicio];
+    let siguiente = caracteres.get(inicio + 1).copied();
+    let tecla = siguiente == Some('\u{20E3}')
+        || (siguiente == Some('\u{FE0F}') && caracteres.get(inicio + 2) == Some(&'\u{20E3}'));
+    // `©`, `°` y compañía se dejan al nivel de puntuación
+    if !tecla && (c.is_ascii() || nombre_signo(c, Idioma::Es).is_some()) {
+        return 0;
+    }
+
+    let mut fin = inicio + 1;
+    if es_indicador_regional(c) && siguiente.is_some_and(es_indicador_regional) {
+        fin += 1;
+    }
+    while let Some(&c) = caracteres.get(fin) {
+        if !es_continuacion(c) {
+            break;
+        }
+        fin += 1;
+        // Tras una unión va el siguiente emoji de la secuencia
+        if c == '\u{200D}' && fin < caracteres.len() {
+            fin += 1;
+        }
+    }
+    fin - inicio
+}
+
+/// Nombre de un emoji sin el tono de piel: "pulgar hacia arriba" para 👍🏽.
+fn nombre(emoji: &Emoji, idioma: Idioma) -> String {
+    let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+    match idioma {
+        Idioma::Es => nombre_es(base),
+        // "flag: Spain", "keycap: 1"
+        Idioma::En => base.name().replace(':', ""),
+    }
+}
+
+/// Nombre de la tabla en español, si lo tiene.
+fn nombre_es_conocido(emoji: &Emoji) -> Option<&'static str> {
+    let clave = emoji.as_str().replace('\u{FE0F}', "");
+    NOMBRES_ES
+        .binary_search_by(|(otro, _)| (*otro).cmp(clave.as_str()))
+        .ok()
+        .map(|i| NOMBRES_ES[i].1)
+}
+
+/// Nombre en español: el de la tabla o, si no está, uno hecho con sus
+/// partes (bandera, tecla, secuencia) o su categoría.
+fn nombre_es(emoji: &Emoji) -> String {
+    if let Some(nombre) = nombre_es_conocido(emoji) {
+        return nombre.to_string();
+    }
+    if emoji.group() == Group::Flags && emoji.name().starts_with("flag:") {
+        return nombre_bandera_es(emoji);
+    }
+    // "keycap: 1", "keycap: #"
+    if let Some(tecla) = emoji.name().strip_prefix("keycap: ") {
+        let mut caracteres = tecla.chars();
+        let signo = match (caracteres.next(), caracteres.next()) {
+            (Some(c), None) => nombre_signo(c, Idioma::Es),
+            _ => None,
+        };
+        return format!("tecla {}", signo.unwrap_or(tecla));
+    }
+    // Secuencias unidas (👨‍👩‍👧, ❤️‍🔥): el nombre de cada parte
+    if emoji.as_str().contains('\u{200D}') {
+        let partes: Vec<&str> = emoji
+            .as_str()
+            .split('\u{200D}')
+            .filter_map(|parte| {
+                emojis::get(parte)
+                    .or_else(|| emojis::get(parte.trim_end_matches('\u{FE0F}')))
+                    .and_then(nombre_es_conocido)
+            })
+            .collect();
+        if !partes.is_empty() {
+            let partes = partes.join(", ");
+            return if emoji.name().starts_with("family") {
+                format!("familia: {}", partes)
+            } else {
+                partes
+            };
+        }
+    }
+    nombre_grupo_es(emoji.group()).to_string()
+}
+
+/// "bandera de España" para 🇪🇸. Sin nombre para el país, las letras de su
+/// código: "bandera de D Z".
+fn nombre_bandera_es(emoji: &Emoji) -> String {
+    // Indicadores regionales en mayúsculas; etiquetas (🏴 de Inglaterra) en
+    // minúsculas
+    let codigo: String = emoji
+        .as_str()
+        .chars()
+        .filter_map(|c| match c {
+            '\u{1F1E6}'..='\u{1F1FF}' => char::from_u32(c as u32 - 0x1F1E6 + 'A' as u32),
+            '\u{E0061}'..='\u{E007A}' => char::from_u32(c as u32 - 0xE0061 + 'a' as u32),
+            _ => None,
+        })
+        .collect();
+    match PAISES_ES.iter().find(|(clave, _)| *clave == codigo) {
+        Some((_, pais)) => format!("bandera de {}", pais),
+        None if codigo.is_empty() => "bandera".to_string(),
+        None => {
+            let letras: Vec<String> = codigo.chars().map(|c| c.to_string()).collect();
+            format!("bandera de {}", letras.join(" "))
+        }
+    }
+}
+
+/// Nombre genérico de un emoji de la categoría, para los que no tienen uno
+/// propio en español.
+fn nombre_grupo_es(grupo: Group) -> &'static str {
+    match grupo {
+        Group::SmileysAndEmotion => "emoji de cara",
+        Group::PeopleAndBody => "emoji de persona",
+        Group::AnimalsAndNature => "emoji de animal o planta",
+        Group::FoodAndDrink => "emoji de comida",
+        Group::TravelAndPlaces => "emoji de viaje o lugar",
+        Group::Activities => "emoji de actividad",
+        Group::Objects => "emoji de objeto",
+        Group::Symbols => "emoji de símbolo",
+        Group::Flags => "bandera",
+    }
+}
+
+/// Cambia los emojis por su nombre en `idioma` o los quita, según `modo`.
+/// Sin idioma conocido (una voz que no es de español ni de inglés) se quita.
+/// Un emoji repetido seguido ("🎉🎉🎉") se nombra una sola vez.
+pub fn nombrar_emojis(texto: &str, idioma: Option<Idioma>, modo: ModoEmoji) -> String {
+    let caracteres: Vec<char> = texto.chars().collect();
+    let mut salida = String::with_capacity(texto.len());
+    let mut ultimo: Option<&str> = None;
+
+    let mut i = 0;
+    while i < caracteres.len() {
+        let largo = largo_candidato(&caracteres, i);
+        let encontrado = (1..=largo).rev().find_map(|n| {
+            let candidato: String = caracteres[i..i + n].iter().collect();
+            emojis::get(&candidato).map(|emoji| (emoji, n))
+        });
+        let Some((emoji, n)) = encontrado else {
+            let c = caracteres[i];
+            if !c.is_whitespace() {
+                ultimo = None;
+            }
+            salida.push(c);
+            i += 1;
+            continue;
+        };
+        i += n;
+
+        salida.push(' ');
+        if ultimo == Some(emoji.as_str()) {
+            continue;
+        }
+        ultimo = Some(emoji.as_str());
+        if let (ModoEmoji::Nombrar, Some(idioma)) = (modo, idioma) {
+            salida.push_str(&nombre(emoji, idioma));
+            salida.push(' ');
+        }
+    }
+    salida
+}
+
+/// Caracteres de control (salvo los espacios) e invisibles: espacios de
+/// ancho cero, marcas de dirección, guion opcional, selectores de variante.
+fn es_invisible(c: char) -> bool {
+    (c.is_control() && !c.is_whitespace())
+        || matches!(c,
+            '\u{00AD}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{E0000}'..='\u{E007F}'
+        )
+}
+
+/// Pasa el texto a la forma NFC (una `á` escrita como `a` más el acento
+/// combinado queda como un solo carácter) y quita los caracteres de control
+/// e invisibles.
+pub fn depurar_unicode(texto: &str) -> String {
+    texto.nfc().filter(|&c| !es_invisible(c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limpio(texto: &str) -> String {
+        texto.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn tabla_en_espanol() {
+        // Ordenada para la búsqueda binaria y sin claves repetidas
+        for par in NOMBRES_ES.windows(2) {
+            assert!(par[0].0 < par[1].0, "{:?} desordenado", par[1]);
+        }
+        for (clave, _) in NOMBRES_ES {
+            assert!(
+                emojis::iter().any(|emoji| emoji.as_str().replace('\u{FE0F}', "") == *clave),
+                "emoji desconocido: {clave}"
+            );
+        }
+    }
+
+    #[test]
+    fn nombrar() {
+        let es = Some(Idioma::Es);
+        assert_eq!(
+            limpio(&nombrar_emojis(
+                "Gracias 🙏 lo miro 👍🏽",
+                es,
+                ModoEmoji::Nombrar
+            )),
+            "Gracias manos en oración lo miro pulgar hacia arriba"
+        );
+        assert_eq!(
+            limpio(&nombrar_emojis("¡Listo!🎉🎉🎉", es, ModoEmoji::Nombrar)),
+            "¡Listo! cañón de confeti"
+        );
+        assert_eq!(
+            limpio(&nombrar_emojis(
+                "Ship it 🚀 👩‍💻 1️⃣",
+                Some(Idioma::En),
+                ModoEmoji::Nombrar
+            )),
+            "Ship it rocket woman technologist keycap 1"
+        );
+        // Sin nombre propio en español se lee su categoría; sin idioma
+        // conocido se quita
+        assert_eq!(
+            limpio(&nombrar_emojis("Hola 🦩", es, ModoEmoji::Nombrar)),
+            "Hola emoji de animal o planta"
+        );
+        assert_eq!(
+            limpio(&nombrar_emojis("Hallo 🚀", None, ModoEmoji::Nombrar)),
+            "Hallo"
+        );
+    }
+
+    #[test]
+    fn nombres_compuestos_en_espanol() {
+        let es = |texto: &str| limpio(&nombrar_emojis(texto, Some(Idioma::Es), ModoEmoji::Nombrar));
+        assert_eq!(
+            es("Desde 🇪🇸 y 🇲🇽"),
+            "Desde bandera de España y bandera de México"
+        );
+        assert_eq!(es("🇩🇿"), "bandera de D Z");
+        assert_eq!(
+            es("\u{1F3F4}\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}"),
+            "bandera de Inglaterra"
+        );
+        assert_eq!(es("🏁"), "bandera de cuadros");
+        assert_eq!(es("Paso 1️⃣ y #️⃣"), "Paso tecla 1 y tecla almohadilla");
+        assert_eq!(es("👨‍👩‍👧"), "familia: hombre, mujer, niña");
+        assert_eq!(es("👩🏽‍💻"), "mujer, ordenador portátil");
+        assert_eq!(es("🍕"), "emoji de comida");
+    }
+
+    #[test]
+    fn todos_tienen_nombre_en_espanol() {
+        for emoji in emojis::iter() {
+            assert!(!nombre(emoji, Idioma::Es).is_empty(), "{}", emoji.name());
+        }
+    }
+
+    #[test]
+    fn omitir_y_signos() {
+        assert_eq!(
+            limpio(&nombrar_emojis(
+                "Vale ❤️ 👍",
+                Some(Idioma::Es),
+                ModoEmoji::Omitir
+            )),
+            "Vale"
+        );
+        // Los símbolos con nombre de puntuación y las letras no son emojis
+        assert_eq!(
+            nombrar_emojis("© 2026 · año 1", Some(Idioma::Es), ModoEmoji::Nombrar),
+            "© 2026 · año 1"
+        );
+    }
+
+    #[test]
+    fn nfc_e_invisibles() {
+        assert_eq!(depurar_unicode("cafe\u{301}"), "café");
+        assert_eq!(
+            depurar_unicode("pa\u{200B}la\u{00AD}bra\u{FEFF}"),
+            "palabra"
+        );
+        assert_eq!(depurar_unicode("a\u{0007}b\tc\u{200E}"), "ab\tc");
+    }
+}
@@ -1,26 +1,38 @@
 //! System tray icon con menú contextual.
 //!
 //! Usa `tray-icon` + `muda` para crear un icono en la bandeja del sistema
-//! con menú para controlar la velocidad de TTS y salir de la aplicación.
+//! con menú para controlar la velocidad de TTS, elegir voz y hablante,
+//! cambiar de perfil y de nivel de puntuación y salir de la aplicación.
 //! El icono se incrusta en el binario con `include_bytes!`.
 
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
 use image::ImageReader;
-use muda::{CheckMenuItem, Menu, MenuEvent, PredefinedMenuItem, Submenu};
+use muda::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
 use tray_icon::menu::MenuId;
 use tray_icon::{Icon, TrayIconBuilder};
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
-use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::WindowId;
 
-use crate::config::{VELOCIDADES_PRESET, VERSION};
+use crate::config::{
+    self, formatear_velocidad, Config, ModoCodigo, NivelPuntuacion, PresetVelocidad,
+    GENERACION_CONFIG, VERSION,
+};
+use crate::lexicon;
 use crate::logging::log;
+use crate::profiles::{
+    activar_perfil, desactivar_perfil, hablante_actual, perfil_activo, voz_actual,
+};
 use crate::state::{
-    establecer_velocidad, etiqueta_velocidad_actual, indice_preset_actual, DEBE_SALIR,
+    establecer_modo_codigo, establecer_nivel_puntuacion, establecer_velocidad,
+    etiqueta_velocidad_actual, indice_preset_actual, modo_codigo, nivel_puntuacion, DEBE_SALIR,
     VELOCIDAD_ACTUAL,
 };
+use crate::voices::{self, Hablante, InfoVoz};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // ICONO
@@ -50,26 +62,292 @@ fn load_tray_icon() -> Icon {
 
 /// Prefijo para IDs de presets de velocidad.
 const ID_SPEED_PREFIX: &str = "speed_";
+/// Entrada que muestra una velocidad que no es ningún preset.
+const ID_SPEED_CUSTOM: &str = "speed_custom";
+/// Prefijo para IDs de perfiles.
+const ID_PROFILE_PREFIX: &str = "profile_";
+/// Entrada "Sin perfil".
+const ID_PROFILE_NONE: &str = "profile_none";
+/// Prefijo para IDs de voces del catálogo.
+const ID_VOICE_PREFIX: &str = "voice_";
+/// Entrada que vuelve a buscar las voces instaladas.
+const ID_VOICE_RESCAN: &str = "voice_rescan";
+/// Prefijo para IDs de hablantes del modelo en uso.
+const ID_SPEAKER_PREFIX: &str = "speaker_";
+/// Prefijo para IDs de niveles de puntuación.
+const ID_PUNCTUATION_PREFIX: &str = "punctuation_";
+/// Prefijo para IDs de modos de lectura de código.
+const ID_CODE_PREFIX: &str = "code_";
+/// Abre el léxico de pronunciación en el editor.
+const ID_LEXICON: &str = "lexicon_edit";
+
+/// A partir de este número de hablantes el submenú se divide en grupos
+/// (hay modelos con cientos de hablantes).
+const HABLANTES_POR_GRUPO: usize = 40;
 const ID_EXIT: &str = "exit";
 
-/// Construye el menú contextual del tray con submenú de velocidad.
-fn build_tray_menu() -> (Menu, Submenu, Vec<CheckMenuItem>) {
-    let vel_actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
-    let etiqueta = etiqueta_velocidad_actual();
+/// Cada cuánto se despierta el event loop para reflejar cambios externos
+/// (hotkeys, recarga de configuración) aunque no lleguen eventos del tray.
+const INTERVALO_SINCRONIZACION: Duration = Duration::from_millis(250);
 
-    // Submenú de velocidad con check items
-    let submenu_velocidad = Submenu::new(format!("Velocidad: {}", etiqueta), true);
-
-    let mut check_items: Vec<CheckMenuItem> = Vec::with_capacity(VELOCIDADES_PRESET.len());
+/// Añade al submenú un check item por cada preset de velocidad.
+fn build_speed_items(submenu: &Submenu, presets: &[PresetVelocidad]) -> Vec<CheckMenuItem> {
+    let vel_actual = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
+    let mut check_items: Vec<CheckMenuItem> = Vec::with_capacity(presets.len());
 
-    for (i, (label, ls_x100)) in VELOCIDADES_PRESET.iter().enumerate() {
+    for (i, preset) in presets.iter().enumerate() {
         let id = format!("{}{}", ID_SPEED_PREFIX, i);
-        let checked = *ls_x100 == vel_actual;
-        let item = CheckMenuItem::with_id(id, *label, true, checked, None);
-        submenu_velocidad.append(&item).unwrap();
+        let checked = preset.multiplicador_x100() == vel_actual;
+        let item = CheckMenuItem::with_id(id, preset.etiqueta(), true, checked, None);
+        submenu.append(&item).unwrap();
         check_items.push(item);
     }
 
+    check_items
+}
+
+/// Título del submenú de puntuación.
+fn titulo_puntuacion(nivel: NivelPuntuacion) -> String {
+    format!("Puntuación: {}", nivel.etiqueta())
+}
+
+/// Añade al submenú un check item por cada nivel de puntuación.
+fn build_punctuation_items(submenu: &Submenu) -> Vec<CheckMenuItem> {
+    let actual = nivel_puntuacion();
+    NivelPuntuacion::NIVELES
+        .iter()
+        .map(|&nivel| {
+            let id = format!("{}{}", ID_PUNCTUATION_PREFIX, nivel.indice());
+            let item = CheckMenuItem::with_id(id, nivel.etiqueta(), true, nivel == actual, None);
+            submenu.append(&item).unwrap();
+            item
+        })
+        .collect()
+}
+
+/// Título del submenú de lectura de código.
+fn titulo_codigo(modo: ModoCodigo) -> String {
+    format!("Código: {}", modo.etiqueta())
+}
+
+/// Añade al submenú un check item por cada modo de lectura de código.
+fn build_code_items(submenu: &Submenu) -> Vec<CheckMenuItem> {
+    let actual = modo_codigo();
+    ModoCodigo::MODOS
+        .iter()
+        .map(|&modo| {
+            let id = format!("{}{}", ID_CODE_PREFIX, modo.indice());
+            let item = CheckMenuItem::with_id(id, modo.etiqueta(), true, modo == actual, None);
+            submenu.append(&item).unwrap();
+            item
+        })
+        .collect()
+}
+
+/// Nombres de los perfiles de una configuración, en orden.
+fn nombres_perfiles(config: &Config) -> Vec<String> {
+    config.perfiles.iter().map(|p| p.nombre.clone()).collect()
+}
+
+/// Título del submenú de perfiles.
+fn titulo_perfil(activo: Option<&str>) -> String {
+    format!("Perfil: {}", activo.unwrap_or("ninguno"))
+}
+
+/// Añade al submenú "Sin perfil" y un check item por cada perfil.
+///
+/// El primer elemento del vector devuelto es siempre "Sin perfil".
+fn build_profile_items(submenu: &Submenu, nombres: &[String]) -> Vec<CheckMenuItem> {
+    let activo = perfil_activo();
+    let mut items: Vec<CheckMenuItem> = Vec::with_capacity(nombres.len() + 1);
+
+    let ninguno =
+        CheckMenuItem::with_id(ID_PROFILE_NONE, "Sin perfil", true, activo.is_none(), None);
+    submenu.append(&ninguno).unwrap();
+    items.push(ninguno);
+
+    for (i, nombre) in nombres.iter().enumerate() {
+        let id = format!("{}{}", ID_PROFILE_PREFIX, i);
+        let checked = activo.as_deref() == Some(nombre.as_str());
+        let item = CheckMenuItem::with_id(id, nombre, true, checked, None);
+        submenu.append(&item).unwrap();
+        items.push(item);
+    }
+
+    items
+}
+
+/// Título del submenú de voces: el nombre del modelo en uso.
+fn titulo_voz(voces: &[InfoVoz], actual: &Path) -> String {
+    let nombre = voces
+        .iter()
+        .find(|v| v.ruta == actual)
+        .map(|v| v.nombre.clone())
+        .or_else(|| actual.file_stem().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    format!("Voz: {}", nombre)
+}
+
+/// Entradas del submenú de voces que cambian con el catálogo.
+struct ItemsVoz {
+    /// Un submenú por idioma
+    grupos: Vec<Submenu>,
+    /// Un check item por voz, en el orden del catálogo (ID `voice_{i}`)
+    voces: Vec<CheckMenuItem>,
+    /// Aviso cuando no se encontró ninguna voz
+    vacio: Option<MenuItem>,
+}
+
+/// Inserta al principio del submenú un submenú por idioma con un check item
+/// por voz. El catálogo viene ordenado por idioma.
+fn build_voice_items(submenu: &Submenu, voces: &[InfoVoz], dir_voces: &Path) -> ItemsVoz {
+    let actual = voz_actual();
+    let mut items = ItemsVoz {
+        grupos: Vec::new(),
+        voces: Vec::with_capacity(voces.len()),
+        vacio: None,
+    };
+
+    if voces.is_empty() {
+        let aviso = MenuItem::new(
+            format!("No hay voces en {}", dir_voces.display()),
+            false,
+            None,
+        );
+        submenu.insert(&aviso, 0).unwrap();
+        items.vacio = Some(aviso);
+        return items;
+    }
+
+    let mut idioma_grupo: Option<String> = None;
+    for (i, voz) in voces.iter().enumerate() {
+        let idioma = voz.etiqueta_idioma();
+        if idioma_grupo.as_deref() != Some(idioma.as_str()) {
+            let grupo = Submenu::new(&idioma, true);
+            submenu.insert(&grupo, items.grupos.len()).unwrap();
+            items.grupos.push(grupo);
+            idioma_grupo = Some(idioma);
+        }
+
+        let id = format!("{}{}", ID_VOICE_PREFIX, i);
+        let item = CheckMenuItem::with_id(id, voz.etiqueta(), true, voz.ruta == actual, None);
+        if let Some(grupo) = items.grupos.last() {
+            grupo.append(&item).unwrap();
+        }
+        items.voces.push(item);
+    }
+
+    items
+}
+
+/// Título del submenú de hablantes. Sin hablante elegido Piper usa el ID 0.
+fn titulo_hablante(hablantes: &[Hablante], actual: Option<u32>) -> String {
+    if hablantes.is_empty() {
+        return "Hablante: único".to_string();
+    }
+    let id = actual.unwrap_or(0);
+    let nombre = hablantes
+        .iter()
+        .find(|h| h.id == id)
+        .map_or_else(|| id.to_string(), |h| h.nombre.clone());
+    format!("Hablante: {}", nombre)
+}
+
+/// Entradas del submenú de hablantes.
+struct ItemsHablante {
+    /// Grupos de `HABLANTES_POR_GRUPO` hablantes, si hacen falta
+    grupos: Vec<Submenu>,
+    /// Un check item por hablante, en orden de ID (ID `speaker_{i}`)
+    hablantes: Vec<CheckMenuItem>,
+}
+
+/// Añade al submenú un check item por hablante, agrupados si son muchos.
+/// El submenú queda deshabilitado si el modelo tiene un solo hablante.
+fn build_speaker_items(submenu: &Submenu, hablantes: &[Hablante]) -> ItemsHablante {
+    let mut items = ItemsHablante {
+        grupos: Vec::new(),
+        hablantes: Vec::with_capacity(hablantes.len()),
+    };
+    submenu.set_enabled(!hablantes.is_empty());
+
+    let agrupar = hablantes.len() > HABLANTES_POR_GRUPO;
+    for (n, bloque) in hablantes.chunks(HABLANTES_POR_GRUPO).enumerate() {
+        let grupo = agrupar.then(|| {
+            let titulo = format!("{} – {}", bloque[0].nombre, bloque[bloque.len() - 1].nombre);
+            let grupo = Submenu::new(titulo, true);
+            submenu.append(&grupo).unwrap();
+            grupo
+        });
+
+        for (i, hablante) in bloque.iter().enumerate() {
+            let id = format!("{}{}", ID_SPEAKER_PREFIX, n * HABLANTES_POR_GRUPO + i);
+            let item = CheckMenuItem::with_id(id, &hablante.nombre, true, false, None);
+            match grupo {
+                Some(ref grupo) => grupo.append(&item).unwrap(),
+                None => submenu.append(&item).unwrap(),
+            }
+            items.hablantes.push(item);
+        }
+        items.grupos.extend(grupo);
+    }
+
+    items
+}
+
+/// Submenús del tray que cambian con el estado.
+struct SubmenusTray {
+    velocidad: Submenu,
+    items_velocidad: Vec<CheckMenuItem>,
+    voz: Submenu,
+    items_voz: ItemsVoz,
+    hablante: Submenu,
+    perfil: Submenu,
+    items_perfil: Vec<CheckMenuItem>,
+    puntuacion: Submenu,
+    items_puntuacion: Vec<CheckMenuItem>,
+    codigo: Submenu,
+    items_codigo: Vec<CheckMenuItem>,
+}
+
+/// Construye el menú contextual del tray con los submenús de velocidad, voz,
+/// hablante, perfil, puntuación y código.
+fn build_tray_menu(config: &Config, voces: &[InfoVoz]) -> (Menu, SubmenusTray) {
+    let etiqueta = etiqueta_velocidad_actual();
+
+    // Submenú de velocidad con check items
+    let submenu_velocidad = Submenu::new(format!("Velocidad: {}", etiqueta), true);
+    let check_items = build_speed_items(&submenu_velocidad, &config.velocidades_preset);
+
+    // Submenú de voces agrupadas por idioma, junto al de velocidad
+    let submenu_voz = Submenu::new(titulo_voz(voces, &voz_actual()), true);
+    let items_voz = build_voice_items(&submenu_voz, voces, &config.dir_voces);
+    submenu_voz
+        .append(&PredefinedMenuItem::separator())
+        .unwrap();
+    submenu_voz
+        .append(&MenuItem::with_id(
+            ID_VOICE_RESCAN,
+            "Buscar voces de nuevo",
+            true,
+            None,
+        ))
+        .unwrap();
+
+    // Submenú de hablantes: se rellena al sincronizar con la voz en uso
+    let submenu_hablante = Submenu::new(titulo_hablante(&[], None), false);
+
+    // Submenú de perfiles
+    let submenu_perfil = Submenu::new(titulo_perfil(perfil_activo().as_deref()), true);
+    let items_perfil = build_profile_items(&submenu_perfil, &nombres_perfiles(config));
+
+    // Submenú de niveles de puntuación
+    let submenu_puntuacion = Submenu::new(titulo_puntuacion(nivel_puntuacion()), true);
+    let items_puntuacion = build_punctuation_items(&submenu_puntuacion);
+
+    // Submenú de lectura de código
+    let submenu_codigo = Submenu::new(titulo_codigo(modo_codigo()), true);
+    let items_codigo = build_code_items(&submenu_codigo);
+
     // Menú principal
     let version_item = muda::MenuItem::with_id(
         "version_info",
@@ -77,16 +355,37 @@ fn build_tray_menu() -> (Menu, Submenu, Vec<CheckMenuItem>) {
         false,
         None,
     );
+    let lexico = MenuItem::with_id(ID_LEXICON, "Editar léxico", true, None);
     let exit = muda::MenuItem::with_id(ID_EXIT, "Salir", true, None);
 
     let menu = Menu::new();
     menu.append(&version_item).unwrap();
     menu.append(&PredefinedMenuItem::separator()).unwrap();
     menu.append(&submenu_velocidad).unwrap();
+    menu.append(&submenu_voz).unwrap();
+    menu.append(&submenu_hablante).unwrap();
+    menu.append(&submenu_perfil).unwrap();
+    menu.append(&submenu_puntuacion).unwrap();
+    menu.append(&submenu_codigo).unwrap();
+    menu.append(&PredefinedMenuItem::separator()).unwrap();
+    menu.append(&lexico).unwrap();
     menu.append(&PredefinedMenuItem::separator()).unwrap();
     menu.append(&exit).unwrap();
 
-    (menu, submenu_velocidad, check_items)
+    let submenus = SubmenusTray {
+        velocidad: submenu_velocidad,
+        items_velocidad: check_items,
+        voz: submenu_voz,
+        items_voz,
+        hablante: submenu_hablante,
+        perfil: submenu_perfil,
+        items_perfil,
+        puntuacion: submenu_puntuacion,
+        items_puntuacion,
+        codigo: submenu_codigo,
+        items_codigo,
+    };
+    (menu, submenus)
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -99,12 +398,59 @@ struct TrayApp {
     submenu_velocidad: Submenu,
     /// Check items de velocidad para actualizar las marcas.
     check_items: Vec<CheckMenuItem>,
+    /// Entrada "Personalizada (xN)", solo presente en el submenú cuando la
+    /// velocidad actual no coincide con ningún preset.
+    item_personalizada: CheckMenuItem,
+    /// Si `item_personalizada` está añadida al submenú.
+    personalizada_visible: bool,
     /// Se guarda para que no se destruya mientras corre el loop.
     _tray_icon: Option<tray_icon::TrayIcon>,
     /// Menú del tray (debe mantenerse vivo).
     _menu: Option<Menu>,
-    /// Último índice de velocidad reflejado en el menú.
-    ultimo_indice_velocidad: usize,
+    /// Última velocidad reflejada en el menú (`None` fuerza a refrescarlo).
+    ultima_velocidad: Option<u32>,
+    /// Presets con los que se construyó el submenú de velocidad.
+    presets_menu: Vec<PresetVelocidad>,
+    /// Submenú de voces para actualizar su título.
+    submenu_voz: Submenu,
+    /// Grupos por idioma y check items de las voces.
+    items_voz: ItemsVoz,
+    /// Catálogo con el que se construyó el submenú de voces.
+    voces_menu: Vec<InfoVoz>,
+    /// Última voz reflejada en el menú (`None` fuerza a refrescarlo).
+    ultima_voz: Option<PathBuf>,
+    /// Submenú de hablantes de la voz en uso.
+    submenu_hablante: Submenu,
+    /// Grupos y check items de los hablantes.
+    items_hablante: ItemsHablante,
+    /// Hablantes con los que se construyó el submenú.
+    hablantes_menu: Vec<Hablante>,
+    /// Modelo cuyos hablantes muestra el submenú (`None` fuerza a reconstruirlo).
+    modelo_hablantes: Option<PathBuf>,
+    /// Último hablante reflejado en el menú (`None` fuerza a refrescarlo).
+    ultimo_hablante: Option<Option<u32>>,
+    /// Submenú de perfiles para actualizar su título.
+    submenu_perfil: Submenu,
+    /// "Sin perfil" seguido de un check item por perfil.
+    items_perfil: Vec<CheckMenuItem>,
+    /// Nombres de perfil con los que se construyó el submenú.
+    perfiles_menu: Vec<String>,
+    /// Último perfil reflejado en el menú (`None` fuerza a refrescarlo).
+    ultimo_perfil: Option<Option<String>>,
+    /// Submenú de puntuación para actualizar su título.
+    submenu_puntuacion: Submenu,
+    /// Un check item por nivel, en el orden de `NivelPuntuacion::NIVELES`.
+    items_puntuacion: Vec<CheckMenuItem>,
+    /// Último nivel reflejado en el menú (`None` fuerza a refrescarlo).
+    ultima_puntuacion: Option<NivelPuntuacion>,
+    /// Submenú de lectura de código para actualizar su título.
+    submenu_codigo: Submenu,
+    /// Un check item por modo, en el orden de `ModoCodigo::MODOS`.
+    items_codigo: Vec<CheckMenuItem>,
+    /// Último modo reflejado en el menú (`None` fuerza a refrescarlo).
+    ultimo_modo_codigo: Option<ModoCodigo>,
+    /// Generación de la configuración reflejada en el menú.
+    ultima_generacion_config: u32,
 }
 
 impl ApplicationHandler for TrayApp {
@@ -127,27 +473,241 @@ impl ApplicationHandler for TrayApp {
             self.handle_menu_event(&event.id, event_loop);
         }
 
-        // Sincronizar UI del tray con cambios de velocidad hechos fuera del menú
-        // (por ejemplo, mediante hotkeys Ctrl+[ / Ctrl+]).
+        // Reconstruir el submenú si la configuración recargada trae otros presets
+        self.sincronizar_config();
+
+        // Sincronizar UI del tray con cambios hechos fuera del menú
+        // (por ejemplo, mediante hotkeys Ctrl+[ / Ctrl+], F7 o F6).
         self.sincronizar_velocidad_desde_estado();
+        self.sincronizar_voz_desde_estado();
+        self.sincronizar_hablante_desde_estado();
+        self.sincronizar_perfil_desde_estado();
+        self.sincronizar_puntuacion_desde_estado();
+        self.sincronizar_codigo_desde_estado();
 
         // Comprobar si debe salir (por ejemplo, desde un hotkey)
         if DEBE_SALIR.load(Ordering::SeqCst) {
             event_loop.exit();
+            return;
         }
+
+        event_loop.set_control_flow(ControlFlow::WaitUntil(
+            Instant::now() + INTERVALO_SINCRONIZACION,
+        ));
     }
 }
 
 impl TrayApp {
+    /// Reconstruye los submenús de velocidad, voces y perfiles si los
+    /// presets, la carpeta de voces o los perfiles han cambiado tras una
+    /// recarga de la configuración.
+    fn sincronizar_config(&mut self) {
+        let generacion = GENERACION_CONFIG.load(Ordering::SeqCst);
+        if generacion == self.ultima_generacion_config {
+            return;
+        }
+        self.ultima_generacion_config = generacion;
+
+        let config = config::actual();
+        if config.velocidades_preset != self.presets_menu {
+            self.reconstruir_velocidad(&config);
+        }
+
+        if voices::actualizar_catalogo() {
+            self.reconstruir_voces(&config);
+        }
+        // La voz en uso depende de `ruta_modelo`, `motor` y los perfiles, y
+        // el hablante además de `hablante`
+        self.ultima_voz = None;
+        self.modelo_hablantes = None;
+
+        let nombres = nombres_perfiles(&config);
+        if nombres != self.perfiles_menu {
+            for item in self.items_perfil.drain(..) {
+                let _ = self.submenu_perfil.remove(&item);
+            }
+            self.items_perfil = build_profile_items(&self.submenu_perfil, &nombres);
+            self.perfiles_menu = nombres;
+            self.ultimo_perfil = None;
+            log("Tray: submenú de perfiles reconstruido");
+        }
+    }
+
+    /// Sustituye los check items de velocidad por los de los nuevos presets.
+    fn reconstruir_velocidad(&mut self, config: &Config) {
+        for item in self.check_items.drain(..) {
+            let _ = self.submenu_velocidad.remove(&item);
+        }
+        if self.personalizada_visible {
+            let _ = self.submenu_velocidad.remove(&self.item_personalizada);
+            self.personalizada_visible = false;
+        }
+        self.check_items = build_speed_items(&self.submenu_velocidad, &config.velocidades_preset);
+        self.presets_menu = config.velocidades_preset.clone();
+
+        // Forzar que la marca y el título se recalculen con los nuevos presets
+        self.ultima_velocidad = None;
+        log("Tray: submenú de velocidad reconstruido");
+    }
+
+    /// Sustituye los grupos de voces por los del catálogo actual.
+    fn reconstruir_voces(&mut self, config: &Config) {
+        for grupo in self.items_voz.grupos.drain(..) {
+            let _ = self.submenu_voz.remove(&grupo);
+        }
+        if let Some(aviso) = self.items_voz.vacio.take() {
+            let _ = self.submenu_voz.remove(&aviso);
+        }
+        self.voces_menu = voices::catalogo();
+        self.items_voz = build_voice_items(&self.submenu_voz, &self.voces_menu, &config.dir_voces);
+        self.ultima_voz = None;
+        self.modelo_hablantes = None;
+        log("Tray: submenú de voces reconstruido");
+    }
+
+    /// Sincroniza el submenú de voces con la voz en uso.
+    fn sincronizar_voz_desde_estado(&mut self) {
+        let actual = voz_actual();
+        if self.ultima_voz.as_ref() == Some(&actual) {
+            return;
+        }
+
+        for (voz, item) in self.voces_menu.iter().zip(&self.items_voz.voces) {
+            item.set_checked(voz.ruta == actual);
+        }
+        self.submenu_voz
+            .set_text(titulo_voz(&self.voces_menu, &actual));
+        self.ultima_voz = Some(actual);
+    }
+
+    /// Sustituye los hablantes del submenú por los del modelo indicado.
+    fn reconstruir_hablantes(&mut self, modelo: PathBuf) {
+        for grupo in self.items_hablante.grupos.drain(..) {
+            let _ = self.submenu_hablante.remove(&grupo);
+        }
+        for item in self.items_hablante.hablantes.drain(..) {
+            // Falla para los que estaban dentro de un grupo, que ya se quitó
+            let _ = self.submenu_hablante.remove(&item);
+        }
+
+        self.hablantes_menu = voices::info_voz(&modelo)
+            .map(|info| info.hablantes)
+            .unwrap_or_default();
+        self.items_hablante = build_speaker_items(&self.submenu_hablante, &self.hablantes_menu);
+        self.modelo_hablantes = Some(modelo);
+        self.ultimo_hablante = None;
+    }
+
+    /// Sincroniza el submenú de hablantes con la voz y el hablante en uso.
+    fn sincronizar_hablante_desde_estado(&mut self) {
+        let voz = voz_actual();
+        if self.modelo_hablantes.as_ref() != Some(&voz) {
+            self.reconstruir_hablantes(voz.clone());
+        }
+
+        let actual = hablante_actual(&voz).ok().flatten();
+        if self.ultimo_hablante == Some(actual) {
+            return;
+        }
+
+        let seleccionado = actual.unwrap_or(0);
+        for (hablante, item) in self
+            .hablantes_menu
+            .iter()
+            .zip(&self.items_hablante.hablantes)
+        {
+            item.set_checked(hablante.id == seleccionado);
+        }
+        self.submenu_hablante
+            .set_text(titulo_hablante(&self.hablantes_menu, actual));
+        self.ultimo_hablante = Some(actual);
+    }
+
     /// Sincroniza el submenú de velocidad con el estado global actual.
     fn sincronizar_velocidad_desde_estado(&mut self) {
+        let velocidad = VELOCIDAD_ACTUAL.load(Ordering::SeqCst);
+        if self.ultima_velocidad == Some(velocidad) {
+            return;
+        }
+        self.ultima_velocidad = Some(velocidad);
+
         let idx_actual = indice_preset_actual();
-        if idx_actual != self.ultimo_indice_velocidad {
-            let (etiqueta, _) = VELOCIDADES_PRESET[idx_actual];
-            self.actualizar_checks_velocidad(idx_actual);
-            self.submenu_velocidad
-                .set_text(format!("Velocidad: {}", etiqueta));
-            self.ultimo_indice_velocidad = idx_actual;
+        self.actualizar_checks_velocidad(idx_actual);
+        self.actualizar_item_personalizada(idx_actual.is_none(), velocidad);
+        self.submenu_velocidad
+            .set_text(format!("Velocidad: {}", etiqueta_velocidad_actual()));
+    }
+
+    /// Sincroniza el submenú de perfiles con el perfil activo.
+    fn sincronizar_perfil_desde_estado(&mut self) {
+        let activo = perfil_activo();
+        if self.ultimo_perfil.as_ref() == Some(&activo) {
+            return;
+        }
+
+        // items_perfil[0] es "Sin perfil"; el resto sigue el orden de perfiles_menu
+        let seleccionado = match activo.as_deref() {
+            Some(nombre) => self
+                .perfiles_menu
+                .iter()
+                .position(|n| n == nombre)
+                .map(|i| i + 1),
+            None => Some(0),
+        };
+        for (i, item) in self.items_perfil.iter().enumerate() {
+            item.set_checked(Some(i) == seleccionado);
+        }
+        self.submenu_perfil
+            .set_text(titulo_perfil(activo.as_deref()));
+        self.ultimo_perfil = Some(activo);
+    }
+
+    /// Sincroniza el submenú de puntuación con el nivel actual.
+    fn sincronizar_puntuacion_desde_estado(&mut self) {
+        let nivel = nivel_puntuacion();
+        if self.ultima_puntuacion == Some(nivel) {
+            return;
+        }
+
+        for (item, n) in self.items_puntuacion.iter().zip(NivelPuntuacion::NIVELES) {
+            item.set_checked(n == nivel);
+        }
+        self.submenu_puntuacion.set_text(titulo_puntuacion(nivel));
+        self.ultima_puntuacion = Some(nivel);
+    }
+
+    /// Sincroniza el submenú de lectura de código con el modo actual.
+    fn sincronizar_codigo_desde_estado(&mut self) {
+        let modo = modo_codigo();
+        if self.ultimo_modo_codigo == Some(modo) {
+            return;
+        }
+
+        for (item, m) in self.items_codigo.iter().zip(ModoCodigo::MODOS) {
+            item.set_checked(m == modo);
+        }
+        self.submenu_codigo.set_text(titulo_codigo(modo));
+        self.ultimo_modo_codigo = Some(modo);
+    }
+
+    /// Muestra "Personalizada (xN)" al final del submenú si la velocidad no
+    /// es ningún preset, y la quita en caso contrario.
+    fn actualizar_item_personalizada(&mut self, personalizada: bool, velocidad: u32) {
+        if personalizada {
+            self.item_personalizada.set_text(format!(
+                "Personalizada ({})",
+                formatear_velocidad(velocidad)
+            ));
+            self.item_personalizada.set_checked(true);
+            if !self.personalizada_visible {
+                self.submenu_velocidad
+                    .append(&self.item_personalizada)
+                    .unwrap();
+                self.personalizada_visible = true;
+            }
+        } else if self.personalizada_visible {
+            let _ = self.submenu_velocidad.remove(&self.item_personalizada);
+            self.personalizada_visible = false;
         }
     }
 
@@ -162,26 +722,119 @@ impl TrayApp {
             return;
         }
 
+        if id_str == ID_LEXICON {
+            log("Tray: Editar léxico");
+            if let Err(e) = lexicon::abrir_en_editor() {
+                log(&e);
+            }
+            return;
+        }
+
+        // Comprobar si es una voz
+        if id_str == ID_VOICE_RESCAN {
+            log("Tray: Buscar voces de nuevo");
+            if voices::actualizar_catalogo() {
+                self.reconstruir_voces(&config::actual());
+            }
+            return;
+        }
+        if let Some(idx_str) = id_str.strip_prefix(ID_VOICE_PREFIX) {
+            if let Ok(idx) = idx_str.parse::<usize>() {
+                if let Some(voz) = self.voces_menu.get(idx).cloned() {
+                    voices::elegir_voz(&voz.ruta);
+                    // muda invierte la marca al hacer clic
+                    self.ultima_voz = None;
+                    self.sincronizar_voz_desde_estado();
+                    log(&format!("Tray: Voz → {}", voz.nombre));
+                }
+            }
+            return;
+        }
+
+        // Comprobar si es un hablante
+        if let Some(idx_str) = id_str.strip_prefix(ID_SPEAKER_PREFIX) {
+            if let Ok(idx) = idx_str.parse::<usize>() {
+                if let (Some(modelo), Some(hablante)) = (
+                    self.modelo_hablantes.clone(),
+                    self.hablantes_menu.get(idx).cloned(),
+                ) {
+                    voices::elegir_hablante(&modelo, &hablante.nombre);
+                    self.ultimo_hablante = None;
+                    self.sincronizar_hablante_desde_estado();
+                    log(&format!("Tray: Hablante → {}", hablante.nombre));
+                }
+            }
+            return;
+        }
+
+        // Comprobar si es un perfil
+        if id_str == ID_PROFILE_NONE {
+            desactivar_perfil();
+            self.ultimo_perfil = None;
+            self.sincronizar_perfil_desde_estado();
+            log("Tray: Sin perfil");
+            return;
+        }
+        if let Some(idx_str) = id_str.strip_prefix(ID_PROFILE_PREFIX) {
+            if let Ok(idx) = idx_str.parse::<usize>() {
+                if let Some(nombre) = self.perfiles_menu.get(idx).cloned() {
+                    activar_perfil(&nombre);
+                    // Como con la velocidad: muda invierte la marca al hacer clic
+                    self.ultimo_perfil = None;
+                    self.sincronizar_perfil_desde_estado();
+                    log(&format!("Tray: Perfil → {}", nombre));
+                }
+            }
+            return;
+        }
+
+        // Comprobar si es un nivel de puntuación
+        if let Some(idx_str) = id_str.strip_prefix(ID_PUNCTUATION_PREFIX) {
+            if let Ok(idx) = idx_str.parse::<usize>() {
+                if let Some(&nivel) = NivelPuntuacion::NIVELES.get(idx) {
+                    establecer_nivel_puntuacion(nivel);
+                    // muda invierte la marca al hacer clic
+                    self.ultima_puntuacion = None;
+                    self.sincronizar_puntuacion_desde_estado();
+                    log(&format!("Tray: Puntuación → {}", nivel.etiqueta()));
+                }
+            }
+            return;
+        }
+
+        // Comprobar si es un modo de lectura de código
+        if let Some(idx_str) = id_str.strip_prefix(ID_CODE_PREFIX) {
+            if let Ok(idx) = idx_str.parse::<usize>() {
+                if let Some(&modo) = ModoCodigo::MODOS.get(idx) {
+                    establecer_modo_codigo(modo);
+                    // muda invierte la marca al hacer clic
+                    self.ultimo_modo_codigo = None;
+                    self.sincronizar_codigo_desde_estado();
+                    log(&format!("Tray: Código → {}", modo.etiqueta()));
+                }
+            }
+            return;
+        }
+
         // Comprobar si es un preset de velocidad
         if let Some(idx_str) = id_str.strip_prefix(ID_SPEED_PREFIX) {
             if let Ok(idx) = idx_str.parse::<usize>() {
-                if idx < VELOCIDADES_PRESET.len() {
-                    let (etiqueta, ls_x100) = VELOCIDADES_PRESET[idx];
-                    establecer_velocidad(ls_x100);
-                    self.actualizar_checks_velocidad(idx);
-                    self.submenu_velocidad
-                        .set_text(format!("Velocidad: {}", etiqueta));
-                    self.ultimo_indice_velocidad = idx;
-                    log(&format!("Tray: Velocidad → {}", etiqueta));
+                if let Some(preset) = config::actual().velocidades_preset.get(idx) {
+                    establecer_velocidad(preset.multiplicador_x100());
+                    // muda invierte la marca al hacer clic: refrescar aunque la
+                    // velocidad no haya cambiado
+                    self.ultima_velocidad = None;
+                    self.sincronizar_velocidad_desde_estado();
+                    log(&format!("Tray: Velocidad → {}", preset.etiqueta()));
                 }
             }
         }
     }
 
     /// Actualiza las marcas de los check items: solo el seleccionado queda marcado.
-    fn actualizar_checks_velocidad(&self, seleccionado: usize) {
+    fn actualizar_checks_velocidad(&self, seleccionado: Option<usize>) {
         for (i, item) in self.check_items.iter().enumerate() {
-            item.set_checked(i == seleccionado);
+            item.set_checked(Some(i) == seleccionado);
         }
     }
 }
@@ -189,18 +842,22 @@ impl TrayApp {
 /// Crea el system tray y ejecuta el event loop principal.
 ///
 /// Esta función bloquea el hilo actual (debe ejecutarse en el hilo principal).
-/// Los hotkeys (F8/F9) siguen funcionando en su propio hilo.
+/// Los hotkeys (F6/F7/F8/F9) siguen funcionando en su propio hilo.
 pub fn run_tray() {
     let icon = load_tray_icon();
-    let (menu, submenu_velocidad, check_items) = build_tray_menu();
+    let generacion_config = GENERACION_CONFIG.load(Ordering::SeqCst);
+    let config = config::actual();
+    voices::actualizar_catalogo();
+    let voces = voices::catalogo();
+    let (menu, submenus) = build_tray_menu(&config, &voces);
 
     let event_loop = EventLoop::new().expect("Error creando event loop");
-    event_loop.set_control_flow(winit::event_loop::ControlFlow::Wait);
+    event_loop.set_control_flow(ControlFlow::Wait);
 
     // Construir el tray icon
     let tray_icon = TrayIconBuilder::new()
         .with_menu(Box::new(menu.clone()))
-        .with_tooltip("TrayNarrator - F8:Leer F9:Pausar")
+        .with_tooltip("TrayNarrator - F8:Leer Mayús+F8:Deletrear F9:Pausar F7:Perfil F6:Puntuación")
         .with_icon(icon)
         .build()
         .expect("Error creando tray icon");
@@ -208,11 +865,38 @@ pub fn run_tray() {
     log("System tray iniciado");
 
     let mut app = TrayApp {
-        submenu_velocidad,
-        check_items,
+        submenu_velocidad: submenus.velocidad,
+        check_items: submenus.items_velocidad,
+        // Deshabilitada: solo informa, no se puede elegir
+        item_personalizada: CheckMenuItem::with_id(ID_SPEED_CUSTOM, "", false, true, None),
+        personalizada_visible: false,
         _tray_icon: Some(tray_icon),
         _menu: Some(menu),
-        ultimo_indice_velocidad: indice_preset_actual(),
+        ultima_velocidad: None,
+        presets_menu: config.velocidades_preset.clone(),
+        submenu_voz: submenus.voz,
+        items_voz: submenus.items_voz,
+        voces_menu: voces,
+        ultima_voz: None,
+        submenu_hablante: submenus.hablante,
+        items_hablante: ItemsHablante {
+            grupos: Vec::new(),
+            hablantes: Vec::new(),
+        },
+        hablantes_menu: Vec::new(),
+        modelo_hablantes: None,
+        ultimo_hablante: None,
+        submenu_perfil: submenus.perfil,
+        items_perfil: submenus.items_perfil,
+        perfiles_menu: nombres_perfiles(&config),
+        ultimo_perfil: None,
+        submenu_puntuacion: submenus.puntuacion,
+        items_puntuacion: submenus.items_puntuacion,
+        ultima_puntuacion: None,
+        submenu_codigo: submenus.codigo,
+        items_codigo: submenus.items_codigo,
+        ultimo_modo_codigo: None,
+        ultima_generacion_config: generacion_config,
     };
 
     event_loop
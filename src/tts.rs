@@ -1,42 +1,299 @@
-//! Generación de audio con Piper TTS.
+//! Síntesis de voz.
+//!
+//! `MotorTts` abstrae el motor que convierte texto en audio. Hay tres
+//! implementaciones, elegidas con la clave `motor` de la configuración:
+//!
+//! - `piper`: Piper TTS como subproceso (motor principal)
+//! - `espeak`: la CLI de espeak-ng, útil si no hay modelos de Piper
+//! - `mock`: tonos deterministas, para probar hotkeys, audio y tray sin
+//!   ningún motor instalado
+//!
+//! El audio se devuelve en memoria; el hilo de audio lo reproduce sin volver
+//! a leer archivos. Un motor puede además entregarlo en streaming
+//! (`FlujoPcm`) para que empiece a sonar mientras se genera.
 
-use std::io::Write;
+mod espeak;
+mod mock;
+mod piper;
+
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::{Condvar, Mutex};
+use rodio::{Decoder, Source};
 
-use crate::config::{RUTA_MODELO, RUTA_PIPER, RUTA_TEMP_WAV};
+use crate::config::{self, Config, Motor, Prosodia};
+use crate::language;
+use crate::lexicon;
 use crate::logging::log;
-use crate::state::obtener_velocidad;
+use crate::profiles;
+use crate::state::{nivel_puntuacion, obtener_velocidad};
+use crate::text;
 
-// Para ocultar la ventana de Piper en Windows
+pub use espeak::MotorEspeak;
+pub use mock::MotorMock;
+pub use piper::{ruta_json_modelo, MotorPiper};
+
+// Para ocultar la ventana del motor en Windows
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 #[cfg(windows)]
 use crate::config::CREATE_NO_WINDOW;
 
-/// Genera audio WAV usando Piper TTS
-pub fn generar_audio_piper(texto: &str) -> Result<(), String> {
-    let velocidad = obtener_velocidad();
-    log(&format!(
-        "Generando audio (velocidad: {}) para: '{}'",
-        velocidad,
-        &texto[..texto.len().min(50)]
-    ));
+// ═══════════════════════════════════════════════════════════════════════════════
+// INTERFAZ DE LOS MOTORES
+// ═══════════════════════════════════════════════════════════════════════════════
 
-    let texto_limpio = texto.replace(['\r', '\n'], " ").trim().to_string();
+/// Amplitud por debajo de la cual una muestra se considera silencio.
+const UMBRAL_SILENCIO: u16 = 32;
 
-    if texto_limpio.is_empty() {
-        return Err("Texto vacío después de limpiar".to_string());
+/// Frecuencia de muestreo de los silencios generados (la de Piper en calidad
+/// media).
+const FRECUENCIA_SILENCIO: u32 = 22_050;
+
+/// Audio sintetizado: muestras PCM de 16 bits (intercaladas si hay varios canales).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    pub muestras: Vec<i16>,
+    pub frecuencia_muestreo: u32,
+    pub canales: u16,
+}
+
+impl Audio {
+    /// Silencio mono de la duración dada (rodio lo remuestrea si el audio
+    /// vecino usa otra frecuencia).
+    pub fn silencio(duracion: Duration) -> Audio {
+        let frames = duracion.as_secs_f64() * f64::from(FRECUENCIA_SILENCIO);
+        Audio {
+            muestras: vec![0; frames as usize],
+            frecuencia_muestreo: FRECUENCIA_SILENCIO,
+            canales: 1,
+        }
+    }
+
+    /// Duración del audio.
+    pub fn duracion(&self) -> Duration {
+        let frames = self.muestras.len() as u64 / u64::from(self.canales.max(1));
+        Duration::from_millis(frames * 1000 / u64::from(self.frecuencia_muestreo.max(1)))
+    }
+
+    /// Quita el silencio del final (el que Piper añade tras cada frase),
+    /// dejando `margen` para que la unión con el audio siguiente suene natural.
+    pub fn recortar_silencio_final(&mut self, margen: Duration) {
+        let canales = usize::from(self.canales.max(1));
+        let ultima_con_sonido = self
+            .muestras
+            .iter()
+            .rposition(|m| m.unsigned_abs() > UMBRAL_SILENCIO)
+            .map_or(0, |idx| idx + 1);
+        // Contar en frames para no cortar a mitad de uno
+        let frames_con_sonido = (ultima_con_sonido + canales - 1) / canales;
+        let frames_margen = (margen.as_secs_f64() * f64::from(self.frecuencia_muestreo)) as usize;
+        self.muestras
+            .truncate((frames_con_sonido + frames_margen) * canales);
+    }
+}
+
+/// Audio mono de 16 bits que el motor sigue generando: los bloques llegan por
+/// un canal según se producen. Como fuente de rodio, rellena con silencio
+/// mientras espera al siguiente bloque (sin bloquear la salida de audio, así
+/// que detener y pausar siguen respondiendo) y termina cuando el motor acaba.
+#[derive(Debug)]
+pub struct FlujoPcm {
+    frecuencia_muestreo: u32,
+    receptor: Receiver<Vec<i16>>,
+    bloque: std::vec::IntoIter<i16>,
+    fin: FinGeneracion,
+}
+
+impl FlujoPcm {
+    /// Crea un flujo vacío y el extremo por el que el motor envía los bloques.
+    /// El motor debe llamar a `FinGeneracion::marcar` al terminar.
+    pub fn nuevo(frecuencia_muestreo: u32) -> (Self, Sender<Vec<i16>>, FinGeneracion) {
+        let (emisor, receptor) = mpsc::channel();
+        let fin = FinGeneracion::default();
+        let flujo = Self {
+            frecuencia_muestreo,
+            receptor,
+            bloque: Vec::new().into_iter(),
+            fin: fin.clone(),
+        };
+        (flujo, emisor, fin)
+    }
+
+    /// Señal que se activa cuando el motor termina de generar este flujo.
+    pub fn fin_generacion(&self) -> FinGeneracion {
+        self.fin.clone()
+    }
+
+    /// Espera a que termine la generación y devuelve el audio completo.
+    pub fn completar(self) -> Audio {
+        Audio {
+            muestras: self.receptor.iter().flatten().collect(),
+            frecuencia_muestreo: self.frecuencia_muestreo,
+            canales: 1,
+        }
+    }
+}
+
+impl Iterator for FlujoPcm {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        loop {
+            if let Some(muestra) = self.bloque.next() {
+                return Some(muestra);
+            }
+            self.bloque = match self.receptor.try_recv() {
+                Ok(bloque) => bloque,
+                // 10 ms de silencio mientras el motor genera
+                Err(TryRecvError::Empty) => {
+                    vec![0; (self.frecuencia_muestreo / 100).max(1) as usize]
+                }
+                Err(TryRecvError::Disconnected) => return None,
+            }
+            .into_iter();
+        }
+    }
+}
+
+impl Source for FlujoPcm {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.frecuencia_muestreo
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Señal compartida de que un `FlujoPcm` terminó de generarse.
+#[derive(Debug, Clone, Default)]
+pub struct FinGeneracion(Arc<(Mutex<bool>, Condvar)>);
+
+impl FinGeneracion {
+    /// Marca la generación como terminada y despierta a quien espere.
+    pub fn marcar(&self) {
+        let (terminado, aviso) = &*self.0;
+        *terminado.lock() = true;
+        aviso.notify_all();
+    }
+
+    /// Bloquea hasta que la generación termine.
+    pub fn esperar(&self) {
+        let (terminado, aviso) = &*self.0;
+        let mut terminado = terminado.lock();
+        while !*terminado {
+            aviso.wait(&mut terminado);
+        }
+    }
+}
+
+/// Resultado de una síntesis lista para reproducir.
+#[derive(Debug)]
+pub enum AudioSintetizado {
+    /// Audio completo en memoria
+    Completo(Audio),
+    /// Audio que se sigue generando mientras suena
+    EnCurso(FlujoPcm),
+}
+
+impl AudioSintetizado {
+    /// Señal de fin de generación, si el audio aún se está generando.
+    pub fn fin_generacion(&self) -> Option<FinGeneracion> {
+        match self {
+            AudioSintetizado::Completo(_) => None,
+            AudioSintetizado::EnCurso(flujo) => Some(flujo.fin_generacion()),
+        }
+    }
+}
+
+/// Parámetros de una síntesis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParametrosSintesis {
+    /// Voz: modelo .onnx en Piper, nombre de voz en espeak-ng (el mock la ignora)
+    pub voz: PathBuf,
+    /// Multiplicador de velocidad (1.0 = normal, 2.0 = el doble de rápido)
+    pub velocidad: f32,
+    /// ID de hablante en modelos multi-hablante de Piper (`None` = el por
+    /// defecto del modelo; los demás motores lo ignoran)
+    pub hablante: Option<u32>,
+    /// Prosodia de Piper (los demás motores la ignoran)
+    pub prosodia: Prosodia,
+}
+
+/// Motor que convierte texto en audio.
+pub trait MotorTts {
+    /// Nombre para el log y el diagnóstico.
+    fn nombre(&self) -> &'static str;
+
+    /// Sintetiza `texto` (ya limpio y no vacío) con los parámetros dados.
+    fn sintetizar(&self, texto: &str, parametros: &ParametrosSintesis) -> Result<Audio, String>;
+
+    /// `true` si `sintetizar_en_flujo` devuelve el audio mientras se genera.
+    /// Esos motores reciben varias frases de una vez, una por línea.
+    fn en_flujo(&self) -> bool {
+        false
+    }
+
+    /// Como `sintetizar`, pero el audio puede empezar a sonar antes de estar
+    /// completo. Por defecto genera el audio entero.
+    fn sintetizar_en_flujo(
+        &self,
+        texto: &str,
+        parametros: &ParametrosSintesis,
+    ) -> Result<AudioSintetizado, String> {
+        self.sintetizar(texto, parametros)
+            .map(AudioSintetizado::Completo)
+    }
+}
+
+/// Crea el motor elegido en la configuración.
+pub fn crear_motor(config: &Config) -> Box<dyn MotorTts> {
+    match config.motor {
+        Motor::Piper => Box::new(MotorPiper {
+            ruta_piper: config.ruta_piper.clone(),
+            ruta_wav: config.ruta_temp_wav.clone(),
+            persistente: config.piper_persistente,
+            salida: config.salida_piper,
+        }),
+        Motor::Espeak => Box::new(MotorEspeak {
+            ruta_espeak: config.ruta_espeak.clone(),
+            ruta_wav: config.ruta_temp_wav.clone(),
+        }),
+        Motor::Mock => Box::new(MotorMock),
     }
+}
+
+/// Libera los recursos de los motores (los procesos persistentes de Piper).
+/// Se llama al salir de la aplicación.
+pub fn cerrar() {
+    piper::detener_trabajadores();
+}
 
-    let mut comando = Command::new(RUTA_PIPER);
+// ═══════════════════════════════════════════════════════════════════════════════
+// UTILIDADES PARA LOS MOTORES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Ejecuta un motor externo pasándole el texto por la entrada estándar y
+/// espera a que termine. `nombre` se usa en los mensajes de error.
+fn ejecutar_con_texto(comando: &mut Command, texto: &str, nombre: &str) -> Result<(), String> {
     comando
-        .arg("--model")
-        .arg(RUTA_MODELO)
-        .arg("--output_file")
-        .arg(RUTA_TEMP_WAV)
-        .arg("--length_scale")
-        .arg(velocidad.to_string())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -44,30 +301,256 @@ pub fn generar_audio_piper(texto: &str) -> Result<(), String> {
     #[cfg(windows)]
     comando.creation_flags(CREATE_NO_WINDOW);
 
-    let mut proceso = comando.spawn().map_err(|e| {
-        let msg = format!("Error iniciando Piper: {}", e);
-        log(&msg);
-        msg
-    })?;
+    let mut proceso = comando
+        .spawn()
+        .map_err(|e| format!("Error iniciando {}: {}", nombre, e))?;
 
     if let Some(ref mut stdin) = proceso.stdin {
         stdin
-            .write_all(texto_limpio.as_bytes())
-            .map_err(|e| format!("Error escribiendo a Piper: {}", e))?;
+            .write_all(texto.as_bytes())
+            .map_err(|e| format!("Error escribiendo a {}: {}", nombre, e))?;
     }
     drop(proceso.stdin.take());
 
     let output = proceso
         .wait_with_output()
-        .map_err(|e| format!("Error esperando a Piper: {}", e))?;
+        .map_err(|e| format!("Error esperando a {}: {}", nombre, e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let msg = format!("Piper error: {:?}. {}", output.status.code(), stderr);
-        log(&msg);
-        return Err(msg);
+        return Err(format!(
+            "{} error: {:?}. {}",
+            nombre,
+            output.status.code(),
+            stderr
+        ));
     }
 
-    log("Piper terminó correctamente");
     Ok(())
 }
+
+/// Lecturas que han pedido un WAV temporal, para darle a cada una el suyo.
+static WAVS_TEMPORALES: AtomicU64 = AtomicU64::new(0);
+
+/// WAV temporal propio de una lectura, junto a `base` (`ruta_temp_wav`):
+/// "tray_narrator-ana.wav" → "tray_narrator-ana-1234-7.wav", con el proceso
+/// y un contador. Dos lecturas a la vez (una vista previa durante otra
+/// lectura, dos instancias) no escriben en el mismo archivo.
+fn ruta_wav_unica(base: &Path) -> PathBuf {
+    let numero = WAVS_TEMPORALES.fetch_add(1, Ordering::SeqCst);
+    let nombre = base
+        .file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "tray_narrator".to_string());
+    base.with_file_name(format!("{}-{}-{}.wav", nombre, std::process::id(), numero))
+}
+
+/// Lee un WAV temporal a memoria y lo borra.
+fn leer_wav_temporal(ruta: &Path) -> Result<Audio, String> {
+    let audio = leer_wav(ruta);
+    let _ = std::fs::remove_file(ruta);
+    audio
+}
+
+/// Lee un archivo WAV completo a memoria.
+fn leer_wav(ruta: &Path) -> Result<Audio, String> {
+    let archivo =
+        File::open(ruta).map_err(|e| format!("Error abriendo WAV {}: {}", ruta.display(), e))?;
+    let decoder = Decoder::new(BufReader::new(archivo))
+        .map_err(|e| format!("Error decodificando WAV {}: {}", ruta.display(), e))?;
+
+    let frecuencia_muestreo = decoder.sample_rate();
+    let canales = decoder.channels();
+    Ok(Audio {
+        muestras: decoder.collect(),
+        frecuencia_muestreo,
+        canales,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SÍNTESIS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Qué se hace con el texto antes de sintetizarlo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preparacion {
+    /// Léxico, normalización y signos según la configuración
+    Completa,
+    /// Código ya verbalizado por el modo código: léxico y Unicode, pero sin
+    /// normalizar números ni leer signos, que cambiarían lo que se lee
+    Codigo,
+    /// El texto ya está escrito como debe sonar (el deletreo): se sintetiza
+    /// tal cual
+    Literal,
+}
+
+/// Emojis, léxico, normalización y signos en el idioma del tramo o, si no se
+/// detectó, en el de la voz. Los emojis van primero, antes de quitar los
+/// caracteres invisibles que los unen; el léxico antes de la normalización
+/// para poder corregir también cifras y abreviaturas, y los signos después,
+/// cuando las cifras ya no llevan puntos ni comas. El código
+/// (`Preparacion::Codigo`) se queda en el léxico.
+fn aplicar_reglas(
+    texto: &str,
+    idioma: Option<&str>,
+    voz: &Path,
+    config: &Config,
+    preparacion: Preparacion,
+) -> String {
+    let idioma_texto = idioma
+        .map(str::to_string)
+        .or_else(|| language::idioma_de_voz(voz));
+    let idioma_reglas = idioma_texto.as_deref().and_then(text::Idioma::desde_codigo);
+    let texto = text::nombrar_emojis(texto, idioma_reglas, config.emojis);
+    let texto = text::depurar_unicode(&texto);
+    let texto = lexicon::aplicar(&texto, voz, idioma_texto.as_deref());
+    match idioma_reglas {
+        Some(idioma) if preparacion == Preparacion::Completa => {
+            let normalizado = text::normalizar(&texto, idioma, &config.normalizacion);
+            text::verbalizar_signos(&normalizado, idioma, nivel_puntuacion())
+        }
+        _ => texto,
+    }
+}
+
+/// Prepara una síntesis con el motor, la voz y la velocidad actuales. Cada
+/// línea de `lineas` se limpia por separado y el texto resultante las une con
+/// saltos de línea, sin las que quedan vacías.
+fn preparar(
+    lineas: &[&str],
+    idioma: Option<&str>,
+    preparacion: Preparacion,
+) -> Result<(Box<dyn MotorTts>, String, ParametrosSintesis), String> {
+    let velocidad = obtener_velocidad();
+    log(&format!(
+        "Generando audio (velocidad: x{}) para: '{}'",
+        velocidad,
+        lineas.join(" ").chars().take(50).collect::<String>()
+    ));
+
+    let config = config::actual();
+    let motor = crear_motor(&config);
+    let voz = profiles::voz_para_idioma(idioma);
+
+    let texto_limpio = lineas
+        .iter()
+        .map(|linea| {
+            let linea = linea.replace(['\r', '\n'], " ").trim().to_string();
+            match preparacion {
+                Preparacion::Literal => linea,
+                _ => aplicar_reglas(&linea, idioma, &voz, &config, preparacion),
+            }
+        })
+        .filter(|linea| !linea.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // El léxico o los signos callados pueden dejar el texto vacío
+    if texto_limpio.trim().is_empty() {
+        return Err("Texto vacío después de limpiar".to_string());
+    }
+
+    // Un hablante desconocido no impide leer: se usa el por defecto
+    let hablante = profiles::hablante_actual(&voz).unwrap_or_else(|e| {
+        log(&format!("Se usa el hablante por defecto: {}", e));
+        None
+    });
+    let prosodia = profiles::prosodia_actual(&voz);
+    let parametros = ParametrosSintesis {
+        hablante,
+        prosodia,
+        voz,
+        velocidad,
+    };
+    Ok((motor, texto_limpio, parametros))
+}
+
+/// Sintetiza un texto con el motor, la voz y la velocidad actuales. Con
+/// `idioma`, la voz es la asignada a ese idioma en `voces_idioma`.
+pub fn sintetizar(texto: &str, idioma: Option<&str>) -> Result<Audio, String> {
+    let (motor, texto_limpio, parametros) = preparar(&[texto], idioma, Preparacion::Completa)?;
+
+    let audio = motor.sintetizar(&texto_limpio, &parametros).map_err(|e| {
+        log(&e);
+        e
+    })?;
+
+    log(&format!(
+        "{} terminó correctamente ({:.1} s de audio)",
+        motor.nombre(),
+        audio.duracion().as_secs_f32()
+    ));
+    Ok(audio)
+}
+
+/// `true` si el motor actual sintetiza en streaming (Piper con
+/// `salida_piper = "raw"`): entonces conviene pasarle varias frases juntas a
+/// `sintetizar_en_flujo` en vez de lanzarlo frase a frase.
+pub fn motor_en_flujo() -> bool {
+    crear_motor(&config::actual()).en_flujo()
+}
+
+/// Como `sintetizar`, pero devuelve el audio en streaming si el motor lo
+/// admite (Piper con `salida_piper = "raw"`). Con un motor en streaming
+/// (`motor_en_flujo`) se pueden pasar varias frases, que se leen seguidas
+/// con la pausa de fin de frase entre ellas; a los demás, una sola.
+pub fn sintetizar_en_flujo(
+    frases: &[&str],
+    idioma: Option<&str>,
+    preparacion: Preparacion,
+) -> Result<AudioSintetizado, String> {
+    let (motor, texto_limpio, parametros) = preparar(frases, idioma, preparacion)?;
+
+    let audio = motor
+        .sintetizar_en_flujo(&texto_limpio, &parametros)
+        .map_err(|e| {
+            log(&e);
+            e
+        })?;
+
+    match audio {
+        AudioSintetizado::Completo(ref completo) => log(&format!(
+            "{} terminó correctamente ({:.1} s de audio)",
+            motor.nombre(),
+            completo.duracion().as_secs_f32()
+        )),
+        AudioSintetizado::EnCurso(_) => log(&format!("{} generando en streaming", motor.nombre())),
+    }
+    Ok(audio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_temporal_por_lectura() {
+        let base = Path::new("/tmp/tray_narrator-ana.wav");
+        let primera = ruta_wav_unica(base);
+        let segunda = ruta_wav_unica(base);
+        assert_ne!(primera, segunda);
+        assert_eq!(primera.parent(), base.parent());
+        let nombre = primera.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(nombre.starts_with(&format!("tray_narrator-ana-{}-", std::process::id())));
+        assert!(nombre.ends_with(".wav"));
+    }
+
+    #[test]
+    fn el_codigo_no_se_normaliza() {
+        let lineas = text::leer_codigo("let x: u32 = a->b;", text::Idioma::Es).concat();
+        let voz = Path::new("es_ES-prueba-medium.onnx");
+        for linea in lineas {
+            assert_eq!(
+                aplicar_reglas(
+                    &linea,
+                    Some("es"),
+                    voz,
+                    &Config::default(),
+                    Preparacion::Codigo
+                ),
+                linea
+            );
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Motor espeak-ng: usa la CLI `espeak-ng`, disponible en la mayoría de
+//! distribuciones Linux y sin modelos que descargar.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::{
+    ejecutar_con_texto, leer_wav_temporal, ruta_wav_unica, Audio, MotorTts, ParametrosSintesis,
+};
+
+/// Palabras por minuto de espeak-ng a velocidad x1 (su valor por defecto)
+const PALABRAS_POR_MINUTO: f32 = 175.0;
+
+/// Límites de `-s` que admite espeak-ng
+const PALABRAS_POR_MINUTO_MINIMO: f32 = 80.0;
+const PALABRAS_POR_MINUTO_MAXIMO: f32 = 450.0;
+
+/// espeak-ng como subproceso.
+///
+/// Escribe a un WAV temporal con `-w` en vez de usar `--stdout`: por la salida
+/// estándar la cabecera no lleva la longitud real de los datos.
+pub struct MotorEspeak {
+    /// Ejecutable de espeak-ng
+    pub ruta_espeak: PathBuf,
+    /// WAV temporal donde espeak-ng deja el audio: cada lectura usa uno
+    /// propio con este nombre de base (ver `ruta_wav_unica`)
+    pub ruta_wav: PathBuf,
+}
+
+impl MotorTts for MotorEspeak {
+    fn nombre(&self) -> &'static str {
+        "espeak-ng"
+    }
+
+    fn sintetizar(&self, texto: &str, parametros: &ParametrosSintesis) -> Result<Audio, String> {
+        let palabras_por_minuto = (PALABRAS_POR_MINUTO * parametros.velocidad)
+            .clamp(PALABRAS_POR_MINUTO_MINIMO, PALABRAS_POR_MINUTO_MAXIMO);
+
+        let ruta_wav = ruta_wav_unica(&self.ruta_wav);
+        let mut comando = Command::new(&self.ruta_espeak);
+        comando
+            .arg("-v")
+            .arg(&parametros.voz)
+            .arg("-s")
+            .arg((palabras_por_minuto.round() as u32).to_string())
+            .arg("-w")
+            .arg(&ruta_wav)
+            .arg("--stdin");
+
+        if let Err(e) = ejecutar_con_texto(&mut comando, texto, self.nombre()) {
+            let _ = std::fs::remove_file(&ruta_wav);
+            return Err(e);
+        }
+        leer_wav_temporal(&ruta_wav)
+    }
+}
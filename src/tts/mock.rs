@@ -0,0 +1,55 @@
+//! Motor de pruebas: convierte cada carácter en un tono, sin ningún motor
+//! externo. El mismo texto y velocidad producen siempre el mismo audio.
+
+use std::f32::consts::PI;
+
+use super::{Audio, MotorTts, ParametrosSintesis};
+
+/// Frecuencia de muestreo del audio generado (la de los modelos medium de Piper)
+const FRECUENCIA_MUESTREO: u32 = 22_050;
+
+/// Duración de cada carácter a velocidad x1
+const DURACION_CARACTER_MS: f32 = 60.0;
+
+/// Amplitud de los tonos (fracción del máximo de i16)
+const AMPLITUD: f32 = 0.25;
+
+/// Frecuencia del tono más grave y separación entre tonos
+const FRECUENCIA_BASE_HZ: f32 = 220.0;
+const PASO_FRECUENCIA_HZ: f32 = 20.0;
+
+/// Tonos deterministas: un tono por carácter y silencio en los espacios.
+pub struct MotorMock;
+
+impl MotorTts for MotorMock {
+    fn nombre(&self) -> &'static str {
+        "mock"
+    }
+
+    fn sintetizar(&self, texto: &str, parametros: &ParametrosSintesis) -> Result<Audio, String> {
+        let muestras_por_caracter = (FRECUENCIA_MUESTREO as f32 * DURACION_CARACTER_MS
+            / 1000.0
+            / parametros.velocidad) as usize;
+
+        let mut muestras = Vec::with_capacity(texto.chars().count() * muestras_por_caracter);
+        for caracter in texto.chars() {
+            if caracter.is_whitespace() {
+                muestras.extend(std::iter::repeat(0).take(muestras_por_caracter));
+                continue;
+            }
+
+            let frecuencia =
+                FRECUENCIA_BASE_HZ + (caracter as u32 % 32) as f32 * PASO_FRECUENCIA_HZ;
+            muestras.extend((0..muestras_por_caracter).map(|n| {
+                let t = n as f32 / FRECUENCIA_MUESTREO as f32;
+                ((2.0 * PI * frecuencia * t).sin() * AMPLITUD * i16::MAX as f32) as i16
+            }));
+        }
+
+        Ok(Audio {
+            muestras,
+            frecuencia_muestreo: FRECUENCIA_MUESTREO,
+            canales: 1,
+        })
+    }
+}
@@ -0,0 +1,629 @@
+//! Motor Piper TTS.
+//!
+//! Por defecto mantiene un proceso de Piper abierto en modo `--json-input`:
+//! el modelo ONNX se carga una sola vez y cada lectura es una línea JSON por
+//! la entrada estándar. Con `--output_dir`, Piper escribe cada lectura en un
+//! WAV nuevo de esa carpeta y responde con su ruta por la salida estándar.
+//! La CLI de Piper solo lee la velocidad (`length_scale`) y la prosodia al
+//! arrancar, así que se mantiene un proceso por combinación de voz, velocidad
+//! y prosodia (hasta `MAX_TRABAJADORES`, cerrando el usado hace más tiempo):
+//! alternar voces o volver a una velocidad reciente no recarga el modelo. Si
+//! un proceso muere o deja de responder (`ESPERA_PETICION`) se mata y se
+//! relanza, y todos se cierran al salir de la aplicación
+//! (`detener_trabajadores`).
+//!
+//! Con `piper_persistente = false` se lanza un proceso por lectura, como en
+//! versiones anteriores.
+//!
+//! Con `salida_piper = "raw"` Piper escribe PCM crudo por la salida estándar
+//! (`--output-raw`) y el audio se reproduce según llega, sin tocar el disco.
+//! La salida cruda no marca dónde acaba cada lectura, así que no se usa el
+//! proceso persistente: el narrador pasa de una vez todas las frases seguidas
+//! con la misma voz (hasta el fin de párrafo), una por línea, y un mismo
+//! Piper las sintetiza una tras otra mientras suenan las primeras.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStderr, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use super::{
+    ejecutar_con_texto, leer_wav_temporal, ruta_wav_unica, Audio, AudioSintetizado, FlujoPcm,
+    MotorTts, ParametrosSintesis,
+};
+use crate::config::{Prosodia, SalidaPiper};
+use crate::logging::log;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+#[cfg(windows)]
+use crate::config::CREATE_NO_WINDOW;
+
+/// Líneas de stderr de Piper que se conservan para los mensajes de error
+const LINEAS_STDERR: usize = 20;
+
+/// Tiempo que se da a Piper para terminar tras cerrar su entrada estándar
+const ESPERA_CIERRE: Duration = Duration::from_secs(2);
+
+/// Procesos persistentes abiertos a la vez como máximo: uno por voz y
+/// velocidad, para alternar voces (tramos en otro idioma) o velocidades sin
+/// recargar el modelo
+const MAX_TRABAJADORES: usize = 3;
+
+/// Tiempo máximo de una petición al proceso persistente (incluida la carga
+/// del modelo en la primera). Pasado, se da por colgado y se relanza.
+const ESPERA_PETICION: Duration = Duration::from_secs(60);
+
+/// Tamaño de lectura de la salida cruda (bytes): unos 90 ms a 22050 Hz
+const TAMANO_BLOQUE_RAW: usize = 4096;
+
+/// Piper TTS: escribe el audio en un WAV temporal que luego se lee a memoria.
+pub struct MotorPiper {
+    /// Ejecutable de Piper
+    pub ruta_piper: PathBuf,
+    /// WAV temporal donde Piper deja el audio: cada lectura usa uno propio
+    /// con este nombre de base (ver `ruta_wav_unica`). En modo persistente
+    /// solo se usa su carpeta.
+    pub ruta_wav: PathBuf,
+    /// Reutilizar un proceso de Piper entre lecturas (solo con salida WAV)
+    pub persistente: bool,
+    /// WAV temporal o PCM crudo en streaming
+    pub salida: SalidaPiper,
+}
+
+impl MotorTts for MotorPiper {
+    fn nombre(&self) -> &'static str {
+        "Piper"
+    }
+
+    fn en_flujo(&self) -> bool {
+        self.salida == SalidaPiper::Raw
+    }
+
+    fn sintetizar(&self, texto: &str, parametros: &ParametrosSintesis) -> Result<Audio, String> {
+        if self.salida == SalidaPiper::Raw {
+            return self
+                .sintetizar_raw(texto, parametros)
+                .map(FlujoPcm::completar);
+        }
+
+        // Piper controla la duración de los fonemas: length_scale = 1 / multiplicador
+        let clave = ClaveTrabajador {
+            ruta_piper: self.ruta_piper.clone(),
+            modelo: parametros.voz.clone(),
+            length_scale: (1.0 / parametros.velocidad).to_string(),
+            prosodia: parametros.prosodia,
+            dir_salida: self
+                .ruta_wav
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+        };
+
+        if !self.persistente {
+            let ruta_wav = ruta_wav_unica(&self.ruta_wav);
+            let mut comando = clave.comando();
+            comando.arg("--output_file").arg(&ruta_wav);
+            argumento_hablante(&mut comando, parametros.hablante);
+            if let Err(e) = ejecutar_con_texto(&mut comando, texto, self.nombre()) {
+                let _ = fs::remove_file(&ruta_wav);
+                return Err(e);
+            }
+            return leer_wav_temporal(&ruta_wav);
+        }
+
+        let ruta_generada = sintetizar_persistente(&clave, texto, parametros.hablante)?;
+        leer_wav_temporal(&ruta_generada)
+    }
+
+    fn sintetizar_en_flujo(
+        &self,
+        texto: &str,
+        parametros: &ParametrosSintesis,
+    ) -> Result<AudioSintetizado, String> {
+        match self.salida {
+            SalidaPiper::Raw => self
+                .sintetizar_raw(texto, parametros)
+                .map(AudioSintetizado::EnCurso),
+            SalidaPiper::Wav => self
+                .sintetizar(texto, parametros)
+                .map(AudioSintetizado::Completo),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SALIDA CRUDA (STREAMING)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Añade `--speaker` si se ha elegido un hablante.
+fn argumento_hablante(comando: &mut Command, hablante: Option<u32>) {
+    if let Some(id) = hablante {
+        comando.arg("--speaker").arg(id.to_string());
+    }
+}
+
+/// Añade `--noise_scale`, `--noise_w` y `--sentence_silence` para los
+/// valores definidos; los demás quedan con el valor por defecto de Piper.
+fn argumentos_prosodia(comando: &mut Command, prosodia: &Prosodia) {
+    let argumentos = [
+        ("--noise_scale", prosodia.noise_scale),
+        ("--noise_w", prosodia.noise_w),
+        ("--sentence_silence", prosodia.sentence_silence),
+    ];
+    for (nombre, valor) in argumentos {
+        if let Some(valor) = valor {
+            comando.arg(nombre).arg(valor.to_string());
+        }
+    }
+}
+
+/// Ruta de la configuración JSON que acompaña a un modelo (`.onnx.json`).
+pub fn ruta_json_modelo(modelo: &Path) -> PathBuf {
+    let mut nombre = modelo.as_os_str().to_owned();
+    nombre.push(".json");
+    PathBuf::from(nombre)
+}
+
+/// Frecuencia de muestreo del modelo (`audio.sample_rate` en su `.onnx.json`).
+/// La salida cruda no lleva cabecera, así que hay que saberla de antemano.
+fn frecuencia_modelo(modelo: &Path) -> Result<u32, String> {
+    let json = ruta_json_modelo(modelo);
+    let contenido = fs::read_to_string(&json)
+        .map_err(|e| format!("Error leyendo {}: {}", json.display(), e))?;
+    let valor: serde_json::Value = serde_json::from_str(&contenido)
+        .map_err(|e| format!("{} no es JSON válido: {}", json.display(), e))?;
+
+    valor["audio"]["sample_rate"]
+        .as_u64()
+        .and_then(|f| u32::try_from(f).ok())
+        .filter(|&f| f > 0)
+        .ok_or_else(|| format!("{} no indica audio.sample_rate", json.display()))
+}
+
+impl MotorPiper {
+    /// Lanza Piper con `--output-raw` y devuelve el flujo que se va llenando
+    /// con su salida. Cada línea de `texto` es una frase; el modelo se carga
+    /// una vez para todas. Un hilo lee la salida estándar hasta que Piper
+    /// termina.
+    fn sintetizar_raw(
+        &self,
+        texto: &str,
+        parametros: &ParametrosSintesis,
+    ) -> Result<FlujoPcm, String> {
+        let frecuencia = frecuencia_modelo(&parametros.voz)?;
+
+        let mut comando = Command::new(&self.ruta_piper);
+        comando
+            .arg("--model")
+            .arg(&parametros.voz)
+            .arg("--length_scale")
+            .arg((1.0 / parametros.velocidad).to_string())
+            .arg("--output-raw");
+        argumentos_prosodia(&mut comando, &parametros.prosodia);
+        argumento_hablante(&mut comando, parametros.hablante);
+        comando
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        comando.creation_flags(CREATE_NO_WINDOW);
+
+        let mut proceso = comando
+            .spawn()
+            .map_err(|e| format!("Error iniciando Piper: {}", e))?;
+
+        let mut stdin = proceso.stdin.take().ok_or("Piper sin entrada estándar")?;
+        let stderr = proceso.stderr.take().ok_or("Piper sin stderr")?;
+        let errores = vaciar_stderr(stderr);
+
+        // Piper sintetiza cada línea de la entrada por separado, en orden, y
+        // solo lee la siguiente al terminar la anterior: con muchas frases la
+        // escritura se bloquearía, así que va en su propio hilo
+        let texto = texto.to_string();
+        thread::spawn(move || {
+            if let Err(e) = writeln!(stdin, "{}", texto) {
+                log(&format!("Error escribiendo a Piper: {}", e));
+            }
+        });
+
+        let (flujo, emisor, fin) = FlujoPcm::nuevo(frecuencia);
+        thread::spawn(move || {
+            leer_salida_raw(proceso, emisor, &errores);
+            fin.marcar();
+        });
+        Ok(flujo)
+    }
+}
+
+/// Lee el stderr de Piper en un hilo y guarda las últimas `LINEAS_STDERR`
+/// líneas para los mensajes de error. Hay que vaciarlo aunque nadie lo lea
+/// o Piper se bloquea al llenarse la tubería (escribe una línea por frase).
+fn vaciar_stderr(stderr: ChildStderr) -> Arc<Mutex<VecDeque<String>>> {
+    let errores = Arc::new(Mutex::new(VecDeque::with_capacity(LINEAS_STDERR)));
+    let errores_hilo = Arc::clone(&errores);
+    thread::spawn(move || {
+        for linea in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let mut errores = errores_hilo.lock();
+            if errores.len() == LINEAS_STDERR {
+                errores.pop_front();
+            }
+            errores.push_back(linea);
+        }
+    });
+    errores
+}
+
+/// Últimas líneas de stderr unidas, para los mensajes de error.
+fn unir_errores(errores: &Mutex<VecDeque<String>>) -> String {
+    let errores = errores.lock();
+    errores.iter().cloned().collect::<Vec<_>>().join(" | ")
+}
+
+/// Pasa la salida cruda de Piper (PCM de 16 bits little-endian) al flujo en
+/// bloques. Si el flujo se descarta (lectura cancelada) termina el proceso.
+fn leer_salida_raw(
+    mut proceso: Child,
+    emisor: Sender<Vec<i16>>,
+    errores: &Mutex<VecDeque<String>>,
+) {
+    let Some(mut stdout) = proceso.stdout.take() else {
+        return;
+    };
+
+    let mut buffer = [0u8; TAMANO_BLOQUE_RAW];
+    // Byte suelto de una muestra partida entre dos lecturas
+    let mut pendiente: Option<u8> = None;
+
+    loop {
+        let leidos = match stdout.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) => {
+                log(&format!("Error leyendo la salida de Piper: {}", e));
+                break;
+            }
+        };
+
+        let mut bytes = Vec::with_capacity(leidos + 1);
+        bytes.extend(pendiente.take());
+        bytes.extend_from_slice(&buffer[..leidos]);
+        if bytes.len() % 2 == 1 {
+            pendiente = bytes.pop();
+        }
+
+        let muestras = bytes
+            .chunks_exact(2)
+            .map(|par| i16::from_le_bytes([par[0], par[1]]))
+            .collect();
+        if emisor.send(muestras).is_err() {
+            log("Lectura cancelada: terminando Piper");
+            let _ = proceso.kill();
+            let _ = proceso.wait();
+            return;
+        }
+    }
+
+    match proceso.wait() {
+        Ok(estado) if !estado.success() => log(&format!(
+            "Piper error: {:?}. {}",
+            estado.code(),
+            unir_errores(errores)
+        )),
+        Ok(_) => {}
+        Err(e) => log(&format!("Error esperando a Piper: {}", e)),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROCESO PERSISTENTE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Argumentos con los que se lanza Piper. Si cambian (otra voz, otra
+/// velocidad, otra prosodia, otro ejecutable) hace falta otro proceso: la
+/// CLI de Piper solo lee length_scale y la prosodia al arrancar.
+#[derive(Debug, Clone, PartialEq)]
+struct ClaveTrabajador {
+    ruta_piper: PathBuf,
+    modelo: PathBuf,
+    length_scale: String,
+    prosodia: Prosodia,
+    /// Carpeta donde el proceso persistente crea un WAV por petición
+    dir_salida: PathBuf,
+}
+
+impl ClaveTrabajador {
+    /// Comando de Piper con el modelo, la velocidad y la prosodia, sin la
+    /// salida.
+    fn comando(&self) -> Command {
+        let mut comando = Command::new(&self.ruta_piper);
+        comando
+            .arg("--model")
+            .arg(&self.modelo)
+            .arg("--length_scale")
+            .arg(&self.length_scale);
+        argumentos_prosodia(&mut comando, &self.prosodia);
+        comando
+    }
+}
+
+/// Proceso de Piper en modo `--json-input` y los hilos que leen su salida.
+struct TrabajadorPiper {
+    clave: ClaveTrabajador,
+    proceso: Child,
+    /// `None` una vez cerrada para pedirle a Piper que termine
+    stdin: Option<ChildStdin>,
+    /// Líneas de stdout: Piper escribe la ruta del WAV al terminar cada petición
+    salida: Receiver<String>,
+    /// Últimas líneas de stderr, para explicar los fallos
+    errores: Arc<Mutex<VecDeque<String>>>,
+}
+
+/// Procesos libres, del usado hace más tiempo al más reciente. Una vez
+/// cerrada al salir, los que vuelven de una petición se cierran en vez de
+/// quedarse en la lista.
+struct Reserva<T> {
+    libres: Vec<T>,
+    cerrada: bool,
+}
+
+impl<T> Reserva<T> {
+    fn nueva() -> Self {
+        Self {
+            libres: Vec::new(),
+            cerrada: false,
+        }
+    }
+
+    /// Saca el primero que cumpla `buscado`.
+    fn sacar(&mut self, buscado: impl Fn(&T) -> bool) -> Option<T> {
+        let posicion = self.libres.iter().position(buscado)?;
+        Some(self.libres.remove(posicion))
+    }
+
+    /// Devuelve uno a la lista. Devuelve los que hay que cerrar: los que
+    /// sobran por encima de `MAX_TRABAJADORES`, empezando por el usado hace
+    /// más tiempo, o el propio devuelto si la reserva ya está cerrada.
+    fn devolver(&mut self, libre: T) -> Vec<T> {
+        if self.cerrada {
+            return vec![libre];
+        }
+        self.libres.push(libre);
+        let exceso = self.libres.len().saturating_sub(MAX_TRABAJADORES);
+        self.libres.drain(..exceso).collect()
+    }
+
+    /// Cierra la reserva y devuelve los libres, que hay que cerrar.
+    fn cerrar(&mut self) -> Vec<T> {
+        self.cerrada = true;
+        std::mem::take(&mut self.libres)
+    }
+}
+
+lazy_static! {
+    /// Procesos de Piper libres. Una petición saca su proceso de la lista
+    /// mientras lo usa, así que el Mutex solo se toma para sacarlo y
+    /// devolverlo, nunca durante la síntesis.
+    static ref TRABAJADORES: Mutex<Reserva<TrabajadorPiper>> = Mutex::new(Reserva::nueva());
+}
+
+impl TrabajadorPiper {
+    /// Lanza Piper y los hilos que vacían su stdout y su stderr.
+    fn lanzar(clave: &ClaveTrabajador) -> Result<Self, String> {
+        let mut comando = clave.comando();
+        comando
+            .arg("--output_dir")
+            .arg(&clave.dir_salida)
+            .arg("--json-input")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        #[cfg(windows)]
+        comando.creation_flags(CREATE_NO_WINDOW);
+
+        let mut proceso = comando
+            .spawn()
+            .map_err(|e| format!("Error iniciando Piper: {}", e))?;
+
+        let stdin = proceso.stdin.take();
+        let stdout = proceso.stdout.take().ok_or("Piper sin stdout")?;
+        let stderr = proceso.stderr.take().ok_or("Piper sin stderr")?;
+
+        // stdout: una línea por petición atendida. Al morir Piper el canal se
+        // cierra y la espera en `sintetizar` termina con error.
+        let (enviar_salida, salida) = mpsc::channel();
+        thread::spawn(move || {
+            for linea in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if enviar_salida.send(linea).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let errores = vaciar_stderr(stderr);
+
+        log(&format!(
+            "Piper persistente iniciado (modelo: {}, length_scale: {})",
+            clave.modelo.display(),
+            clave.length_scale
+        ));
+
+        Ok(Self {
+            clave: clave.clone(),
+            proceso,
+            stdin,
+            salida,
+            errores,
+        })
+    }
+
+    /// Indica si el proceso sigue en marcha.
+    fn vivo(&mut self) -> bool {
+        matches!(self.proceso.try_wait(), Ok(None))
+    }
+
+    /// Envía una petición y espera a que Piper devuelva la ruta del WAV escrito.
+    /// El hablante va en cada petición, así que cambiarlo no relanza Piper. Si
+    /// no responde en `ESPERA_PETICION`, mata el proceso y devuelve un error.
+    fn sintetizar(&mut self, texto: &str, hablante: Option<u32>) -> Result<PathBuf, String> {
+        let mut peticion = serde_json::json!({ "text": texto });
+        if let Some(id) = hablante {
+            peticion["speaker_id"] = id.into();
+        }
+
+        let stdin = self.stdin.as_mut().ok_or("Piper ya está cerrado")?;
+        writeln!(stdin, "{}", peticion)
+            .and_then(|_| stdin.flush())
+            .map_err(|e| {
+                format!(
+                    "Error escribiendo a Piper: {}. {}",
+                    e,
+                    unir_errores(&self.errores)
+                )
+            })?;
+
+        match self.salida.recv_timeout(ESPERA_PETICION) {
+            Ok(ruta) => Ok(PathBuf::from(ruta)),
+            Err(RecvTimeoutError::Timeout) => {
+                let _ = self.proceso.kill();
+                let _ = self.proceso.wait();
+                Err(format!(
+                    "Piper no respondió en {} s: proceso terminado. {}",
+                    ESPERA_PETICION.as_secs(),
+                    unir_errores(&self.errores)
+                ))
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(format!(
+                "Piper terminó inesperadamente. {}",
+                unir_errores(&self.errores)
+            )),
+        }
+    }
+}
+
+impl Drop for TrabajadorPiper {
+    /// Cierra la entrada estándar para que Piper termine por sí mismo y, si
+    /// no lo hace a tiempo, lo mata.
+    fn drop(&mut self) {
+        drop(self.stdin.take());
+
+        let limite = Instant::now() + ESPERA_CIERRE;
+        while Instant::now() < limite {
+            if !matches!(self.proceso.try_wait(), Ok(None)) {
+                log("Piper persistente cerrado");
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        let _ = self.proceso.kill();
+        let _ = self.proceso.wait();
+        log("Piper persistente no terminó a tiempo: proceso terminado a la fuerza");
+    }
+}
+
+/// Saca de la lista el proceso libre de esa clave o, si no hay o ha muerto,
+/// lanza uno nuevo. La carga del modelo se hace con el Mutex ya soltado.
+fn tomar_trabajador(clave: &ClaveTrabajador) -> Result<TrabajadorPiper, String> {
+    let libre = TRABAJADORES.lock().sacar(|t| t.clave == *clave);
+    match libre {
+        Some(mut trabajador) => {
+            if trabajador.vivo() {
+                return Ok(trabajador);
+            }
+            drop(trabajador);
+            TrabajadorPiper::lanzar(clave)
+        }
+        None => TrabajadorPiper::lanzar(clave),
+    }
+}
+
+/// Devuelve un proceso a la lista de libres y cierra los que sobren por
+/// encima de `MAX_TRABAJADORES`, empezando por el usado hace más tiempo. Si
+/// la aplicación ya está saliendo se cierra el propio proceso.
+fn devolver_trabajador(trabajador: TrabajadorPiper) {
+    // Se cierran con el Mutex ya soltado: cerrar espera a que Piper termine
+    let sobrantes = TRABAJADORES.lock().devolver(trabajador);
+    drop(sobrantes);
+}
+
+/// Sintetiza con el proceso persistente de esa clave, lanzándolo si hace
+/// falta, y devuelve la ruta del WAV generado. Si Piper muere o se cuelga
+/// durante la petición se reintenta una vez con un proceso nuevo.
+fn sintetizar_persistente(
+    clave: &ClaveTrabajador,
+    texto: &str,
+    hablante: Option<u32>,
+) -> Result<PathBuf, String> {
+    let mut intentos_restantes = 2;
+    loop {
+        intentos_restantes -= 1;
+
+        let mut trabajador = tomar_trabajador(clave)?;
+
+        match trabajador.sintetizar(texto, hablante) {
+            Ok(ruta) => {
+                devolver_trabajador(trabajador);
+                return Ok(ruta);
+            }
+            Err(e) => {
+                log(&format!("Error en Piper persistente: {}", e));
+                drop(trabajador);
+                if intentos_restantes == 0 {
+                    return Err(e);
+                }
+                log("Relanzando Piper...");
+            }
+        }
+    }
+}
+
+/// Cierra los procesos persistentes de Piper libres, si los hay. Uno que esté
+/// atendiendo una petición no está en la lista: se cierra al devolverlo.
+pub fn detener_trabajadores() {
+    let trabajadores = TRABAJADORES.lock().cerrar();
+    drop(trabajadores);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserva_cierra_los_sobrantes() {
+        let mut reserva = Reserva::nueva();
+        for n in 1..=MAX_TRABAJADORES {
+            assert!(reserva.devolver(n).is_empty());
+        }
+        // El usado hace más tiempo es el que sobra
+        assert_eq!(reserva.devolver(10), vec![1]);
+        assert_eq!(reserva.sacar(|&n| n == 2), Some(2));
+        assert_eq!(reserva.sacar(|&n| n == 2), None);
+        assert!(reserva.devolver(2).is_empty());
+        assert_eq!(reserva.libres, vec![3, 10, 2]);
+    }
+
+    #[test]
+    fn reserva_cerrada_no_guarda_los_devueltos() {
+        let mut reserva = Reserva::nueva();
+        reserva.devolver(1);
+        let en_uso = reserva.sacar(|&n| n == 1).unwrap();
+        reserva.devolver(2);
+
+        // Al salir se cierran los libres; el que estaba en uso, al volver
+        assert_eq!(reserva.cerrar(), vec![2]);
+        assert_eq!(reserva.devolver(en_uso), vec![1]);
+        assert!(reserva.libres.is_empty());
+    }
+}
@@ -0,0 +1,481 @@
+//! Catálogo de voces de Piper instaladas.
+//!
+//! Se buscan pares `*.onnx` + `*.onnx.json` en `dir_voces` y se leen sus
+//! metadatos para el submenú "Voz" del tray. La voz elegida allí se recuerda
+//! entre reinicios y tiene prioridad sobre la de la configuración y la del
+//! perfil, hasta que se activa un perfil con voz propia.
+//!
+//! Los modelos con varios hablantes (`speaker_id_map`) permiten además elegir
+//! hablante por nombre; la elección se recuerda para cada modelo.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::Deserialize;
+
+use crate::config::{self, Config, Prosodia};
+use crate::logging::log;
+use crate::persistence;
+use crate::tts::ruta_json_modelo;
+
+/// Voz instalada, con los metadatos de su `.onnx.json`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfoVoz {
+    /// Ruta del modelo `.onnx`
+    pub ruta: PathBuf,
+    /// Nombre del archivo sin extensión (`es_ES-sharvard-medium`)
+    pub nombre: String,
+    /// Código de idioma (`es_ES`)
+    pub idioma: String,
+    /// Nombre legible del idioma (`Español`), o el código si no lo indica
+    pub nombre_idioma: String,
+    /// Conjunto de datos con el que se entrenó (`sharvard`)
+    pub dataset: String,
+    /// Calidad (`x_low`, `low`, `medium`, `high`), si la indica
+    pub calidad: Option<String>,
+    /// Frecuencia de muestreo en Hz
+    pub frecuencia_muestreo: u32,
+    /// Número de hablantes del modelo (1 = una sola voz)
+    pub num_hablantes: usize,
+    /// Hablantes por nombre, ordenados por ID (vacío si solo hay uno)
+    pub hablantes: Vec<Hablante>,
+    /// Prosodia recomendada por el modelo (sección `inference`)
+    pub prosodia: Prosodia,
+}
+
+/// Hablante de un modelo multi-hablante.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hablante {
+    /// Nombre en `speaker_id_map` (o el ID si el modelo no trae nombres)
+    pub nombre: String,
+    /// ID que se pasa a Piper
+    pub id: u32,
+}
+
+impl InfoVoz {
+    /// Texto para el menú: "sharvard (medium, 2 hablantes)".
+    pub fn etiqueta(&self) -> String {
+        let mut detalles: Vec<String> = self.calidad.iter().cloned().collect();
+        if self.num_hablantes > 1 {
+            detalles.push(format!("{} hablantes", self.num_hablantes));
+        }
+        if detalles.is_empty() {
+            self.dataset.clone()
+        } else {
+            format!("{} ({})", self.dataset, detalles.join(", "))
+        }
+    }
+
+    /// Texto para el grupo de idioma: "Español (es_ES)".
+    pub fn etiqueta_idioma(&self) -> String {
+        if self.nombre_idioma == self.idioma {
+            self.idioma.clone()
+        } else {
+            format!("{} ({})", self.nombre_idioma, self.idioma)
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// METADATOS DEL MODELO
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Parte del `.onnx.json` que interesa. Los modelos antiguos no traen
+/// `language` ni `dataset`, así que todo es opcional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetadatosModelo {
+    audio: MetadatosAudio,
+    dataset: Option<String>,
+    language: Option<MetadatosIdioma>,
+    espeak: Option<MetadatosEspeak>,
+    num_speakers: Option<usize>,
+    speaker_id_map: HashMap<String, u32>,
+    inference: MetadatosInferencia,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetadatosAudio {
+    sample_rate: u32,
+    quality: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetadatosIdioma {
+    code: String,
+    name_native: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetadatosEspeak {
+    voice: String,
+}
+
+/// Valores con los que se entrenó el modelo. `sentence_silence` no aparece
+/// aquí: si nadie lo configura, se usa el valor por defecto de Piper.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetadatosInferencia {
+    noise_scale: Option<f32>,
+    noise_w: Option<f32>,
+}
+
+/// Lee los metadatos de un modelo a partir de su `.onnx.json`.
+pub fn leer_info(modelo: &Path) -> Result<InfoVoz, String> {
+    let json = ruta_json_modelo(modelo);
+    let contenido = fs::read_to_string(&json)
+        .map_err(|e| format!("Error leyendo {}: {}", json.display(), e))?;
+    parsear_info(modelo, &contenido).map_err(|e| format!("{} no es válido: {}", json.display(), e))
+}
+
+/// Interpreta el contenido del `.onnx.json` de `modelo`.
+fn parsear_info(modelo: &Path, contenido: &str) -> Result<InfoVoz, String> {
+    let metadatos: MetadatosModelo = serde_json::from_str(contenido).map_err(|e| e.to_string())?;
+
+    let nombre = modelo
+        .file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let idioma = metadatos
+        .language
+        .as_ref()
+        .map(|l| l.code.clone())
+        .filter(|c| !c.is_empty())
+        .or_else(|| metadatos.espeak.as_ref().map(|e| e.voice.clone()))
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "?".to_string());
+    let nombre_idioma = metadatos
+        .language
+        .as_ref()
+        .and_then(|l| l.name_native.clone())
+        .unwrap_or_else(|| idioma.clone());
+
+    let num_hablantes = metadatos
+        .num_speakers
+        .unwrap_or(1)
+        .max(metadatos.speaker_id_map.len())
+        .max(1);
+    let mut hablantes: Vec<Hablante> = metadatos
+        .speaker_id_map
+        .into_iter()
+        .map(|(nombre, id)| Hablante { nombre, id })
+        .collect();
+    if hablantes.is_empty() && num_hablantes > 1 {
+        // Sin nombres: se eligen por número
+        hablantes = (0..num_hablantes as u32)
+            .map(|id| Hablante {
+                nombre: id.to_string(),
+                id,
+            })
+            .collect();
+    }
+    hablantes.sort_by_key(|h| h.id);
+    if num_hablantes == 1 {
+        hablantes.clear();
+    }
+
+    Ok(InfoVoz {
+        ruta: modelo.to_path_buf(),
+        dataset: metadatos.dataset.unwrap_or_else(|| nombre.clone()),
+        nombre,
+        idioma,
+        nombre_idioma,
+        calidad: metadatos.audio.quality,
+        frecuencia_muestreo: metadatos.audio.sample_rate,
+        num_hablantes,
+        hablantes,
+        prosodia: Prosodia {
+            noise_scale: metadatos.inference.noise_scale,
+            noise_w: metadatos.inference.noise_w,
+            sentence_silence: None,
+        },
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CATÁLOGO
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Metadatos leídos de un `.onnx.json` (o `None` si no se pudo), con la
+/// fecha de modificación del archivo al leerlo.
+type InfoLeida = (Option<SystemTime>, Option<InfoVoz>);
+
+lazy_static! {
+    /// Voces encontradas en el último escaneo, ordenadas por idioma y nombre.
+    static ref CATALOGO: RwLock<Vec<InfoVoz>> = RwLock::new(Vec::new());
+    /// Modelo elegido en el tray (`None` = el de la configuración o el perfil).
+    static ref VOZ_ELEGIDA: RwLock<Option<PathBuf>> = RwLock::new(None);
+    /// Hablante elegido en el tray para cada modelo.
+    static ref HABLANTES_ELEGIDOS: RwLock<BTreeMap<PathBuf, String>> =
+        RwLock::new(BTreeMap::new());
+    /// Metadatos de los modelos que no están en el catálogo (p. ej. uno
+    /// pasado con `--voice`).
+    static ref FUERA_DE_CATALOGO: RwLock<HashMap<PathBuf, InfoLeida>> =
+        RwLock::new(HashMap::new());
+}
+
+/// Busca las voces de `dir_voces` y añade `ruta_modelo` si está fuera de esa
+/// carpeta. Los modelos sin `.onnx.json` legible se omiten (y se registran).
+fn escanear(config: &Config) -> Vec<InfoVoz> {
+    let mut modelos: Vec<PathBuf> = match fs::read_dir(&config.dir_voces) {
+        Ok(entradas) => entradas
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|ruta| ruta.extension().is_some_and(|ext| ext == "onnx") && ruta.is_file())
+            .collect(),
+        Err(e) => {
+            log(&format!(
+                "No se pudo leer la carpeta de voces {}: {}",
+                config.dir_voces.display(),
+                e
+            ));
+            Vec::new()
+        }
+    };
+    if config.ruta_modelo.is_file() && !modelos.contains(&config.ruta_modelo) {
+        modelos.push(config.ruta_modelo.clone());
+    }
+
+    let mut voces: Vec<InfoVoz> = modelos
+        .iter()
+        .filter_map(|modelo| match leer_info(modelo) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                log(&format!("Voz omitida: {}", e));
+                None
+            }
+        })
+        .collect();
+    voces.sort_by(|a, b| {
+        (&a.nombre_idioma, &a.idioma, &a.nombre).cmp(&(&b.nombre_idioma, &b.idioma, &b.nombre))
+    });
+    voces
+}
+
+/// Vuelve a escanear las voces instaladas. Devuelve `true` si el catálogo
+/// ha cambiado.
+pub fn actualizar_catalogo() -> bool {
+    let voces = escanear(&config::actual());
+    let mut catalogo = CATALOGO.write();
+    if *catalogo == voces {
+        return false;
+    }
+    log(&format!("Catálogo de voces: {} voces", voces.len()));
+    *catalogo = voces;
+    true
+}
+
+/// Copia del catálogo actual.
+pub fn catalogo() -> Vec<InfoVoz> {
+    CATALOGO.read().clone()
+}
+
+/// Metadatos de un modelo: del catálogo si está en él, o de su
+/// `.onnx.json` (p. ej. un modelo pasado con `--voice`). El tray lo consulta
+/// a cada vuelta, así que el archivo solo se vuelve a leer si cambia.
+pub fn info_voz(modelo: &Path) -> Option<InfoVoz> {
+    if let Some(info) = CATALOGO.read().iter().find(|v| v.ruta == modelo) {
+        return Some(info.clone());
+    }
+
+    let modificacion = config::fecha_modificacion(&ruta_json_modelo(modelo));
+    if let Some((fecha, info)) = FUERA_DE_CATALOGO.read().get(modelo) {
+        if *fecha == modificacion {
+            return info.clone();
+        }
+    }
+    let info = leer_info(modelo).ok();
+    FUERA_DE_CATALOGO
+        .write()
+        .insert(modelo.to_path_buf(), (modificacion, info.clone()));
+    info
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// VOZ ELEGIDA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Modelo elegido en el tray, si lo hay.
+pub fn voz_elegida() -> Option<PathBuf> {
+    VOZ_ELEGIDA.read().clone()
+}
+
+/// Elige una voz desde el tray y la recuerda para el próximo arranque.
+pub fn elegir_voz(ruta: &Path) {
+    *VOZ_ELEGIDA.write() = Some(ruta.to_path_buf());
+    persistence::guardar_voz(Some(ruta));
+    log(&format!("Voz elegida: {}", ruta.display()));
+}
+
+/// Olvida la voz elegida en el tray: vuelve a mandar la del perfil o la
+/// configuración.
+pub fn olvidar_voz() {
+    if VOZ_ELEGIDA.write().take().is_some() {
+        persistence::guardar_voz(None);
+    }
+}
+
+/// Restaura la voz guardada al arrancar, si el modelo sigue existiendo.
+pub fn restaurar_voz(ruta: PathBuf) {
+    if ruta.is_file() {
+        log(&format!("Voz restaurada: {}", ruta.display()));
+        *VOZ_ELEGIDA.write() = Some(ruta);
+    } else {
+        log(&format!("La voz guardada {} ya no existe", ruta.display()));
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// HABLANTES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Hablante elegido en el tray para un modelo, si lo hay.
+pub fn hablante_elegido(modelo: &Path) -> Option<String> {
+    HABLANTES_ELEGIDOS.read().get(modelo).cloned()
+}
+
+/// Elige el hablante de un modelo desde el tray y lo recuerda.
+pub fn elegir_hablante(modelo: &Path, nombre: &str) {
+    HABLANTES_ELEGIDOS
+        .write()
+        .insert(modelo.to_path_buf(), nombre.to_string());
+    persistence::guardar_hablante(modelo, Some(nombre));
+    log(&format!(
+        "Hablante elegido para {}: {}",
+        modelo.display(),
+        nombre
+    ));
+}
+
+/// Olvida el hablante elegido en el tray para un modelo.
+pub fn olvidar_hablante(modelo: &Path) {
+    if HABLANTES_ELEGIDOS.write().remove(modelo).is_some() {
+        persistence::guardar_hablante(modelo, None);
+    }
+}
+
+/// Restaura los hablantes guardados al arrancar (ruta del modelo → nombre).
+pub fn restaurar_hablantes(guardados: &BTreeMap<String, String>) {
+    let mut elegidos = HABLANTES_ELEGIDOS.write();
+    for (modelo, nombre) in guardados {
+        elegidos.insert(PathBuf::from(modelo), nombre.clone());
+    }
+}
+
+/// ID de Piper para un hablante del modelo, por nombre o por número.
+///
+/// Devuelve `Ok(None)` si el modelo tiene un solo hablante (o no se pueden
+/// leer sus metadatos) y un error si no conoce ese hablante.
+pub fn id_hablante(modelo: &Path, nombre: &str) -> Result<Option<u32>, String> {
+    let Some(info) = info_voz(modelo) else {
+        return Ok(None);
+    };
+    if info.num_hablantes <= 1 {
+        return Ok(None);
+    }
+
+    info.hablantes
+        .iter()
+        .find(|h| h.nombre == nombre)
+        .map(|h| h.id)
+        .or_else(|| {
+            nombre
+                .parse::<u32>()
+                .ok()
+                .filter(|&id| (id as usize) < info.num_hablantes)
+        })
+        .map(Some)
+        .ok_or_else(|| format!("hablante desconocido en {}: '{}'", info.nombre, nombre))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(contenido: &str) -> InfoVoz {
+        parsear_info(Path::new("voces/en_US-libritts-high.onnx"), contenido).unwrap()
+    }
+
+    const MULTI_HABLANTE: &str = r#"{
+        "audio": { "sample_rate": 22050, "quality": "high" },
+        "dataset": "libritts",
+        "language": { "code": "en_US", "name_native": "English" },
+        "num_speakers": 3,
+        "speaker_id_map": { "p239": 2, "p225": 0, "p226": 1 },
+        "inference": { "noise_scale": 0.667, "length_scale": 1, "noise_w": 0.8 }
+    }"#;
+
+    #[test]
+    fn multi_hablante_e_inferencia() {
+        let voz = info(MULTI_HABLANTE);
+        assert_eq!(voz.nombre, "en_US-libritts-high");
+        assert_eq!(voz.etiqueta(), "libritts (high, 3 hablantes)");
+        assert_eq!(voz.etiqueta_idioma(), "English (en_US)");
+        assert_eq!(voz.frecuencia_muestreo, 22050);
+        // Ordenados por ID
+        let nombres: Vec<&str> = voz.hablantes.iter().map(|h| h.nombre.as_str()).collect();
+        assert_eq!(nombres, ["p225", "p226", "p239"]);
+        assert_eq!(
+            voz.prosodia,
+            Prosodia {
+                noise_scale: Some(0.667),
+                noise_w: Some(0.8),
+                sentence_silence: None,
+            }
+        );
+
+        // Sin nombres, los hablantes se eligen por número
+        let sin_nombres = info(r#"{ "num_speakers": 2 }"#);
+        let ids: Vec<(&str, u32)> = sin_nombres
+            .hablantes
+            .iter()
+            .map(|h| (h.nombre.as_str(), h.id))
+            .collect();
+        assert_eq!(ids, [("0", 0), ("1", 1)]);
+    }
+
+    #[test]
+    fn campos_ausentes() {
+        let voz = info("{}");
+        assert_eq!(voz.idioma, "?");
+        assert_eq!(voz.dataset, "en_US-libritts-high");
+        assert_eq!(voz.etiqueta(), "en_US-libritts-high");
+        assert_eq!((voz.calidad, voz.num_hablantes), (None, 1));
+        assert!(voz.hablantes.is_empty());
+        assert_eq!(voz.prosodia, Prosodia::default());
+
+        // Modelos antiguos: el idioma sale de la voz de espeak
+        let antiguo = info(r#"{ "espeak": { "voice": "es" }, "language": { "code": "" } }"#);
+        assert_eq!(antiguo.idioma, "es");
+        assert_eq!(antiguo.etiqueta_idioma(), "es");
+
+        assert!(parsear_info(Path::new("x.onnx"), "{ no es json").is_err());
+    }
+
+    #[test]
+    fn hablantes_de_un_modelo_fuera_del_catalogo() {
+        let dir =
+            std::env::temp_dir().join(format!("tray_narrator_prueba_{}_voces", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let modelo = dir.join("en_US-libritts-high.onnx");
+        let json = ruta_json_modelo(&modelo);
+        fs::write(&json, MULTI_HABLANTE).unwrap();
+
+        assert_eq!(info_voz(&modelo).map(|v| v.num_hablantes), Some(3));
+        assert_eq!(id_hablante(&modelo, "p226"), Ok(Some(1)));
+        assert_eq!(id_hablante(&modelo, "2"), Ok(Some(2)));
+        assert!(id_hablante(&modelo, "3").is_err());
+        assert!(id_hablante(&modelo, "p999").is_err());
+
+        // Sin `.onnx.json` no se usa lo leído antes
+        fs::remove_file(&json).unwrap();
+        assert_eq!(info_voz(&modelo), None);
+        assert_eq!(id_hablante(&modelo, "p226"), Ok(None));
+    }
+}